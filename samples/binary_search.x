@@ -5,14 +5,14 @@ fun main() {
     let target = 10;
     let result = binarySearch(array, 0, n - 1, target);
 
-    if result == -1 {
-        println "Element is not present in the array";
+    if let index = result {
+        println "Element is present at index ", index;
     } else {
-        println "Element is present at index ", result;
+        println "Element is not present in the array";
     }
 }
 
-fun binarySearch(array: [i32; 10], left: i32, right: i32, target: i32) -> i32 {
+fun binarySearch(array: [i32; 10], mut left: i32, mut right: i32, target: i32) -> ?i32 {
     while left <= right {
         let mid = left + (right - left) / 2;
 
@@ -27,5 +27,5 @@ fun binarySearch(array: [i32; 10], left: i32, right: i32, target: i32) -> i32 {
         }
     }
 
-    return -1;
+    return none;
 }
@@ -5,17 +5,16 @@ fun main() {
         1101001
     ];
 
-    for i in 0..3 {
-        let j = i + 0;
-        let result = convertToDecimal(bins[j]);
-        println "Bin (", bins[j], ") to decimal ", result;
+    for bin in bins {
+        let result = convertToDecimal(bin);
+        println "Bin (", bin, ") to decimal ", result;
     }
 }
 
 fun convertToDecimal(number: u64) -> u32 {
-    let n = number;
-    let i = 0;
-    let decimal = 0;
+    let mut n = number;
+    let mut i = 0;
+    let mut decimal = 0;
 
     while n > 0 {
         decimal += (n % 10) * pow(2, i);
@@ -1,6 +1,6 @@
 fun main() {
-    let visited: [bool; 100];
-    let graph: [[i32; 100]; 100];
+    let mut visited = [false; 100];
+    let mut graph = [[0; 100]; 100];
 
     let nodesAmount = 4;
     let edges = [
@@ -19,7 +19,7 @@ fun main() {
     dfs(0, nodesAmount, visited, graph);
 }
 
-fun dfs(node: i32, nodesAmount: i32, visited: [bool; 100], graph: [[i32; 100]; 100]) {
+fun dfs(node: i32, nodesAmount: i32, mut visited: [bool; 100], graph: [[i32; 100]; 100]) {
     visited[node] = true;
     println node;
 
@@ -5,22 +5,22 @@ fun main() {
         9
     ];
 
-    for i in 0..3 {
-        let result = fib(fibs[i]);
-        println "Fib (", fibs[i], "): ", result;
+    for n in fibs {
+        let result = fib(n);
+        println "Fib (", n, "): ", result;
     }
 }
 
 fun fib(n: i32) -> i32 {
-    let a = 0;
-    let b = 1;
-    let c: i32;
+    let mut a = 0;
+    let mut b = 1;
+    let mut c: i32;
 
     if n == 0 {
         return a;
     }
 
-    for i in 2..=n {
+    for _ in 2..=n {
         c = a + b;
         a = b;
         b = c;
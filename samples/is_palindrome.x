@@ -10,8 +10,8 @@ fun main() {
 }
 
 fun isPalindrome(str: string, size: i32) -> bool {
-    let left = 0;
-    let right = size - 1;
+    let mut left = 0;
+    let mut right = size - 1;
 
     while right > left {
         if str[left] != str[right] {
@@ -1,10 +1,10 @@
 fun main() {
-    let array = [9, 8, 7, 5, 6, 4, 1, 2, 3, 0];
+    let mut array = [9, 8, 7, 5, 6, 4, 1, 2, 3, 0];
 
     println "The given array is: ";
     printArray(array);
 
-    mergeSort(array, 0, 9);
+    mergeSort(array, 0, len(array) - 1);
 
     println;
     println "After sorting the result is: ";
@@ -12,7 +12,7 @@ fun main() {
     printArray(array);
 }
 
-fun mergeSort(array: [i32; 10], left: i32, right: i32) {
+fun mergeSort(mut array: [i32], left: i32, right: i32) {
     if left < right {
         let mid = left + (right - left) / 2;
 
@@ -23,12 +23,12 @@ fun mergeSort(array: [i32; 10], left: i32, right: i32) {
     }
 }
 
-fun merge(array: [i32; 10], left: i32, mid: i32, right: i32) {
+fun merge(mut array: [i32], left: i32, mid: i32, right: i32) {
     let n1 = mid - left + 1;
     let n2 = right - mid;
 
-    let l: [i32; 10];
-    let r: [i32; 10];
+    let mut l = [0; 10];
+    let mut r = [0; 10];
 
     for i in 0..n1 {
         l[i] = array[left + i];
@@ -38,9 +38,9 @@ fun merge(array: [i32; 10], left: i32, mid: i32, right: i32) {
         r[j] = array[mid + 1 + j];
     }
 
-    let i = 0;
-    let j = 0;
-    let k = left;
+    let mut i = 0;
+    let mut j = 0;
+    let mut k = left;
 
     while i < n1 && j < n2 {
         if l[i] <= r[j] {
@@ -66,11 +66,11 @@ fun merge(array: [i32; 10], left: i32, mid: i32, right: i32) {
     }
 }
 
-fun printArray(array: [i32; 10]) {
-    for i in 0..10 {
-        print array[i];
+fun printArray(array: [i32]) {
+    for i, value in array {
+        print value;
 
-        if i != 9 {
+        if i != len(array) - 1 {
             print " ";
         }
     }
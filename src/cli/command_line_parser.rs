@@ -28,7 +28,7 @@ impl CommandLineParser {
         for (i, mut key) in args.iter().enumerate() {
             key = self.short_options.get(key).unwrap_or(key);
 
-            if i + i > args.len() - 1 {
+            if i + 1 > args.len() - 1 {
                 parsed_options.insert(String::from(key), String::from(""));
                 continue;
             }
@@ -42,3 +42,27 @@ impl CommandLineParser {
         ParsedOptions::new(parsed_options)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::CommandLineParser;
+
+    fn parse(args: &[&str]) -> super::ParsedOptions {
+        let mut parser = CommandLineParser::new();
+        parser.add_option("-D", "--deny-warnings");
+
+        let args = args.iter().map(|arg| String::from(*arg)).collect();
+        parser.parse(&args)
+    }
+
+    #[test]
+    fn test_parse() {
+        let options = parse(&["compiler", "-D", "--compile", "main.x"]);
+        assert!(options.has("--deny-warnings"));
+        assert_eq!(options.get("--compile"), Ok("main.x"));
+
+        let options = parse(&["compiler", "--compile", "main.x", "-D"]);
+        assert!(options.has("--deny-warnings"));
+        assert_eq!(options.get("--compile"), Ok("main.x"));
+    }
+}
@@ -10,6 +10,7 @@ use super::{
 
 pub struct Compiler {
     code: String,
+    deny_warnings: bool,
 }
 
 impl Compiler {
@@ -22,9 +23,16 @@ impl Compiler {
     pub fn from_code(code: &str) -> Self {
         Self {
             code: String::from(code),
+            deny_warnings: false,
         }
     }
 
+    /// Makes the compilation fail when the semantic analysis reports any warning.
+    pub fn with_deny_warnings(mut self, deny_warnings: bool) -> Self {
+        self.deny_warnings = deny_warnings;
+        self
+    }
+
     pub fn compile(&self) -> Result<(), String> {
         let mut lexer = Lexer::new(&self.code);
         let tokens = lexer.lex()?;
@@ -34,18 +42,34 @@ impl Compiler {
 
         let analyzer = Analyzer::analyze(&ast);
 
+        for warning in &analyzer.warnings {
+            println!("{}", warning);
+        }
+
+        if self.deny_warnings && !analyzer.warnings.is_empty() && analyzer.diagnosis.is_empty() {
+            return Err(format!(
+                "Compilation failed due to {} warning(s) with '--deny-warnings' enabled",
+                analyzer.warnings.len()
+            ));
+        }
+
         if analyzer.diagnosis.len() > 0 {
             for error in &analyzer.diagnosis {
                 println!("{}", error);
             }
-        } else {
-            let mut generator = CCodeGenerator2::new(&ast, &analyzer.scopes);
-            let code = generator.generate();
 
-            let mut file = File::create("output.c").unwrap();
-            file.write_all(code.content().as_bytes()).unwrap();
+            return Err(format!(
+                "Compilation failed due to {} error(s)",
+                analyzer.diagnosis.len()
+            ));
         }
 
+        let mut generator = CCodeGenerator2::new(&ast, &analyzer.scopes);
+        let code = generator.generate();
+
+        let mut file = File::create("output.c").unwrap();
+        file.write_all(code.content().as_bytes()).unwrap();
+
         Ok(())
     }
 }
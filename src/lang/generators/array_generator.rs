@@ -2,8 +2,15 @@ use std::{cell::RefCell, rc::Rc};
 
 use crate::lang::{
     generators::expression_meta_generator::ExpressionMetaGenerator,
-    semantic::{scope::Scope, semantic_type::SemanticType},
-    syntax::expressions::expression::{Expression, ExpressionMeta},
+    position::Positioned,
+    semantic::{
+        expressions::expression_analyzer::ExpressionAnalyzer, scope::Scope,
+        semantic_type::SemanticType,
+    },
+    syntax::expressions::{
+        array::Array,
+        expression::{Expression, ExpressionMeta},
+    },
 };
 
 use super::{c_code_generator2::CCode, expression_generator::ExpressionGenerator};
@@ -25,16 +32,24 @@ impl ArrayGenerator {
         let expressions = Self::generate_expressions(expressions, Rc::clone(&scope), ccode);
 
         if let Some(meta) = meta {
-            format!(
-                "({}{}){{{}}}{}",
+            let code = format!(
+                "({}{}){{{}}}",
                 ccode.get_type(root_type),
                 dimensions
                     .iter()
                     .map(|d| format!("[{}]", d))
                     .collect::<Vec<String>>()
                     .join(""),
-                expressions.join(","),
-                ExpressionMetaGenerator::generate(meta, Rc::clone(&scope), ccode),
+                expressions.join(",")
+            );
+
+            ExpressionMetaGenerator::generate(
+                code,
+                array_type,
+                meta,
+                true,
+                Rc::clone(&scope),
+                ccode,
             )
         } else {
             format!(
@@ -50,30 +65,209 @@ impl ArrayGenerator {
         }
     }
 
+    /// Whether the array is written in the repeat form `[value; size]`, or holds an array that
+    /// is, so that it cannot be written as a single initializer.
+    pub fn has_repeat(array: &Array) -> bool {
+        array.size.is_some()
+            || array.expressions.iter().any(|expression| match expression {
+                Expression::Array(array, _) => Self::has_repeat(array),
+                _ => false,
+            })
+    }
+
+    /// Generates an array that is zeroed before the statement where it is written and then
+    /// receives a copy of each element. The value of the repeat form `[value; size]` is only
+    /// computed once.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - The array to be generated.
+    /// * `array_type` - The type of the array.
+    /// * `meta` - What is accessed on the array, such as an index.
+    /// * `scope` - A reference-counted reference to the scope in which it is written.
+    /// * `ccode` - Where the code is generated.
+    ///
+    /// # Returns
+    ///
+    /// The code that reads the array, such as `__repeat_3_17`.
+    pub fn generate_copied(
+        array: &Array,
+        array_type: &SemanticType,
+        meta: &Option<ExpressionMeta>,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        let position = array.get_position();
+        let name = format!("__repeat_{}_{}", position.line, position.column);
+
+        // The size of the repeat form is taken from the type, since it may be a size parameter.
+        let (element_type, size) = match array_type {
+            SemanticType::Array(element_type, size) => (element_type.as_ref().clone(), *size),
+            _ => (SemanticType::Any, 0),
+        };
+
+        let code = ccode.hoist_value(&name, array_type, |ccode| {
+            let root_type = Self::get_array_root_type(array_type);
+            let dimensions = Self::get_next_array_dimensions(array_type)
+                .iter()
+                .map(|d| format!("[{}]", d))
+                .collect::<String>();
+
+            let c_root_type = ccode.get_type(root_type);
+            ccode.push(&format!("{}=({}{}){{0}};", name, c_root_type, dimensions));
+
+            match &array.size {
+                Some(_) => {
+                    // Strings are arrays in C, so the value is kept as a pointer to the original.
+                    let c_element_type = match element_type {
+                        SemanticType::String => String::from("const char*"),
+                        _ => ccode.get_type(element_type.clone()),
+                    };
+
+                    let value = ExpressionGenerator::generate_moved(
+                        &array.expressions[0],
+                        Rc::clone(&scope),
+                        ccode,
+                    );
+                    let copy =
+                        Self::generate_copy(&name, "__index", "__value", &element_type, ccode);
+
+                    ccode.push(&format!(
+                        "{{{} __value={};for(signed int __index=0;__index<{};__index++){}}}",
+                        c_element_type, value, size, copy
+                    ));
+                }
+                None => {
+                    for (index, expression) in array.expressions.iter().enumerate() {
+                        let value = ExpressionGenerator::generate_moved(
+                            expression,
+                            Rc::clone(&scope),
+                            ccode,
+                        );
+                        let copy = Self::generate_copy(
+                            &name,
+                            &index.to_string(),
+                            &value,
+                            &element_type,
+                            ccode,
+                        );
+
+                        ccode.push(&copy);
+                    }
+                }
+            }
+        });
+
+        match meta {
+            Some(meta) => {
+                ExpressionMetaGenerator::generate(code, array_type, meta, true, scope, ccode)
+            }
+            None => code,
+        }
+    }
+
+    /// Generates the code that copies a value into an element of an array, where the elements
+    /// that are arrays in C, such as strings, cannot be assigned.
+    fn generate_copy(
+        name: &str,
+        index: &str,
+        value: &str,
+        element_type: &SemanticType,
+        ccode: &mut CCode,
+    ) -> String {
+        match element_type {
+            SemanticType::Array(..) => {
+                ccode.push_import("#include<string.h>");
+                format!(
+                    "memcpy({}[{}],{},sizeof({}[{}]));",
+                    name, index, value, name, index
+                )
+            }
+            _ => {
+                let store = ccode.assign(&format!("{}[{}]", name, index), value, element_type);
+                format!("{};", store)
+            }
+        }
+    }
+
+    /// Generates a copy of an array that is read from elsewhere, as arrays are pointers in C
+    /// and would otherwise share their elements with the original.
+    pub fn generate_copy_of(code: &str, array_type: &SemanticType, ccode: &mut CCode) -> String {
+        let c_type = ccode.get_type(array_type.clone());
+        let c_storage_type = Self::get_storage_type(array_type, ccode);
+
+        ccode.push_import("#include<string.h>");
+        format!(
+            "({})memcpy(({}){{0}},{},sizeof({}))",
+            c_type, c_storage_type, code, c_storage_type
+        )
+    }
+
+    /// Generates the code that copies the elements of an array into another one.
+    pub fn generate_assignment(
+        target: &str,
+        code: &str,
+        array_type: &SemanticType,
+        ccode: &mut CCode,
+    ) -> String {
+        let c_storage_type = Self::get_storage_type(array_type, ccode);
+
+        ccode.push_import("#include<string.h>");
+        format!("memmove({},{},sizeof({}));", target, code, c_storage_type)
+    }
+
+    /// Generates the declaration of a variable that holds all the elements of an array, such as
+    /// `int a[2][3]`.
+    pub fn generate_storage(name: &str, array_type: &SemanticType, ccode: &mut CCode) -> String {
+        let root_type = Self::get_array_root_type(array_type);
+        let dimensions = Self::get_next_array_dimensions(array_type)
+            .iter()
+            .map(|d| format!("[{}]", d))
+            .collect::<String>();
+
+        format!("{} {}{}", ccode.get_type(root_type), name, dimensions)
+    }
+
+    /// Generates the C type that holds all the elements of an array, such as `int[2][3]`.
+    fn get_storage_type(array_type: &SemanticType, ccode: &mut CCode) -> String {
+        let root_type = Self::get_array_root_type(array_type);
+        let dimensions = Self::get_next_array_dimensions(array_type)
+            .iter()
+            .map(|d| format!("[{}]", d))
+            .collect::<String>();
+
+        format!("{}{}", ccode.get_type(root_type), dimensions)
+    }
+
     fn generate_expressions(
         expressions: &Vec<Expression>,
         scope: Rc<RefCell<Scope>>,
         ccode: &mut CCode,
     ) -> Vec<String> {
-        let mut results: Vec<String> = vec![];
+        let mut elements: Vec<&Expression> = vec![];
+        Self::get_elements(expressions, &mut elements);
+
+        ExpressionGenerator::generate_in_order(
+            vec![],
+            &elements,
+            true,
+            ccode,
+            |_, expression, ccode| {
+                ExpressionGenerator::generate_moved(expression, Rc::clone(&scope), ccode)
+            },
+            |_, expression| ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type,
+        )
+    }
 
+    /// Gets the elements of an array along with the elements of the arrays written in it, which
+    /// are written as a single initializer.
+    fn get_elements<'e>(expressions: &'e [Expression], elements: &mut Vec<&'e Expression>) {
         for expression in expressions {
-            if let Expression::Array(array, ..) = expression {
-                results.extend(Self::generate_expressions(
-                    &array.expressions,
-                    Rc::clone(&scope),
-                    ccode,
-                ));
-            } else {
-                results.push(ExpressionGenerator::generate(
-                    expression,
-                    Rc::clone(&scope),
-                    ccode,
-                ));
+            match expression {
+                Expression::Array(array, ..) => Self::get_elements(&array.expressions, elements),
+                _ => elements.push(expression),
             }
         }
-
-        results
     }
 
     fn get_next_array_dimensions(r#type: &SemanticType) -> Vec<usize> {
@@ -1,121 +1,480 @@
-use std::rc::Rc;
+use std::{cell::RefCell, rc::Rc};
 
 use crate::lang::{
-    semantic::analyzer::Scopes,
-    syntax::{shared::block::Block, statements::statement::Statement},
+    lexer::token_kind::TokenKind,
+    position::{Position, Positioned},
+    semantic::{
+        analyzer::Scopes,
+        expressions::{
+            expression_analyzer::ExpressionAnalyzer, identifier_analyzer::IdentifierAnalyzer,
+        },
+        scope::Scope,
+        semantic_type::SemanticType,
+        statements::block_analyzer::BlockAnalyzer,
+        symbol::Symbol,
+    },
+    syntax::{
+        expressions::expression::Expression,
+        shared::{block::Block, identifier::Identifier},
+        statements::{r#break::Break, r#if::If, statement::Statement},
+    },
 };
 
 use super::{
-    c_code_generator2::CCode, expression_generator::ExpressionGenerator,
-    for_generator::ForGenerator, let_generator::LetGenerator, print_generator::PrintGenerator,
+    array_generator::ArrayGenerator, c_code_generator2::CCode,
+    expression_generator::ExpressionGenerator, for_generator::ForGenerator,
+    let_generator::LetGenerator, print_generator::PrintGenerator,
 };
 
 pub struct BlockGenerator;
 
 impl BlockGenerator {
     pub fn generate(block: &Block, scopes: &Scopes, ccode: &mut CCode) {
+        Self::generate_with_value(block, None, scopes, ccode);
+    }
+
+    /// Generates a block whose value, if any, is stored in the hidden variable with the given
+    /// name and type, as the blocks of `if` expressions do. Otherwise the value is discarded.
+    pub fn generate_with_value(
+        block: &Block,
+        value: Option<(&str, &SemanticType)>,
+        scopes: &Scopes,
+        ccode: &mut CCode,
+    ) {
         let scope = scopes.get(&block.id).unwrap().clone();
 
         ccode.push("{");
 
+        let is_loop = scope.borrow().is_loop_scope();
+        let is_function = scope.borrow().is_function_scope();
+        ccode.enter_frame(is_loop, is_function);
+
         for statement in &block.statements {
+            ccode.enter_statement();
+
             match statement {
                 Statement::Expression(expression) => {
                     let code = ExpressionGenerator::generate(expression, Rc::clone(&scope), ccode);
                     ccode.push(&code);
                     ccode.push(";");
                 }
-                Statement::For(r#for) => ForGenerator::generate(r#for, scopes, ccode),
+                Statement::For(r#for) => Self::generate_labeled(&r#for.label, ccode, |ccode| {
+                    ForGenerator::generate(r#for, scopes, ccode)
+                }),
                 Statement::Block(block) => Self::generate(block, scopes, ccode),
                 Statement::Let(r#let) => LetGenerator::generate(r#let, Rc::clone(&scope), ccode),
                 Statement::Print(print) => {
                     PrintGenerator::generate(print, Rc::clone(&scope), ccode)
                 }
-                Statement::Break(_) => {
-                    ccode.push("break;");
+                Statement::Defer(defer) => {
+                    // The deferred block is placed at every exit of the enclosing block, where
+                    // it runs before the values declared ahead of it are released.
+                    let code = ccode.capture(|ccode| Self::generate(&defer.block, scopes, ccode));
+                    ccode.add_drop(code);
                 }
-                Statement::Continue(_) => {
-                    ccode.push("continue;");
+                Statement::Break(r#break) => {
+                    if let Some(expression) = &r#break.expression {
+                        Self::generate_break_value(r#break, expression, Rc::clone(&scope), ccode);
+                    }
+
+                    match &r#break.label {
+                        Some(label) => {
+                            let (position, drops) = ccode.get_label_exit(&label.name);
+                            ccode.push(&drops);
+                            ccode.push(&format!(
+                                "goto __break_{}_{};",
+                                position.line, position.column
+                            ));
+                        }
+                        None => {
+                            let drops = ccode.get_loop_drops();
+                            ccode.push(&drops);
+                            ccode.push("break;");
+                        }
+                    }
                 }
+                Statement::Continue(r#continue) => match &r#continue.label {
+                    Some(label) => {
+                        let (position, drops) = ccode.get_label_exit(&label.name);
+                        ccode.push(&drops);
+                        ccode.push(&format!(
+                            "goto __continue_{}_{};",
+                            position.line, position.column
+                        ));
+                    }
+                    None => {
+                        let drops = ccode.get_loop_drops();
+                        ccode.push(&drops);
+                        ccode.push("continue;");
+                    }
+                },
+                Statement::Loop(r#loop) => Self::generate_labeled(&r#loop.label, ccode, |ccode| {
+                    ccode.push("while(1)");
+                    Self::generate_loop_body(&r#loop.block, &r#loop.label, scopes, ccode);
+                }),
                 Statement::DoWhile(do_while) => {
-                    ccode.push("do");
-                    Self::generate(&do_while.block, scopes, ccode);
-                    ccode.push("while(");
-                    let code = ExpressionGenerator::generate(
-                        &do_while.expression,
-                        Rc::clone(&scope),
-                        ccode,
-                    );
-                    ccode.push(&code);
-                    ccode.push(");");
+                    Self::generate_labeled(&do_while.label, ccode, |ccode| {
+                        ccode.push("do");
+                        Self::generate_loop_body(&do_while.block, &do_while.label, scopes, ccode);
+                        ccode.push("while(");
+                        let code = ExpressionGenerator::generate(
+                            &do_while.expression,
+                            Rc::clone(&scope),
+                            ccode,
+                        );
+                        ccode.push(&code);
+                        ccode.push(");");
+                    })
                 }
                 Statement::While(r#while) => {
-                    ccode.push("while(");
+                    Self::generate_labeled(&r#while.label, ccode, |ccode| {
+                        ccode.push("while(");
 
-                    let code = ExpressionGenerator::generate(
-                        &r#while.expression,
-                        Rc::clone(&scope),
-                        ccode,
-                    );
-                    ccode.push(&code);
+                        let code = ExpressionGenerator::generate(
+                            &r#while.expression,
+                            Rc::clone(&scope),
+                            ccode,
+                        );
+                        ccode.push(&code);
 
-                    ccode.push(")");
-                    Self::generate(&r#while.block, scopes, ccode);
+                        ccode.push(")");
+                        Self::generate_loop_body(&r#while.block, &r#while.label, scopes, ccode);
+                    })
                 }
                 Statement::If(r#if) => {
-                    ccode.push("if(");
-
-                    let code =
-                        ExpressionGenerator::generate(&r#if.expression, Rc::clone(&scope), ccode);
-                    ccode.push(&code);
-                    ccode.push(")");
-
-                    Self::generate(&r#if.block, scopes, ccode);
-
-                    match &r#if.r#else {
-                        None => {}
-                        Some(r#else) => {
-                            ccode.push("else");
-                            Self::generate(&r#else.block, scopes, ccode);
-                        }
-                    }
+                    Self::generate_if(r#if, None, scopes, Rc::clone(&scope), ccode)
                 }
                 Statement::Assignment(assignment) => {
-                    let left_code =
-                        ExpressionGenerator::generate(&assignment.left, Rc::clone(&scope), ccode);
+                    let (left_code, left_type) = match &assignment.left {
+                        // An optional is assigned as a whole even where it was checked against
+                        // none.
+                        Expression::Identifier(identifier, None)
+                            if assignment.operator.token.kind == TokenKind::Equals =>
+                        {
+                            let analyzer =
+                                IdentifierAnalyzer::analyze_assignee(identifier, Rc::clone(&scope));
 
-                    let right_code =
-                        ExpressionGenerator::generate(&assignment.right, Rc::clone(&scope), ccode);
+                            (
+                                ExpressionGenerator::generate_binding(
+                                    &identifier.name,
+                                    &analyzer.return_type,
+                                ),
+                                analyzer.return_type,
+                            )
+                        }
+                        left => (
+                            ExpressionGenerator::generate(left, Rc::clone(&scope), ccode),
+                            ExpressionAnalyzer::analyze(left, Rc::clone(&scope)).return_type,
+                        ),
+                    };
+
+                    let right_code = ExpressionGenerator::generate_owned(
+                        &assignment.right,
+                        &left_type,
+                        Rc::clone(&scope),
+                        ccode,
+                    );
+
+                    if left_type.is_owned() {
+                        // The new value is stored before the old one is freed, as it may be
+                        // copied from it.
+                        let c_type = ccode.get_type(left_type);
 
-                    ccode.push(&format!(
-                        "{}{}{};",
-                        left_code, assignment.operator.name, right_code
-                    ));
+                        ccode.push(&format!(
+                            "{{{} __value={};{}_free({});{}=__value;}}",
+                            c_type, right_code, c_type, left_code, left_code
+                        ));
+                    } else if let SemanticType::Array(..) = left_type {
+                        // Arrays are pointers in C, so the elements are copied instead of shared.
+                        let code = ArrayGenerator::generate_assignment(
+                            &left_code,
+                            &right_code,
+                            &left_type,
+                            ccode,
+                        );
+                        ccode.push(&code);
+                    } else {
+                        ccode.push(&format!(
+                            "{}{}{};",
+                            left_code, assignment.operator.name, right_code
+                        ));
+                    }
                 }
                 Statement::Return(r#return) => {
-                    if scope.borrow().get_function_name().unwrap() == "main" {
+                    let is_main = scope.borrow().get_function_name().unwrap() == "main"
+                        && !scope.borrow().is_lambda();
+
+                    if is_main {
+                        let drops = ccode.get_function_drops();
+                        ccode.push(&drops);
                         ccode.push("return 0;");
                     } else {
                         match &r#return.expression {
-                            None => ccode.push("return;"),
+                            None => {
+                                let drops = ccode.get_function_drops();
+                                ccode.push(&drops);
+                                ccode.push("return;");
+                            }
                             Some(expression) => {
-                                ccode.push("return ");
+                                let return_type = match scope.borrow().get_function_type() {
+                                    Some(SemanticType::Function(_, return_type)) => *return_type,
+                                    _ => SemanticType::Any,
+                                };
 
-                                let code = ExpressionGenerator::generate(
+                                let code = ExpressionGenerator::generate_owned(
                                     expression,
+                                    &return_type,
                                     Rc::clone(&scope),
                                     ccode,
                                 );
 
-                                ccode.push(&code);
-                                ccode.push(";");
+                                // The temporaries read by the value are released along with the
+                                // values owned by the blocks.
+                                let c_type = ccode.get_type(return_type);
+                                let r#return = ccode.get_return(&code, &c_type);
+                                ccode.push(&r#return);
                             }
                         }
                     }
                 }
             }
+
+            ccode.exit_statement();
+        }
+
+        // The value is computed before the values it may read are freed.
+        if let Some(expression) = &block.expression {
+            ccode.enter_statement();
+
+            match value {
+                Some((name, r#type)) => {
+                    Self::generate_value(name, r#type, expression, Rc::clone(&scope), ccode)
+                }
+                None => {
+                    let code = ExpressionGenerator::generate(expression, Rc::clone(&scope), ccode);
+                    ccode.push(&code);
+                    ccode.push(";");
+                }
+            }
+
+            ccode.exit_statement();
         }
 
+        let drops = ccode.exit_frame();
+        ccode.push(&drops);
         ccode.push("}");
     }
+
+    /// Generates a loop, which may be labeled. A labeled `break` jumps to the end of the loop,
+    /// as a plain one would only leave the innermost loop. Its labels are local to the block
+    /// around it, as the block of a `defer` is copied onto every exit of its own block.
+    pub fn generate_labeled(
+        label: &Option<Identifier>,
+        ccode: &mut CCode,
+        generate: impl FnOnce(&mut CCode),
+    ) {
+        match label {
+            Some(label) => {
+                let position = label.get_position();
+
+                ccode.push(&format!(
+                    "{{__label__ __break_{}_{},__continue_{}_{};",
+                    position.line, position.column, position.line, position.column
+                ));
+
+                ccode.enter_label(&label.name, position);
+                generate(ccode);
+                ccode.exit_label();
+
+                ccode.push(&format!(
+                    "__break_{}_{}:;}}",
+                    position.line, position.column
+                ));
+            }
+            None => generate(ccode),
+        }
+    }
+
+    /// Generates the body of a loop. A labeled `continue` jumps to the end of the body of a
+    /// labeled loop, after the values it owns are released.
+    pub fn generate_loop_body(
+        block: &Block,
+        label: &Option<Identifier>,
+        scopes: &Scopes,
+        ccode: &mut CCode,
+    ) {
+        match label {
+            Some(label) => {
+                let position = label.get_position();
+
+                ccode.push("{");
+                Self::generate(block, scopes, ccode);
+                ccode.push(&format!(
+                    "__continue_{}_{}:;}}",
+                    position.line, position.column
+                ));
+            }
+            None => Self::generate(block, scopes, ccode),
+        }
+    }
+
+    /// Generates a block written as an expression. Its statements are generated before the
+    /// statement where it is written, and its value is stored in a hidden variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - The block expression to be generated.
+    /// * `position` - The position of the block.
+    /// * `scope` - A reference-counted reference to the scope in which the block is written.
+    /// * `ccode` - Where the code is generated.
+    ///
+    /// # Returns
+    ///
+    /// The code of the value of the block, such as `__block_3_13`.
+    pub fn generate_expression(
+        block: &Block,
+        position: Position,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        let mut scopes = Scopes::new();
+        let return_type = BlockAnalyzer::analyze(block, scope, &mut scopes).return_type;
+
+        let block_scope = Rc::clone(&scopes[&block.id]);
+
+        // A block that only holds its value is the value itself.
+        if let Some(value) = block.get_value() {
+            return ExpressionGenerator::generate_owned(value, &return_type, block_scope, ccode);
+        }
+
+        let name = format!("__block_{}_{}", position.line, position.column);
+
+        ccode.hoist_value(&name, &return_type, |ccode| {
+            Self::generate_with_value(block, Some((&name, &return_type)), &scopes, ccode);
+        })
+    }
+
+    /// Generates an `if`, whose branches store their values in the hidden variable with the
+    /// given name and type when it is written as an expression.
+    pub fn generate_if(
+        r#if: &If,
+        value: Option<(&str, &SemanticType)>,
+        scopes: &Scopes,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) {
+        match &r#if.binding {
+            Some(binding) => Self::generate_if_let(r#if, binding, value, scopes, scope, ccode),
+            None => {
+                ccode.push("if(");
+
+                let code = ExpressionGenerator::generate(&r#if.expression, scope, ccode);
+                ccode.push(&code);
+                ccode.push(")");
+
+                Self::generate_with_value(&r#if.block, value, scopes, ccode);
+            }
+        }
+
+        if let Some(r#else) = &r#if.r#else {
+            ccode.push("else");
+            Self::generate_with_value(&r#else.block, value, scopes, ccode);
+        }
+    }
+
+    /// Generates the value given to a `break`, which is stored in the variable of the `loop`
+    /// expression it leaves before the values owned by the blocks left are released.
+    fn generate_break_value(
+        r#break: &Break,
+        expression: &Expression,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) {
+        let label = r#break.label.as_ref().map(|label| label.name.as_str());
+        let (name, r#type) = ccode.get_loop_value(label);
+
+        Self::generate_value(&name, &r#type, expression, scope, ccode);
+    }
+
+    /// Generates a value stored in the hidden variable of a `loop`, `if` or block expression,
+    /// which owns it.
+    fn generate_value(
+        name: &str,
+        r#type: &SemanticType,
+        expression: &Expression,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) {
+        let code = ExpressionGenerator::generate_owned(expression, r#type, scope, ccode);
+
+        match r#type {
+            SemanticType::Void | SemanticType::Any => ccode.push(&format!("{};", code)),
+            _ => {
+                let store = ccode.assign(name, &code, r#type);
+                ccode.push(&format!("{};", store));
+            }
+        }
+    }
+
+    /// Generates an `if let` statement. The optional or the result is kept in a hidden variable,
+    /// and its value is bound at the start of the block, which only runs when there is one.
+    fn generate_if_let(
+        r#if: &If,
+        binding: &Identifier,
+        value: Option<(&str, &SemanticType)>,
+        scopes: &Scopes,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) {
+        let optional_type =
+            ExpressionAnalyzer::analyze(&r#if.expression, Rc::clone(&scope)).return_type;
+        let c_optional_type = ccode.get_type(optional_type.clone());
+
+        let position = binding.get_position();
+        let optional_name = format!("__optional_{}_{}", position.line, position.column);
+
+        // The value is borrowed from the optional, which is released after the statement when
+        // it is a new one.
+        let code = ExpressionGenerator::generate(&r#if.expression, Rc::clone(&scope), ccode);
+        ccode.push(&format!("{} {}={};", c_optional_type, optional_name, code));
+
+        let tag = match optional_type {
+            SemanticType::Result(..) => "ok",
+            _ => "some",
+        };
+
+        ccode.push(&format!("if({}.{}){{", optional_name, tag));
+
+        let block_scope = Rc::clone(&scopes[&r#if.block.id]);
+        let symbol = block_scope.borrow().get(&binding.name);
+
+        if let Some(Symbol::Variable { symbol_type, .. }) = symbol {
+            let c_type = ccode.get_binding_type(symbol_type.clone(), false);
+            let value = format!("{}.value", optional_name);
+
+            let declaration = ccode.declare(&c_type, &binding.name, &value, &symbol_type);
+            ccode.push(&format!("{};", declaration));
+        }
+
+        Self::generate_with_value(&r#if.block, value, scopes, ccode);
+        ccode.push("}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::generators::test_helpers::generate;
+
+    #[test]
+    fn test_deferred_labels() {
+        // The deferred loop is copied onto both exits, each copy declaring its own labels.
+        let code = "fun f(n: i32) { defer { 'outer: for i in 0..3 { for j in 0..3 { if j == 1 { continue 'outer; } } } } if n > 0 { return; } } fun main() { f(1); }";
+        let c = generate(code);
+        assert_eq!(
+            c.matches("{__label__ __break_1_25,__continue_1_25;")
+                .count(),
+            2
+        );
+        assert_eq!(c.matches("__break_1_25:;}").count(), 2);
+    }
 }
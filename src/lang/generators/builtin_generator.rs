@@ -0,0 +1,427 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::lang::{
+    semantic::{
+        expressions::{
+            alloc_analyzer::AllocAnalyzer, builtin_analyzer::BuiltinAnalyzer,
+            expression_analyzer::ExpressionAnalyzer, identifier_analyzer::IdentifierAnalyzer,
+        },
+        scope::Scope,
+        semantic_type::SemanticType,
+        symbol::Symbol,
+    },
+    syntax::{
+        expressions::{
+            alloc::Alloc,
+            expression::{Expression, ExpressionMeta},
+        },
+        shared::identifier::Identifier,
+    },
+};
+
+use super::{
+    c_code_generator2::CCode, expression_generator::ExpressionGenerator,
+    expression_meta_generator::ExpressionMetaGenerator,
+};
+
+pub struct BuiltinGenerator;
+
+impl BuiltinGenerator {
+    pub fn generate(
+        identifier: &Identifier,
+        meta: &Option<ExpressionMeta>,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        let (expressions, meta, position) = match meta {
+            Some(ExpressionMeta::Call(expressions, meta, position)) => {
+                (expressions, meta, position)
+            }
+            _ => unreachable!(),
+        };
+
+        let code = match &identifier.name[..] {
+            "len" => Self::generate_len(&expressions[0], Rc::clone(&scope), ccode),
+            "free" => Self::generate_free(&expressions[0], Rc::clone(&scope), ccode),
+            "push" => Self::generate_push(expressions, Rc::clone(&scope), ccode),
+            "pop" => Self::generate_pop(&expressions[0], Rc::clone(&scope), ccode),
+            "ok" | "err" => {
+                // Without a result expected, the type that is not known is left as `any`.
+                let r#type = BuiltinAnalyzer::analyze_call(
+                    identifier,
+                    expressions,
+                    *position,
+                    Rc::clone(&scope),
+                )
+                .return_type;
+
+                Self::generate_result(identifier, expressions, &r#type, Rc::clone(&scope), ccode)
+            }
+            "error" => Self::generate_error(&expressions[0], Rc::clone(&scope), ccode),
+            "panic" => Self::generate_panic(&expressions[0], Rc::clone(&scope), ccode),
+            _ => unreachable!(),
+        };
+
+        if let Some(meta) = meta.as_ref() {
+            let return_type = BuiltinAnalyzer::analyze_call(
+                identifier,
+                expressions,
+                *position,
+                Rc::clone(&scope),
+            )
+            .return_type;
+
+            return ExpressionMetaGenerator::generate(
+                code,
+                &return_type,
+                meta,
+                true,
+                Rc::clone(&scope),
+                ccode,
+            );
+        }
+
+        code
+    }
+
+    /// Generates a call to `ok` or `err` as a value of the given result type.
+    pub fn generate_result(
+        identifier: &Identifier,
+        expressions: &[Expression],
+        r#type: &SemanticType,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        let c_type = ccode.get_type(r#type.clone());
+
+        let (value_type, error_type) = match r#type {
+            SemanticType::Result(value_type, error_type) => (value_type, error_type),
+            _ => unreachable!(),
+        };
+
+        // The result takes ownership of its value or its error.
+        match (&identifier.name[..], expressions) {
+            ("ok", []) => format!("{}_ok()", c_type),
+            ("ok", [expression]) => format!(
+                "{}_ok({})",
+                c_type,
+                ExpressionGenerator::generate_owned(expression, value_type, scope, ccode)
+            ),
+            (_, [expression]) => format!(
+                "{}_err({})",
+                c_type,
+                ExpressionGenerator::generate_owned(expression, error_type, scope, ccode)
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    fn generate_error(
+        expression: &Expression,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        let r#type = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type;
+        let c_type = ccode.get_type(r#type);
+
+        format!(
+            "{}_error({})",
+            c_type,
+            ExpressionGenerator::generate(expression, Rc::clone(&scope), ccode)
+        )
+    }
+
+    /// Declares the C function of the overload of a builtin function that is used, such as
+    /// `readLine` or `sqrtf`, if the symbol is a builtin function.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - The symbol the identifier refers to.
+    /// * `meta` - The meta that follows the identifier.
+    /// * `scope` - A reference-counted reference to the scope in which it is used.
+    /// * `ccode` - Where the function is declared.
+    ///
+    /// # Returns
+    ///
+    /// The name of the C function along with the type of the overload.
+    pub fn generate_function(
+        symbol: &Symbol,
+        meta: &Option<ExpressionMeta>,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> Option<(String, SemanticType)> {
+        let overload = IdentifierAnalyzer::resolve_overload(symbol, meta, scope)?.ok()?;
+        let c_return_type = ccode.get_type(overload.return_type.clone());
+
+        // The numbers are read as words, so that a word that is not a number is skipped. A line
+        // that does not fit in a string is skipped and read as none, as a number that does not
+        // fit in its type is. The arguments are named `a` and `b`.
+        let body = match overload.c_name {
+            "readLine" => concat!(
+                "o={0};",
+                "if(!fgets(o.value,sizeof(o.value),stdin))return o;",
+                "size_t n=strlen(o.value);",
+                "if(n&&o.value[n-1]=='\\n')o.value[--n]=0;",
+                "else{int c=getchar();if(c!='\\n'&&c!=EOF){",
+                "while((c=getchar())!='\\n'&&c!=EOF);",
+                "return o;}}",
+                "if(n&&o.value[n-1]=='\\r')o.value[--n]=0;",
+                "o.some=1;return o;"
+            ),
+            "readInt" => concat!(
+                "o={0};char s[256];char* end;",
+                "if(scanf(\"%255s\",s)!=1)return o;",
+                "errno=0;o.value=strtoll(s,&end,10);",
+                "o.some=*end==0&&errno==0;return o;"
+            ),
+            "readFloat" => concat!(
+                "o={0};char s[256];char* end;",
+                "if(scanf(\"%255s\",s)!=1)return o;",
+                "errno=0;o.value=strtod(s,&end);",
+                "o.some=*end==0&&errno==0;return o;"
+            ),
+            "__file_open" => concat!(
+                "r={0};FILE* f=fopen(a,b);",
+                "if(!f){strcpy(r.error,strerror(errno));return r;}",
+                "r.value=malloc(sizeof(*r.value));",
+                "if(!r.value){fclose(f);strcpy(r.error,strerror(ENOMEM));return r;}",
+                "r.value->refs=1;r.value->f=f;r.ok=1;return r;"
+            ),
+            // The content that does not fit in a string is reported instead of cut.
+            "__file_read_all" => concat!(
+                "r={0};FILE* f=a->f;",
+                "if(!f){strcpy(r.error,\"File is closed\");return r;}",
+                "size_t n=fread(r.value,1,sizeof(r.value)-1,f);",
+                "if(ferror(f)){strcpy(r.error,strerror(errno));return r;}",
+                "if(fgetc(f)!=EOF){strcpy(r.error,\"File too large to fit in a string\");return r;}",
+                "r.value[n]=0;r.ok=1;return r;"
+            ),
+            "__file_write_all" => concat!(
+                "r={0};FILE* f=a->f;",
+                "if(!f){strcpy(r.error,\"File is closed\");return r;}",
+                "if(fputs(b,f)==EOF||fflush(f)){strcpy(r.error,strerror(errno));return r;}",
+                "r.ok=1;return r;"
+            ),
+            "__file_append_line" => concat!(
+                "r={0};FILE* f=a->f;",
+                "if(!f){strcpy(r.error,\"File is closed\");return r;}",
+                "if(fputs(b,f)==EOF||fputc('\\n',f)==EOF||fflush(f)){",
+                "strcpy(r.error,strerror(errno));return r;}",
+                "r.ok=1;return r;"
+            ),
+            // The file is closed even if `fclose` fails, so it is never closed twice.
+            "__file_close" => concat!(
+                "r={0};FILE* f=a->f;",
+                "if(!f){strcpy(r.error,\"File is closed\");return r;}",
+                "a->f=0;",
+                "if(fclose(f)){strcpy(r.error,strerror(errno));return r;}",
+                "r.ok=1;return r;"
+            ),
+            // The integer overloads of `min` and `max`.
+            c_name if c_name.starts_with("__") => {
+                let operator = if c_name.starts_with("__min") { "<" } else { ">" };
+
+                ccode.push_typedef(format!(
+                    "{} {}({} a,{} b){{return a{}b?a:b;}}",
+                    c_return_type, c_name, c_return_type, c_return_type, operator
+                ));
+
+                return Some((String::from(c_name), overload.get_type()));
+            }
+            "abs" | "llabs" => {
+                ccode.push_import("#include<stdlib.h>");
+                return Some((String::from(overload.c_name), overload.get_type()));
+            }
+            c_name => {
+                ccode.push_import("#include<math.h>");
+                return Some((String::from(c_name), overload.get_type()));
+            }
+        };
+
+        ccode.push_import("#include<stdio.h>");
+        ccode.push_import("#include<stdlib.h>");
+        ccode.push_import("#include<string.h>");
+        ccode.push_import("#include<errno.h>");
+
+        let c_params: Vec<String> = overload
+            .params
+            .iter()
+            .zip(["a", "b"])
+            .map(|(param, name)| format!("{} {}", ccode.get_type(param.clone()), name))
+            .collect();
+
+        ccode.push_typedef(format!(
+            "{} {}({}){{{} {}}}",
+            c_return_type,
+            overload.c_name,
+            c_params.join(","),
+            c_return_type,
+            body
+        ));
+
+        Some((String::from(overload.c_name), overload.get_type()))
+    }
+
+    fn generate_panic(
+        expression: &Expression,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        ccode.push_import("#include<stdio.h>");
+        ccode.push_import("#include<stdlib.h>");
+
+        format!(
+            "(fprintf(stderr,\"%s\\n\",{}),exit(1))",
+            ExpressionGenerator::generate(expression, Rc::clone(&scope), ccode)
+        )
+    }
+
+    /// Generates the allocation of zeroed values on the heap, which ends the program when
+    /// there is no memory left for them.
+    pub fn generate_alloc(alloc: &Alloc, scope: Rc<RefCell<Scope>>, ccode: &mut CCode) -> String {
+        let r#type = AllocAnalyzer::analyze(alloc, Rc::clone(&scope)).return_type;
+        let c_type = ccode.get_type(r#type);
+
+        ccode.push_import("#include<stdio.h>");
+        ccode.push_import("#include<stdlib.h>");
+        ccode.push_typedef(String::from(
+            "void* __alloc(signed long long n,unsigned long long size){void* p=n>0?calloc(n,size):0;if(n>0&&!p){fprintf(stderr,\"Out of memory\\n\");exit(1);}return p;}",
+        ));
+
+        // The size of the values is the size of what the reference points to, as the type of
+        // an array value is itself a pointer.
+        format!(
+            "(({})__alloc({},sizeof(*({})0)))",
+            c_type,
+            ExpressionGenerator::generate(&alloc.count, Rc::clone(&scope), ccode),
+            c_type
+        )
+    }
+
+    fn generate_free(
+        expression: &Expression,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        ccode.push_import("#include<stdlib.h>");
+
+        format!(
+            "free({})",
+            ExpressionGenerator::generate(expression, Rc::clone(&scope), ccode)
+        )
+    }
+
+    fn generate_len(
+        expression: &Expression,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        let r#type = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type;
+
+        match r#type {
+            // The size of an array is always known at compile time.
+            SemanticType::Array(_, size) => size.to_string(),
+            SemanticType::Slice(_) => format!(
+                "((signed int)({}).len)",
+                ExpressionGenerator::generate(expression, Rc::clone(&scope), ccode)
+            ),
+            SemanticType::Vec(_) => format!(
+                "((signed int)({})->len)",
+                ExpressionGenerator::generate(expression, Rc::clone(&scope), ccode)
+            ),
+            SemanticType::String | SemanticType::CString => {
+                ccode.push_import("#include<string.h>");
+
+                format!(
+                    "((signed int)strlen({}))",
+                    ExpressionGenerator::generate(expression, Rc::clone(&scope), ccode)
+                )
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn generate_push(
+        expressions: &[Expression],
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        let r#type = ExpressionAnalyzer::analyze(&expressions[0], Rc::clone(&scope)).return_type;
+
+        let element_type = match &r#type {
+            SemanticType::Vec(element_type) => element_type.as_ref().clone(),
+            _ => unreachable!(),
+        };
+
+        let c_type = ccode.get_type(r#type.clone());
+        let vec = ExpressionGenerator::generate(&expressions[0], Rc::clone(&scope), ccode);
+
+        // The vector takes ownership of the value, so vectors pushed into it are copied.
+        let value = ExpressionGenerator::generate_owned(
+            &expressions[1],
+            &element_type,
+            Rc::clone(&scope),
+            ccode,
+        );
+
+        format!("{}_push({},{})", c_type, vec, value)
+    }
+
+    fn generate_pop(
+        expression: &Expression,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        let r#type = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type;
+        let c_type = ccode.get_type(r#type);
+
+        format!(
+            "{}_pop({})",
+            c_type,
+            ExpressionGenerator::generate(expression, Rc::clone(&scope), ccode)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::generators::test_helpers::generate;
+
+    #[test]
+    fn test_reads() {
+        let code = generate(
+            "fun main() { if let line = readLine() { println line; } \
+            if let n = readInt() { println n; } }",
+        );
+
+        // The line is read without its line break, and a line too long for a string or a word
+        // that is not a number is none.
+        assert!(code.contains("if(n&&o.value[n-1]=='\\n')o.value[--n]=0;"));
+        assert!(code.contains(
+            "else{int c=getchar();if(c!='\\n'&&c!=EOF){while((c=getchar())!='\\n'&&c!=EOF);return o;}}"
+        ));
+        assert!(code.contains("o.value=strtoll(s,&end,10);o.some=*end==0&&errno==0;"));
+        assert!(!code.contains("readFloat"));
+    }
+
+    #[test]
+    fn test_files() {
+        let code = generate(
+            "fun run() -> result<void, string> { let h = openFile(\"a.txt\", \"r\")?; \
+            close(h)?; return close(h); } fun main() { let r = run(); }",
+        );
+
+        // A closed file is reported instead of closed again.
+        let close = code.find("__file_close(__file a)").unwrap();
+        let checked = code[close..]
+            .find("if(!f){strcpy(r.error,\"File is closed\")")
+            .unwrap();
+        let cleared = code[close..].find("a->f=0;").unwrap();
+        let closed = code[close..].find("fclose(f)").unwrap();
+        assert!(checked < cleared && cleared < closed);
+
+        // The handle is released when the last copy is dropped.
+        assert!(code.contains("if(h&&!--h->refs){if(h->f)fclose(h->f);free(h);}"));
+        assert!(code.contains("__file_free(h);"));
+    }
+}
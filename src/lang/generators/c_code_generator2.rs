@@ -1,12 +1,17 @@
 use std::collections::BTreeSet;
-use std::collections::{hash_map::DefaultHasher, HashMap};
+use std::collections::{hash_map::DefaultHasher, HashMap, VecDeque};
 use std::hash::{Hash, Hasher};
 
+use crate::lang::position::Position;
 use crate::lang::semantic::analyzer::Scopes;
-use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::semantic_type::{SemanticType, TypeArguments};
+use crate::lang::semantic::top_level_statements::function_analyzer::FunctionAnalyzer;
+use crate::lang::semantic::top_level_statements::impl_analyzer::ImplAnalyzer;
 use crate::lang::syntax::compilation_unit::CompilationUnit;
+use crate::lang::syntax::top_level_statements::function::Function;
 use crate::lang::syntax::top_level_statements::top_level_statement::TopLevelStatement;
 
+use super::array_generator::ArrayGenerator;
 use super::function_generator::FunctionGenerator;
 
 fn hash_type(r#type: &SemanticType) -> u64 {
@@ -19,7 +24,7 @@ impl SemanticType {
     fn to_c_type(&self) -> String {
         match &self {
             SemanticType::Void => "void",
-            SemanticType::Char => "unsigned char",
+            SemanticType::Char => "unsigned int",
             SemanticType::Bool => "unsigned char",
             SemanticType::U8 => "unsigned char",
             SemanticType::I8 => "signed char",
@@ -38,23 +43,68 @@ impl SemanticType {
     }
 }
 
+/// A block of the generated code along with the values it owns, which are released when
+/// the block is left.
+#[derive(Clone, Debug)]
+struct Frame {
+    is_loop: bool,
+    is_function: bool,
+    drops: Vec<String>,
+    /// The new values read by the statement being generated, which are released after it.
+    temporaries: Vec<String>,
+}
+
+impl Frame {
+    /// Gets the code that releases the values owned by the block, the newest first.
+    fn get_drops(&self) -> String {
+        self.temporaries
+            .iter()
+            .rev()
+            .chain(self.drops.iter().rev())
+            .cloned()
+            .collect()
+    }
+}
+
 // REVIEW: Should we use a common Vec instead of a BTreeSet?
 
 #[derive(Clone, Debug)]
 pub struct CCode {
     content: String,
+    functions: String,
     imports: BTreeSet<String>,
     typedefs: Vec<String>,
     types_map: HashMap<u64, String>,
+    frames: Vec<Frame>,
+    /// The labeled loops being generated, with the positions of their labels and the number of
+    /// frames around them.
+    labels: Vec<(String, Position, usize)>,
+    /// The `loop` expressions being generated, with their labels, the variables that receive
+    /// their values and their types.
+    loop_values: Vec<(Option<String>, String, SemanticType)>,
+    /// The statements being generated, with where each starts and the code hoisted before it.
+    statements: Vec<(usize, String)>,
+    /// How many hidden variables keep the operands evaluated before a hoisted one.
+    operands: usize,
+    instances: BTreeSet<String>,
+    pending_instances: VecDeque<(String, String, TypeArguments)>,
 }
 
 impl CCode {
     pub fn new() -> Self {
         Self {
             content: String::new(),
+            functions: String::new(),
             imports: BTreeSet::new(),
             typedefs: Vec::new(),
             types_map: HashMap::new(),
+            frames: Vec::new(),
+            labels: Vec::new(),
+            loop_values: Vec::new(),
+            statements: Vec::new(),
+            operands: 0,
+            instances: BTreeSet::new(),
+            pending_instances: VecDeque::new(),
         }
     }
 
@@ -73,7 +123,10 @@ impl CCode {
             .collect::<Vec<String>>()
             .join("");
 
-        format!("{}\n{}{}", imports, typedefs, self.content)
+        format!(
+            "{}\n{}{}{}",
+            imports, typedefs, self.content, self.functions
+        )
     }
 
     pub fn pop(&mut self) {
@@ -88,6 +141,559 @@ impl CCode {
         self.imports.insert(String::from(import));
     }
 
+    /// Declares a type or a prototype, returning whether it was not declared yet.
+    pub fn push_typedef(&mut self, typedef: String) -> bool {
+        if self.typedefs.contains(&typedef) {
+            return false;
+        }
+
+        self.typedefs.push(typedef);
+        true
+    }
+
+    /// Generates a function apart from the code being generated, such as the body of a lambda
+    /// found in the middle of an expression. The function is placed after the others, so its
+    /// prototype has to be declared with `push_typedef`.
+    pub fn push_function(&mut self, generate: impl FnOnce(&mut CCode)) {
+        let content = std::mem::take(&mut self.content);
+        let frames = std::mem::take(&mut self.frames);
+        let labels = std::mem::take(&mut self.labels);
+        let loop_values = std::mem::take(&mut self.loop_values);
+        let statements = std::mem::take(&mut self.statements);
+
+        generate(self);
+
+        let function = std::mem::replace(&mut self.content, content);
+        self.frames = frames;
+        self.labels = labels;
+        self.loop_values = loop_values;
+        self.statements = statements;
+        self.functions.push_str(&function);
+    }
+
+    /// Gets the value of a named function used as a value of a function type. As function
+    /// values carry the values captured by lambdas, the function is called through a wrapper
+    /// that ignores them.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the function.
+    /// * `function_type` - The type of the function.
+    ///
+    /// # Returns
+    ///
+    /// The code of the function value, such as `((__fn_1){0,__function_add})`.
+    pub fn get_function_value(&mut self, name: &str, function_type: &SemanticType) -> String {
+        let c_type = self.get_type(function_type.clone());
+        let wrapper = format!("__function_{}", name);
+
+        if let SemanticType::Function(params, return_type) = function_type {
+            let c_return_type = self.get_type(return_type.as_ref().clone());
+
+            let c_params: Vec<String> = params
+                .iter()
+                .enumerate()
+                .map(|(index, param)| format!("{} a{}", self.get_type(param.clone()), index))
+                .collect();
+
+            let arguments: Vec<String> = (0..params.len())
+                .map(|index| format!("a{}", index))
+                .collect();
+
+            let prototype = format!(
+                "{} {}(void* env{})",
+                c_return_type,
+                wrapper,
+                c_params
+                    .iter()
+                    .map(|param| format!(",{}", param))
+                    .collect::<String>()
+            );
+
+            if self.push_typedef(format!("{};", prototype)) {
+                let r#return = if return_type.as_ref() == &SemanticType::Void {
+                    ""
+                } else {
+                    "return "
+                };
+
+                self.functions.push_str(&format!(
+                    "{}{{{}{}({});}}",
+                    prototype,
+                    r#return,
+                    name,
+                    arguments.join(",")
+                ));
+            }
+        }
+
+        format!("(({}){{0,{}}})", c_type, wrapper)
+    }
+
+    /// Generates the code that stores a value. Strings are arrays in C, so they are copied
+    /// instead of assigned.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Where the value is stored, such as `t.e0`.
+    /// * `value` - The code of the value.
+    /// * `r#type` - The type of the value.
+    ///
+    /// # Returns
+    ///
+    /// The code that stores the value, such as `strcpy(t.e0,e0)`.
+    pub fn assign(&mut self, target: &str, value: &str, r#type: &SemanticType) -> String {
+        match r#type {
+            SemanticType::String => {
+                self.push_import("#include<string.h>");
+                format!("strcpy({},{})", target, value)
+            }
+            _ => format!("{}={}", target, value),
+        }
+    }
+
+    /// Generates the declaration of a variable that starts with a value, which is stored after
+    /// the declaration when it cannot be used to initialize it, as with strings.
+    ///
+    /// # Returns
+    ///
+    /// The code of the declaration, such as `__string name;strcpy(name,value)`.
+    pub fn declare(
+        &mut self,
+        c_type: &str,
+        name: &str,
+        value: &str,
+        r#type: &SemanticType,
+    ) -> String {
+        match r#type {
+            SemanticType::String => {
+                format!("{} {};{}", c_type, name, self.assign(name, value, r#type))
+            }
+            _ => format!("{} {}={}", c_type, name, value),
+        }
+    }
+
+    /// Escapes a text to be written between the quotes of a C string, where the characters that
+    /// are not printable ASCII are written as the octal escapes of their UTF-8 bytes.
+    pub fn escape_string(text: &str) -> String {
+        let mut escaped = String::new();
+
+        for byte in text.bytes() {
+            match byte {
+                b'"' | b'\\' => {
+                    escaped.push('\\');
+                    escaped.push(byte as char);
+                }
+                b' '..=b'~' => escaped.push(byte as char),
+                _ => escaped.push_str(&format!("\\{:03o}", byte)),
+            }
+        }
+
+        escaped
+    }
+
+    /// Gets the name of a function in C. The names are prefixed, so that they do not clash with
+    /// the functions of C, except for `main`. Methods, such as `vec<i32>.push`, and instances of
+    /// generic functions, such as `max<i32>`, have prefixes of their own, and the types in their
+    /// names are escaped, so that no two names are the same in C.
+    ///
+    /// # Examples
+    ///
+    /// `max` is `__x_max`, `vec<i32>.push` is `__m_vec_3ci32_3e__push` and `max<i32>` is
+    /// `__g_max__i32`.
+    pub fn get_function_name(name: &str) -> String {
+        if name == "main" {
+            return String::from(name);
+        }
+
+        if let Some((r#type, method)) = name.rsplit_once('.') {
+            return format!("__m_{}__{}", Self::escape_name(r#type), method);
+        }
+
+        match name.split_once('<') {
+            Some((function, arguments)) => format!(
+                "__g_{}__{}",
+                Self::escape_name(function),
+                Self::escape_name(arguments.strip_suffix('>').unwrap_or(arguments))
+            ),
+            None => format!("__x_{}", name),
+        }
+    }
+
+    /// Escapes a name to be part of an identifier, keeping letters and digits and writing any
+    /// other character, including `_`, as `_` followed by its hexadecimal code. An escaped name
+    /// never has two `_` in a row, so it can be told apart from what follows it.
+    fn escape_name(name: &str) -> String {
+        name.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_string()
+                } else {
+                    format!("_{:02x}", c as u32)
+                }
+            })
+            .collect()
+    }
+
+    /// Gets the name of a function that calls a method on a value it takes by value, for the
+    /// values that are not bound anywhere, as the method takes the value by reference.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the method in C.
+    /// * `function_type` - The type of the method, whose first parameter is the value.
+    ///
+    /// # Returns
+    ///
+    /// The name of the function, such as `__v__m_vec_3ci32_3e__sum`.
+    pub fn get_value_method(&mut self, name: &str, function_type: &SemanticType) -> String {
+        let wrapper = format!("__v{}", name);
+
+        if let SemanticType::Function(params, return_type) = function_type {
+            let c_return_type = self.get_type(return_type.as_ref().clone());
+
+            let c_params: Vec<String> = params
+                .iter()
+                .enumerate()
+                .map(|(index, param)| format!("{} a{}", self.get_type(param.clone()), index))
+                .collect();
+
+            let arguments: Vec<String> = (1..params.len())
+                .map(|index| format!(",a{}", index))
+                .collect();
+
+            let prototype = format!("{} {}({})", c_return_type, wrapper, c_params.join(","));
+
+            if self.push_typedef(format!("{};", prototype)) {
+                let r#return = if return_type.as_ref() == &SemanticType::Void {
+                    ""
+                } else {
+                    "return "
+                };
+
+                self.functions.push_str(&format!(
+                    "{}{{{}{}(&a0{});}}",
+                    prototype,
+                    r#return,
+                    name,
+                    arguments.concat()
+                ));
+            }
+        }
+
+        wrapper
+    }
+
+    /// Gets the name of the instance of a generic function for the given type arguments,
+    /// declaring it the first time it is used so it is generated later.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the generic function.
+    /// * `type_arguments` - The types given to its type parameters.
+    /// * `instance_type` - The type of the function with the type arguments applied.
+    ///
+    /// # Returns
+    ///
+    /// The name of the instance, such as `max<i32>` or `sum<3>`.
+    pub fn request_instance(
+        &mut self,
+        name: &str,
+        type_arguments: TypeArguments,
+        instance_type: &SemanticType,
+    ) -> String {
+        let arguments: Vec<String> = type_arguments
+            .types
+            .values()
+            .map(|r#type| r#type.to_string())
+            .chain(type_arguments.sizes.values().map(|size| size.to_string()))
+            .collect();
+
+        let instance_name = format!("{}<{}>", name, arguments.join(", "));
+
+        if self.instances.insert(instance_name.clone()) {
+            if let SemanticType::Function(params, return_type) = instance_type {
+                let c_return_type = self.get_type(return_type.as_ref().clone());
+
+                let c_params: Vec<String> = params
+                    .iter()
+                    .map(|param| self.get_type(param.clone()))
+                    .collect();
+
+                self.typedefs.push(format!(
+                    "{} {}({});",
+                    c_return_type,
+                    Self::get_function_name(&instance_name),
+                    c_params.join(",")
+                ));
+            }
+
+            self.pending_instances.push_back((
+                String::from(name),
+                instance_name.clone(),
+                type_arguments,
+            ));
+        }
+
+        instance_name
+    }
+
+    /// Takes the next instance of a generic function that still has to be generated, as the
+    /// name of the function, the name of the instance and the type arguments.
+    pub fn next_instance(&mut self) -> Option<(String, String, TypeArguments)> {
+        self.pending_instances.pop_front()
+    }
+
+    /// Starts a block that may own values, such as vectors.
+    pub fn enter_frame(&mut self, is_loop: bool, is_function: bool) {
+        self.frames.push(Frame {
+            is_loop,
+            is_function,
+            drops: vec![],
+            temporaries: vec![],
+        });
+    }
+
+    /// Finishes the current block, returning the code that releases the values it owns.
+    pub fn exit_frame(&mut self) -> String {
+        let frame = self.frames.pop().unwrap();
+        frame.drops.iter().rev().cloned().collect()
+    }
+
+    /// Starts a statement, before which the code hoisted out of its expressions is placed.
+    pub fn enter_statement(&mut self) {
+        self.statements.push((self.content.len(), String::new()));
+    }
+
+    /// Finishes the current statement, placing the code hoisted out of it before it and the
+    /// code that releases its temporaries after it.
+    pub fn exit_statement(&mut self) {
+        let (start, hoisted) = self.statements.pop().unwrap();
+        self.content.insert_str(start, &hoisted);
+
+        let temporaries = std::mem::take(&mut self.frames.last_mut().unwrap().temporaries);
+        self.content
+            .extend(temporaries.iter().rev().map(String::as_str));
+    }
+
+    /// Places code before the statement being generated, such as the check made by a `?`
+    /// in the middle of an expression.
+    pub fn hoist(&mut self, code: &str) {
+        self.statements.last_mut().unwrap().1.push_str(code);
+    }
+
+    /// Keeps operands in hidden variables before the statement being generated. The code
+    /// hoisted out of an operand, as by a `?`, runs before the whole expression, so the operands
+    /// written before it would otherwise be evaluated after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `operands` - The code and the type of the operands, which are replaced by the reads of
+    ///   the hidden variables.
+    /// * `is_moved` - Whether the values of the operands are moved into the expression, as the
+    ///   elements of a tuple are. A kept value that owns memory is then released after the
+    ///   statement, like a temporary, and a copy of it is moved instead, so that it is not lost
+    ///   when the hoisted code returns early.
+    pub fn keep_operands(&mut self, operands: &mut [(String, SemanticType)], is_moved: bool) {
+        for (code, r#type) in operands.iter_mut() {
+            if matches!(r#type, SemanticType::Void | SemanticType::Any) {
+                continue;
+            }
+
+            self.operands += 1;
+            let name = format!("__operand_{}", self.operands);
+            let c_type = self.get_type(r#type.clone());
+
+            if is_moved && r#type.is_owned() {
+                let store = self.add_temporary(&name, r#type, code);
+                self.hoist(&format!("{};", store));
+
+                *code = format!("{}_copy({})", c_type, name);
+            } else {
+                let declaration = self.declare(&c_type, &name, code, r#type);
+                self.hoist(&format!("{};", declaration));
+
+                *code = name;
+            }
+        }
+    }
+
+    /// Generates code before the statement being generated that stores a value in a hidden
+    /// variable, such as the value of a `loop` in the middle of an expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the hidden variable.
+    /// * `r#type` - The type of the value, which is not stored when there is none.
+    /// * `generate` - Generates the code that stores the value.
+    ///
+    /// # Returns
+    ///
+    /// The code that reads the value.
+    pub fn hoist_value(
+        &mut self,
+        name: &str,
+        r#type: &SemanticType,
+        generate: impl FnOnce(&mut CCode),
+    ) -> String {
+        let content = std::mem::take(&mut self.content);
+        let has_value = !matches!(r#type, SemanticType::Void | SemanticType::Any);
+
+        if has_value {
+            let c_type = self.get_type(r#type.clone());
+            self.push(&format!("{} {};", c_type, name));
+        }
+
+        generate(self);
+
+        let code = std::mem::replace(&mut self.content, content);
+        self.hoist(&code);
+
+        if has_value {
+            String::from(name)
+        } else {
+            String::from("(void)0")
+        }
+    }
+
+    /// Keeps a new value that is only read by the statement being generated, such as a vector
+    /// passed to a function, in a hidden variable declared before the statement and released
+    /// after it. The value is stored where it is computed, so that it can be computed more than
+    /// once, as in a loop condition, where the previous one is released first.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the hidden variable.
+    /// * `r#type` - The type of the value, which owns memory.
+    /// * `code` - The code that computes the value.
+    ///
+    /// # Returns
+    ///
+    /// The code that computes the value and stores it, such as
+    /// `(__vec_1_free(__temporary_3_7),__temporary_3_7=__x_make(5))`.
+    pub fn add_temporary(&mut self, name: &str, r#type: &SemanticType, code: &str) -> String {
+        let c_type = self.get_type(r#type.clone());
+
+        // A value that was not stored yet has nothing to be released.
+        self.hoist(&format!("{} {}={{0}};", c_type, name));
+        self.frames
+            .last_mut()
+            .unwrap()
+            .temporaries
+            .push(format!("{}_free({});", c_type, name));
+
+        format!("({}_free({}),{}={})", c_type, name, name, code)
+    }
+
+    /// Generates code apart from the current content, returning it instead, as the code of a
+    /// `defer`, which is placed wherever its block is left.
+    pub fn capture(&mut self, generate: impl FnOnce(&mut CCode)) -> String {
+        let content = std::mem::take(&mut self.content);
+        generate(self);
+        std::mem::replace(&mut self.content, content)
+    }
+
+    /// Registers the code that releases a value owned by the current block.
+    pub fn add_drop(&mut self, drop: String) {
+        self.frames.last_mut().unwrap().drops.push(drop);
+    }
+
+    /// Gets the code that releases the values owned by the blocks left by a `break` or
+    /// `continue`, up to the innermost loop.
+    pub fn get_loop_drops(&self) -> String {
+        self.get_drops_until(|frame| frame.is_loop)
+    }
+
+    /// Starts a labeled loop, whose body is the next block.
+    pub fn enter_label(&mut self, name: &str, position: Position) {
+        self.labels
+            .push((String::from(name), position, self.frames.len()));
+    }
+
+    /// Finishes the innermost labeled loop.
+    pub fn exit_label(&mut self) {
+        self.labels.pop();
+    }
+
+    /// Starts a `loop` expression, whose value is stored in the variable with the given name.
+    pub fn enter_loop_value(&mut self, label: Option<&str>, name: &str, r#type: SemanticType) {
+        self.loop_values
+            .push((label.map(String::from), String::from(name), r#type));
+    }
+
+    /// Finishes the innermost `loop` expression.
+    pub fn exit_loop_value(&mut self) {
+        self.loop_values.pop();
+    }
+
+    /// Gets the variable that receives the value of the `loop` expression with the given label,
+    /// or the innermost one, along with its type.
+    pub fn get_loop_value(&self, label: Option<&str>) -> (String, SemanticType) {
+        let (_, name, r#type) = match label {
+            Some(label) => self
+                .loop_values
+                .iter()
+                .rev()
+                .find(|(loop_label, _, _)| loop_label.as_deref() == Some(label))
+                .unwrap(),
+            None => self.loop_values.last().unwrap(),
+        };
+
+        (name.clone(), r#type.clone())
+    }
+
+    /// Gets the position of the innermost loop with the given label, which names the C labels
+    /// its `break` and `continue` jump to, along with the code that releases the values owned
+    /// by the blocks left to reach them.
+    pub fn get_label_exit(&self, name: &str) -> (Position, String) {
+        let (_, position, depth) = self
+            .labels
+            .iter()
+            .rev()
+            .find(|(label, _, _)| label == name)
+            .unwrap();
+
+        let drops = self.frames[*depth..]
+            .iter()
+            .rev()
+            .map(|frame| frame.get_drops())
+            .collect();
+
+        (*position, drops)
+    }
+
+    /// Gets the code that releases the values owned by the blocks left by a `return`.
+    pub fn get_function_drops(&self) -> String {
+        self.get_drops_until(|frame| frame.is_function)
+    }
+
+    /// Gets the statement that returns a value of the given C type from the current function,
+    /// releasing the values owned by its blocks. The value is computed before the values it may
+    /// read are freed.
+    pub fn get_return(&self, code: &str, c_type: &str) -> String {
+        let drops = self.get_function_drops();
+
+        if drops.is_empty() {
+            format!("return {};", code)
+        } else {
+            format!("{{{} __return={};{}return __return;}}", c_type, code, drops)
+        }
+    }
+
+    fn get_drops_until(&self, is_last: impl Fn(&Frame) -> bool) -> String {
+        let mut drops = String::new();
+
+        for frame in self.frames.iter().rev() {
+            drops.push_str(&frame.get_drops());
+
+            if is_last(frame) {
+                break;
+            }
+        }
+
+        drops
+    }
+
     pub fn get_type(&mut self, r#type: SemanticType) -> String {
         let hash = hash_type(&r#type);
 
@@ -101,6 +707,51 @@ impl CCode {
 
                 self.types_map.insert(hash, "__string".to_string());
             }
+            SemanticType::Range => {
+                // A range keeps its bounds along with the step, whose sign gives the direction.
+                let values = [
+                    "typedef struct{signed long long start;signed long long end;signed long long step;unsigned char inclusive;}__range;",
+                    "unsigned char __range_continues(__range r,signed long long x){if(r.step==0)return 0;return r.step>0?(r.inclusive?x<=r.end:x<r.end):(r.inclusive?x>=r.end:x>r.end);}",
+                    "unsigned char __range_contains(__range r,signed long long x){if(r.step==0||(r.step>0?x<r.start:x>r.start))return 0;return __range_continues(r,x)&&(x-r.start)%r.step==0;}",
+                ];
+
+                for value in values {
+                    let value = String::from(value);
+
+                    if !self.typedefs.contains(&value) {
+                        self.typedefs.push(value);
+                    }
+                }
+
+                self.types_map.insert(hash, "__range".to_string());
+            }
+            SemanticType::CString => {
+                self.types_map.insert(hash, "const char*".to_string());
+            }
+            SemanticType::File => {
+                self.push_import("#include<stdio.h>");
+                self.push_import("#include<stdlib.h>");
+
+                // A file is shared by its copies, so that closing it through one of them is seen
+                // by the others. It is closed, if it was not yet, when the last copy is dropped.
+                let values = [
+                    "typedef struct{unsigned long long refs;FILE* f;}* __file;",
+                    "__file __file_copy(__file h){if(h)h->refs++;return h;}",
+                    "void __file_free(__file h){if(h&&!--h->refs){if(h->f)fclose(h->f);free(h);}}",
+                ];
+
+                for value in values {
+                    self.push_typedef(String::from(value));
+                }
+
+                self.types_map.insert(hash, "__file".to_string());
+            }
+            SemanticType::Ref(r#type) => {
+                // A reference points to the first of its values the same way an array points
+                // to its elements, so that they are indexed the same way.
+                let c_type = self.get_type(SemanticType::Array(r#type.clone(), 0));
+                self.types_map.insert(hash, c_type);
+            }
             SemanticType::Function(params, return_type) => {
                 let c_return_type = self.get_type(return_type.as_ref().clone());
 
@@ -111,15 +762,56 @@ impl CCode {
 
                 let alias = format!("__fn_{hash}");
 
-                let value = format!(
-                    "typedef {} (*{})({});",
-                    c_return_type,
-                    alias,
-                    c_params.join(",")
-                );
+                // A function value is called along with the values captured by a lambda, which
+                // named functions do not have. The captured values are shared by the copies of
+                // the function value, so they are released when the last copy is dropped.
+                self.push_typedef(String::from(
+                    "typedef struct{unsigned long long refs;void(*free)(void*);}__env_header;",
+                ));
 
-                if !self.typedefs.contains(&value) {
-                    self.typedefs.push(value);
+                let arguments: Vec<String> = (0..params.len())
+                    .map(|index| format!(",a{}", index))
+                    .collect();
+
+                let r#return = if return_type.as_ref() == &SemanticType::Void {
+                    ""
+                } else {
+                    "return "
+                };
+
+                let values = [
+                    format!(
+                        "typedef struct{{void* env;{} (*call)(void*{});}}{};",
+                        c_return_type,
+                        c_params
+                            .iter()
+                            .map(|param| format!(",{}", param))
+                            .collect::<String>(),
+                        alias
+                    ),
+                    format!(
+                        "{} {}_call({} f{}){{{}f.call(f.env{});}}",
+                        c_return_type,
+                        alias,
+                        alias,
+                        c_params
+                            .iter()
+                            .enumerate()
+                            .map(|(index, param)| format!(",{} a{}", param, index))
+                            .collect::<String>(),
+                        r#return,
+                        arguments.join("")
+                    ),
+                    format!(
+                        "{alias} {alias}_copy({alias} f){{if(f.env)((__env_header*)f.env)->refs++;return f;}}"
+                    ),
+                    format!(
+                        "void {alias}_free({alias} f){{__env_header* env=f.env;if(env&&!--env->refs)env->free(env);}}"
+                    ),
+                ];
+
+                for value in values {
+                    self.push_typedef(value);
                 }
 
                 self.types_map.insert(hash, alias);
@@ -143,6 +835,246 @@ impl CCode {
 
                 self.types_map.insert(hash, alias);
             }
+            SemanticType::Vec(element_type) => {
+                let alias = format!("__vec_{hash}");
+                let c_element_type = self.get_type(element_type.as_ref().clone());
+
+                self.push_import("#include<stdio.h>");
+                self.push_import("#include<stdlib.h>");
+
+                // Strings are arrays in C, so they are received as pointers to be copied.
+                let c_value_type = match element_type.as_ref() {
+                    SemanticType::String => "char*",
+                    _ => c_element_type.as_str(),
+                };
+
+                // An array is a pointer in C, so its elements are stored in the vector instead.
+                let (c_data_type, store) = match element_type.as_ref() {
+                    SemanticType::Array(..) => {
+                        let c_data_type = format!("{alias}_element");
+                        let storage =
+                            ArrayGenerator::generate_storage(&c_data_type, element_type, self);
+                        self.push_typedef(format!("typedef {};", storage));
+
+                        let store = ArrayGenerator::generate_assignment(
+                            "v->data[v->len]",
+                            "value",
+                            element_type,
+                            self,
+                        );
+                        (c_data_type, store)
+                    }
+                    _ => (
+                        c_element_type.clone(),
+                        format!("{};", self.assign("v->data[v->len]", "value", element_type)),
+                    ),
+                };
+
+                // The elements that own memory are owned by the vector that holds them.
+                let (copy, free) = match element_type.as_ref() {
+                    r#type if r#type.is_owned() => (
+                        format!("{}_copy(v->data[i])", c_element_type),
+                        format!(
+                            "for(unsigned long long i=0;i<v->len;i++){}_free(v->data[i]);",
+                            c_element_type
+                        ),
+                    ),
+                    _ => (String::from("v->data[i]"), String::new()),
+                };
+
+                let values = [
+                    format!("typedef struct{{{c_data_type}* data;unsigned long long len;unsigned long long capacity;}}*{alias};"),
+                    format!("{alias} {alias}_new(unsigned long long capacity){{{alias} v=malloc(sizeof(*v));v->len=0;v->capacity=capacity>4?capacity:4;v->data=malloc(v->capacity*sizeof(*v->data));return v;}}"),
+                    format!("void {alias}_push({alias} v,{c_value_type} value){{if(v->len==v->capacity){{v->capacity*=2;v->data=realloc(v->data,v->capacity*sizeof(*v->data));}}{store}v->len++;}}"),
+                    format!("{c_value_type} {alias}_pop({alias} v){{if(v->len==0){{fprintf(stderr,\"Cannot pop from an empty vec\\n\");exit(1);}}return v->data[--v->len];}}"),
+                    format!("{alias} {alias}_from({c_data_type}* values,unsigned long long len){{{alias} v={alias}_new(len);for(unsigned long long i=0;i<len;i++){alias}_push(v,values[i]);return v;}}"),
+                    format!("{alias} {alias}_copy({alias} v){{{alias} copy={alias}_new(v->len);for(unsigned long long i=0;i<v->len;i++){alias}_push(copy,{copy});return copy;}}"),
+                    format!("void {alias}_free({alias} v){{if(!v)return;{free}free(v->data);free(v);}}"),
+                ];
+
+                for value in values {
+                    if !self.typedefs.contains(&value) {
+                        self.typedefs.push(value);
+                    }
+                }
+
+                self.types_map.insert(hash, alias);
+            }
+            SemanticType::Tuple(types) => {
+                let alias = format!("__tuple_{hash}");
+
+                let c_types: Vec<String> = types
+                    .iter()
+                    .map(|r#type| self.get_type(r#type.clone()))
+                    .collect();
+
+                let mut fields = String::new();
+                let mut params: Vec<String> = vec![];
+                let mut stores = String::new();
+                let mut copies = String::new();
+                let mut frees = String::new();
+
+                for (index, (r#type, c_type)) in types.iter().zip(&c_types).enumerate() {
+                    fields.push_str(&format!("{} e{};", c_type, index));
+                    params.push(format!("{} e{}", c_type, index));
+
+                    let store =
+                        self.assign(&format!("t.e{}", index), &format!("e{}", index), r#type);
+                    stores.push_str(&format!("{};", store));
+
+                    if r#type.is_owned() {
+                        copies.push_str(&format!("t.e{}={}_copy(t.e{});", index, c_type, index));
+                        frees.push_str(&format!("{}_free(t.e{});", c_type, index));
+                    }
+                }
+
+                let mut values = vec![
+                    format!("typedef struct{{{fields}}}{alias};"),
+                    format!(
+                        "{alias} {alias}_new({}){{{alias} t;{stores}return t;}}",
+                        params.join(",")
+                    ),
+                ];
+
+                // The elements that own memory are owned by the tuple that holds them.
+                if r#type.is_owned() {
+                    values.push(format!(
+                        "{alias} {alias}_copy({alias} t){{{copies}return t;}}"
+                    ));
+                    values.push(format!("void {alias}_free({alias} t){{{frees}}}"));
+                }
+
+                for value in values {
+                    self.push_typedef(value);
+                }
+
+                self.types_map.insert(hash, alias);
+            }
+            SemanticType::Optional(value_type) => {
+                let alias = format!("__optional_{hash}");
+                let c_value_type = self.get_type(value_type.as_ref().clone());
+
+                let store = self.assign("o.value", "value", value_type);
+
+                let mut values = vec![
+                    format!("typedef struct{{unsigned char some;{c_value_type} value;}}{alias};"),
+                    format!("{alias} {alias}_some({c_value_type} value){{{alias} o;o.some=1;{store};return o;}}"),
+                ];
+
+                // The value that owns memory is owned by the optional that holds it.
+                if r#type.is_owned() {
+                    values.push(format!(
+                        "{alias} {alias}_copy({alias} o){{if(o.some)o.value={c_value_type}_copy(o.value);return o;}}"
+                    ));
+                    values.push(format!(
+                        "void {alias}_free({alias} o){{if(o.some){c_value_type}_free(o.value);}}"
+                    ));
+                }
+
+                for value in values {
+                    self.push_typedef(value);
+                }
+
+                self.types_map.insert(hash, alias);
+            }
+            SemanticType::Result(value_type, error_type) => {
+                let alias = format!("__result_{hash}");
+                let c_error_type = self.get_type(error_type.as_ref().clone());
+                let c_optional_type = self.get_type(SemanticType::Optional(error_type.clone()));
+
+                // A result without a value, as `result<void, E>`, only holds the error.
+                let (value_field, ok) = match value_type.as_ref() {
+                    SemanticType::Void => (
+                        String::new(),
+                        format!("{alias} {alias}_ok(){{{alias} r={{0}};r.ok=1;return r;}}"),
+                    ),
+                    value_type => {
+                        let c_value_type = self.get_type(value_type.clone());
+                        let store = self.assign("r.value", "value", value_type);
+
+                        (
+                            format!("{c_value_type} value;"),
+                            format!(
+                                "{alias} {alias}_ok({c_value_type} value){{{alias} r={{0}};r.ok=1;{store};return r;}}"
+                            ),
+                        )
+                    }
+                };
+
+                let store_error = self.assign("r.error", "error", error_type);
+
+                // The error is copied out of the result, which keeps owning its own.
+                let error = if error_type.is_owned() {
+                    format!("{c_optional_type}_some({c_error_type}_copy(r.error))")
+                } else {
+                    format!("{c_optional_type}_some(r.error)")
+                };
+
+                let mut values = vec![
+                    format!("typedef struct{{unsigned char ok;{value_field}{c_error_type} error;}}{alias};"),
+                    ok,
+                    format!(
+                        "{alias} {alias}_err({c_error_type} error){{{alias} r={{0}};{store_error};return r;}}"
+                    ),
+                    format!(
+                        "{c_optional_type} {alias}_error({alias} r){{if(r.ok)return ({c_optional_type}){{0}};return {error};}}"
+                    ),
+                ];
+
+                // The value or the error that owns memory is owned by the result that holds it.
+                if r#type.is_owned() {
+                    let copy = |field: &str, r#type: &SemanticType, c_type: &str| {
+                        if r#type.is_owned() {
+                            format!("r.{field}={c_type}_copy(r.{field});")
+                        } else {
+                            String::new()
+                        }
+                    };
+                    let free = |field: &str, r#type: &SemanticType, c_type: &str| {
+                        if r#type.is_owned() {
+                            format!("{c_type}_free(r.{field});")
+                        } else {
+                            String::new()
+                        }
+                    };
+
+                    let c_value_type = self.get_type(value_type.as_ref().clone());
+
+                    values.push(format!(
+                        "{alias} {alias}_copy({alias} r){{if(r.ok){{{}}}else{{{}}}return r;}}",
+                        copy("value", value_type, &c_value_type),
+                        copy("error", error_type, &c_error_type)
+                    ));
+                    values.push(format!(
+                        "void {alias}_free({alias} r){{if(r.ok){{{}}}else{{{}}}}}",
+                        free("value", value_type, &c_value_type),
+                        free("error", error_type, &c_error_type)
+                    ));
+                }
+
+                for value in values {
+                    self.push_typedef(value);
+                }
+
+                self.types_map.insert(hash, alias);
+            }
+            SemanticType::Slice(element_type) => {
+                // The elements are pointed the same way an array points to them.
+                let c_data_type = self.get_type(SemanticType::Array(element_type.clone(), 0));
+
+                let alias = format!("__slice_{hash}");
+
+                let value = format!(
+                    "typedef struct{{{} data;unsigned long long len;}}{};",
+                    c_data_type, alias
+                );
+
+                if !self.typedefs.contains(&value) {
+                    self.typedefs.push(value);
+                }
+
+                self.types_map.insert(hash, alias);
+            }
             _ => {
                 self.types_map.insert(hash, r#type.to_c_type());
             }
@@ -150,6 +1082,31 @@ impl CCode {
 
         self.types_map.get(&hash).unwrap().clone()
     }
+
+    /// Gets the C type of a variable or parameter, qualified with `const` when it is immutable.
+    ///
+    /// # Arguments
+    ///
+    /// * `r#type` - The type of the variable or parameter. Strings are never qualified, as
+    ///   they decay to `char*`, and pointers are qualified themselves instead of what they
+    ///   point to, so the elements of arrays and vectors are kept by the semantic analysis.
+    /// * `mutable` - Whether the variable or parameter is declared with `mut`.
+    ///
+    /// # Returns
+    ///
+    /// The qualified C type, such as `const signed int` or `const char*const`.
+    pub fn get_binding_type(&mut self, r#type: SemanticType, mutable: bool) -> String {
+        let is_string = r#type == SemanticType::String;
+        let c_type = self.get_type(r#type);
+
+        if mutable || is_string {
+            c_type
+        } else if c_type.ends_with('*') {
+            format!("{}const", c_type)
+        } else {
+            format!("const {}", c_type)
+        }
+    }
 }
 
 pub struct CCodeGenerator2<'s, 'a> {
@@ -165,23 +1122,86 @@ impl<'s, 'a> CCodeGenerator2<'s, 'a> {
     pub fn generate(&mut self) -> CCode {
         let mut ccode = CCode::new();
 
+        let functions = self.get_functions();
+
         for statement in &self.ast.statements {
-            match statement {
-                TopLevelStatement::Function(function) => {
-                    FunctionGenerator::generate_prototype(function, &mut ccode)
-                }
+            if let TopLevelStatement::Extern(r#extern) = statement {
+                FunctionGenerator::generate_extern(r#extern, &mut ccode);
+            }
+        }
+
+        // Generic functions are only generated for the type arguments they are called with.
+        for function in &functions {
+            if function.type_parameters.is_empty() {
+                FunctionGenerator::generate_prototype(function, &mut ccode)
             }
         }
 
+        for function in &functions {
+            if function.type_parameters.is_empty() {
+                FunctionGenerator::generate(function, self.scopes, &mut ccode)
+            }
+        }
+
+        // Generating an instance may request others, so this runs until none is left.
+        while let Some((name, instance_name, type_arguments)) = ccode.next_instance() {
+            let function = self
+                .ast
+                .statements
+                .iter()
+                .find_map(|statement| match statement {
+                    TopLevelStatement::Function(function) if function.identifier.name == name => {
+                        Some(function)
+                    }
+                    _ => None,
+                });
+
+            if let Some(function) = function {
+                let global_scope = self.scopes[&function.block.id]
+                    .borrow()
+                    .get_parent()
+                    .unwrap();
+
+                let mut scopes = Scopes::new();
+                FunctionAnalyzer::analyze_instance(
+                    function,
+                    &type_arguments,
+                    global_scope,
+                    &mut scopes,
+                );
+
+                FunctionGenerator::generate_instance(function, &instance_name, &scopes, &mut ccode);
+            }
+        }
+
+        ccode
+    }
+
+    /// Gets the functions of the program along with the methods, which are named after their
+    /// types as they were analyzed.
+    fn get_functions(&self) -> Vec<Function> {
+        let mut functions: Vec<Function> = vec![];
+
         for statement in &self.ast.statements {
             match statement {
-                TopLevelStatement::Function(function) => {
-                    FunctionGenerator::generate(function, self.scopes, &mut ccode)
+                TopLevelStatement::Function(function) => functions.push(function.clone()),
+                TopLevelStatement::Impl(r#impl) => {
+                    if let Some(function) = r#impl.functions.first() {
+                        let global_scope = self.scopes[&function.block.id]
+                            .borrow()
+                            .get_parent()
+                            .unwrap();
+
+                        functions.extend(ImplAnalyzer::get_functions(r#impl, global_scope));
+                    }
                 }
+                // The methods of a trait are only generated for the types implementing it, and
+                // the extern functions are implemented in C.
+                TopLevelStatement::Trait(_) | TopLevelStatement::Extern(_) => {}
             }
         }
 
-        ccode
+        functions
     }
 }
 
@@ -2,25 +2,425 @@ use std::{cell::RefCell, rc::Rc};
 
 use crate::lang::{
     generators::expression_meta_generator::ExpressionMetaGenerator,
-    semantic::{expressions::array_analyzer::ArrayAnalyzer, scope::Scope},
-    syntax::expressions::{expression::Expression, literal::Literal},
+    lexer::token_kind::TokenKind,
+    position::Positioned,
+    semantic::{
+        expressions::{
+            array_analyzer::ArrayAnalyzer, builtin_analyzer::BuiltinAnalyzer,
+            expression_analyzer::ExpressionAnalyzer,
+            expression_meta_analyzer::ExpressionMetaAnalyzer, tuple_analyzer::TupleAnalyzer,
+        },
+        scope::Scope,
+        semantic_type::SemanticType,
+        symbol::Symbol,
+    },
+    syntax::expressions::{
+        expression::{Expression, ExpressionMeta},
+        literal::Literal,
+    },
+    syntax::shared::identifier::Identifier,
 };
 
-use super::{array_generator::ArrayGenerator, c_code_generator2::CCode};
+use super::{
+    array_generator::ArrayGenerator, block_generator::BlockGenerator,
+    builtin_generator::BuiltinGenerator, c_code_generator2::CCode, if_generator::IfGenerator,
+    interpolation_generator::InterpolationGenerator, lambda_generator::LambdaGenerator,
+    loop_generator::LoopGenerator,
+};
 
 pub struct ExpressionGenerator;
 
 impl ExpressionGenerator {
+    /// Generates an expression whose value is used where the `expected` type is required,
+    /// converting arrays into slices or vectors and vectors into slices when needed. Values
+    /// are wrapped when an optional is expected, and `ok(..)` and `err(..)` take the type of the
+    /// result that is expected.
+    pub fn generate_coerced(
+        expression: &Expression,
+        expected: &SemanticType,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        Self::coerce(expression, expected, false, scope, ccode)
+    }
+
+    /// Converts the value of an expression into the `expected` type, where a new value is owned
+    /// by where it is stored when `is_owned` is set, and only read where it is used otherwise.
+    fn coerce(
+        expression: &Expression,
+        expected: &SemanticType,
+        is_owned: bool,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        if let SemanticType::Result(..) = expected {
+            let found = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type;
+
+            if found.is_partial() {
+                match expression {
+                    Expression::Identifier(
+                        identifier,
+                        Some(ExpressionMeta::Call(expressions, meta, _)),
+                    ) if meta.is_none() => {
+                        return BuiltinGenerator::generate_result(
+                            identifier,
+                            expressions,
+                            expected,
+                            scope,
+                            ccode,
+                        );
+                    }
+                    Expression::Parenthesized(parenthesized, None) => {
+                        return Self::coerce(
+                            &parenthesized.expression,
+                            expected,
+                            is_owned,
+                            scope,
+                            ccode,
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if let SemanticType::Optional(value_type) = expected {
+            let found = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type;
+            let c_type = ccode.get_type(expected.clone());
+
+            return match found {
+                // `none` is an optional without a value.
+                SemanticType::Optional(found) if *found == SemanticType::Any => {
+                    format!("(({}){{0}})", c_type)
+                }
+                SemanticType::Optional(_) => {
+                    Self::generate_value(expression, is_owned, scope, ccode)
+                }
+                _ => format!(
+                    "{}_some({})",
+                    c_type,
+                    Self::coerce(expression, value_type, is_owned, scope, ccode)
+                ),
+            };
+        }
+
+        let code = Self::generate_value(expression, is_owned, Rc::clone(&scope), ccode);
+
+        match expected {
+            SemanticType::Slice(_) | SemanticType::Vec(_) => {}
+            _ => return code,
+        }
+
+        let found = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type;
+        let c_type = ccode.get_type(expected.clone());
+
+        match (expected, found) {
+            // The empty array literal has no elements to point to.
+            (SemanticType::Slice(_), SemanticType::Array(_, 0)) => {
+                ccode.push_import("#include<stddef.h>");
+                format!("(({}){{NULL,0}})", c_type)
+            }
+            (SemanticType::Slice(_), SemanticType::Array(_, size)) => {
+                format!("(({}){{{},{}}})", c_type, code, size)
+            }
+            (SemanticType::Slice(_), SemanticType::Vec(_)) => {
+                // A new vector is stored in its temporary before it is read twice.
+                match Self::get_temporary(expression, Rc::clone(&scope)) {
+                    Some((name, _)) if !is_owned => {
+                        format!("({},(({}){{{}->data,{}->len}}))", code, c_type, name, name)
+                    }
+                    _ => format!("(({}){{({})->data,({})->len}})", c_type, code, code),
+                }
+            }
+            // The empty array literal has no elements to copy.
+            (SemanticType::Vec(_), SemanticType::Array(_, 0)) => format!("{}_new(0)", c_type),
+            (SemanticType::Vec(_), SemanticType::Array(_, size)) => {
+                format!("{}_from({},{})", c_type, code, size)
+            }
+            _ => code,
+        }
+    }
+
+    /// Generates operands from left to right. When an operand hoists code before the statement,
+    /// as a `?` does, the operands before it are kept in hidden variables ahead of that code,
+    /// so that the values they own are released when that code returns early.
+    ///
+    /// # Arguments
+    ///
+    /// * `operands` - The operands generated before, such as the value a function value is
+    ///   called on, along with their types.
+    /// * `expressions` - The operands to be generated after them.
+    /// * `is_moved` - Whether the values of the operands are moved into the expression.
+    /// * `ccode` - Where the code is generated.
+    /// * `generate` - Generates the operand at the given index of `expressions`.
+    /// * `get_type` - Gets the type of the code generated for an operand, which is only needed
+    ///   when it is kept.
+    ///
+    /// # Returns
+    ///
+    /// The code of all the operands, in order.
+    pub fn generate_in_order(
+        operands: Vec<(String, SemanticType)>,
+        expressions: &[&Expression],
+        is_moved: bool,
+        ccode: &mut CCode,
+        mut generate: impl FnMut(usize, &Expression, &mut CCode) -> String,
+        get_type: impl Fn(usize, &Expression) -> SemanticType,
+    ) -> Vec<String> {
+        let mut codes: Vec<String> = vec![];
+        let mut types: Vec<Option<SemanticType>> = vec![];
+
+        for (code, r#type) in operands {
+            codes.push(code);
+            types.push(Some(r#type));
+        }
+
+        let offset = codes.len();
+        // The operands before this one are already kept.
+        let mut kept = 0;
+
+        for (index, expression) in expressions.iter().enumerate() {
+            if expression.find_hoisted().is_some() {
+                let mut pending: Vec<(String, SemanticType)> = (kept..codes.len())
+                    .map(|position| {
+                        let r#type = types[position].clone().unwrap_or_else(|| {
+                            get_type(position - offset, expressions[position - offset])
+                        });
+
+                        (codes[position].clone(), r#type)
+                    })
+                    .collect();
+
+                ccode.keep_operands(&mut pending, is_moved);
+
+                for (position, (code, _)) in (kept..).zip(pending) {
+                    codes[position] = code;
+                }
+
+                kept = codes.len();
+            }
+
+            codes.push(generate(index, expression, ccode));
+            types.push(None);
+        }
+
+        codes
+    }
+
+    /// Generates an expression whose value is stored where the `expected` type is required.
+    /// Vectors and the tuples that hold them are owned by the binding that holds them, so a
+    /// value that is not a new one is copied.
+    pub fn generate_owned(
+        expression: &Expression,
+        expected: &SemanticType,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        // The value wrapped by an optional is owned by it.
+        if let SemanticType::Optional(value_type) = expected {
+            let found = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type;
+
+            if !matches!(found, SemanticType::Optional(_)) {
+                let code = Self::generate_owned(expression, value_type, scope, ccode);
+                let c_type = ccode.get_type(expected.clone());
+
+                return format!("{}_some({})", c_type, code);
+            }
+        }
+
+        let code = Self::coerce(expression, expected, true, Rc::clone(&scope), ccode);
+
+        if expected.is_owned() {
+            let found = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type;
+
+            if found.is_owned() && !Self::is_new_value(expression) {
+                let c_type = ccode.get_type(expected.clone());
+                return format!("{}_copy({})", c_type, code);
+            }
+        }
+
+        code
+    }
+
+    /// Generates an expression whose new value is either owned by where it is stored or only
+    /// read where it is used.
+    fn generate_value(
+        expression: &Expression,
+        is_owned: bool,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        if is_owned {
+            Self::generate_moved(expression, scope, ccode)
+        } else {
+            Self::generate(expression, scope, ccode)
+        }
+    }
+
+    /// Gets the name and the type of the hidden variable that keeps the value of an expression
+    /// that is only read where it is used, when it is a new value that owns memory.
+    fn get_temporary(
+        expression: &Expression,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Option<(String, SemanticType)> {
+        if let Expression::Parenthesized(parenthesized, None) = expression {
+            return Self::get_temporary(&parenthesized.expression, scope);
+        }
+
+        if !Self::is_new_value(expression) {
+            return None;
+        }
+
+        let r#type = ExpressionAnalyzer::analyze(expression, scope).return_type;
+
+        if !r#type.is_owned() || r#type.is_partial() {
+            return None;
+        }
+
+        let position = expression.get_position();
+        let name = format!("__temporary_{}_{}", position.line, position.column);
+
+        Some((name, r#type))
+    }
+
+    /// Generates a call to a generic function through its instance for the types inferred from
+    /// the arguments.
+    fn generate_generic_call(
+        identifier: &Identifier,
+        function_type: &SemanticType,
+        meta: &ExpressionMeta,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        let expressions = match meta {
+            ExpressionMeta::Call(expressions, ..) => expressions,
+            _ => unreachable!(),
+        };
+
+        let type_arguments = ExpressionMetaAnalyzer::infer_type_arguments(
+            function_type,
+            expressions,
+            Rc::clone(&scope),
+        );
+
+        let instance_type = function_type.substitute(&type_arguments);
+        let instance_name =
+            ccode.request_instance(&identifier.name, type_arguments, &instance_type);
+
+        ExpressionMetaGenerator::generate_direct_call(
+            &CCode::get_function_name(&instance_name),
+            &instance_type,
+            meta,
+            Rc::clone(&scope),
+            ccode,
+        )
+    }
+
+    /// Gets the name in C of the function declared with the given name, which is kept by the
+    /// functions declared with `extern` or `export`.
+    fn get_function_name(name: &str, external: bool) -> String {
+        if external {
+            String::from(name)
+        } else {
+            CCode::get_function_name(name)
+        }
+    }
+
+    /// Whether the value of the expression is created by it, as the result of a call, instead of
+    /// being read from a binding.
+    pub fn is_new_value(expression: &Expression) -> bool {
+        match expression {
+            Expression::Identifier(_, Some(meta)) | Expression::Parenthesized(_, Some(meta)) => {
+                let mut meta = meta;
+                let mut is_call = false;
+
+                loop {
+                    let next = match meta {
+                        ExpressionMeta::Call(_, next, _)
+                        | ExpressionMeta::Method(_, _, next, _) => {
+                            is_call = true;
+                            next
+                        }
+                        ExpressionMeta::Index(_, next, _) | ExpressionMeta::Field(_, next, _) => {
+                            is_call = false;
+                            next
+                        }
+                        // The value taken out of a result is new when the result is.
+                        ExpressionMeta::Try(next, _) => next,
+                    };
+
+                    match next.as_ref() {
+                        Some(next) => meta = next,
+                        None => return is_call,
+                    }
+                }
+            }
+            Expression::Parenthesized(parenthesized, None) => {
+                Self::is_new_value(&parenthesized.expression)
+            }
+            // The elements of a tuple are copied into it when they are not new values, the
+            // values of loops, ifs and blocks are owned by them and lambdas allocate the values
+            // they capture.
+            Expression::Tuple(_, None)
+            | Expression::Lambda(..)
+            | Expression::Loop(_)
+            | Expression::If(_)
+            | Expression::Block(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Generates the read of a variable or a parameter. The value a method is called on is passed
+    /// by reference, unless it is an array, which is already passed as a pointer.
+    pub fn generate_binding(name: &str, r#type: &SemanticType) -> String {
+        match r#type {
+            SemanticType::Array(..) => String::from(name),
+            _ if name == "self" => String::from("(*self)"),
+            _ => String::from(name),
+        }
+    }
+
+    /// Generates an expression whose value is only read where it is used. A new value that
+    /// owns memory, such as a vector returned by a call, is released after the statement.
     pub fn generate(
         expression: &Expression,
         scope: Rc<RefCell<Scope>>,
         ccode: &mut CCode,
+    ) -> String {
+        let code = Self::generate_moved(expression, Rc::clone(&scope), ccode);
+
+        // The value of a parenthesized expression is kept by the expression inside it.
+        if let Expression::Parenthesized(_, None) = expression {
+            return code;
+        }
+
+        match Self::get_temporary(expression, scope) {
+            Some((name, r#type)) => ccode.add_temporary(&name, &r#type, &code),
+            None => code,
+        }
+    }
+
+    /// Generates an expression whose new value is moved to where it is used, which owns it.
+    pub fn generate_moved(
+        expression: &Expression,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
     ) -> String {
         match expression {
             Expression::Array(array, meta) => {
                 let ArrayAnalyzer { return_type, .. } =
                     ArrayAnalyzer::analyze(array, Rc::clone(&scope));
 
+                if ArrayGenerator::has_repeat(array) {
+                    return ArrayGenerator::generate_copied(
+                        array,
+                        &return_type,
+                        meta,
+                        scope,
+                        ccode,
+                    );
+                }
+
                 ArrayGenerator::generate_expression(
                     &return_type,
                     &array.expressions,
@@ -36,20 +436,98 @@ impl ExpressionGenerator {
                     Self::generate(&unary.expression, Rc::clone(&scope), ccode)
                 )
             }
-            Expression::Binary(binary) => {
+            Expression::Binary(binary) => match binary.operator.token.kind {
+                // An optional is compared with `none` by whether it holds a value.
+                TokenKind::EqualsEquals | TokenKind::ExclamationEquals
+                    if matches!(binary.left.as_ref(), Expression::Literal(Literal::None(_)))
+                        || matches!(
+                            binary.right.as_ref(),
+                            Expression::Literal(Literal::None(_))
+                        ) =>
+                {
+                    let optional = match binary.left.as_ref() {
+                        Expression::Literal(Literal::None(_)) => &binary.right,
+                        _ => &binary.left,
+                    };
+
+                    let code = Self::generate(optional, Rc::clone(&scope), ccode);
+
+                    match binary.operator.token.kind {
+                        TokenKind::EqualsEquals => format!("(!({}).some)", code),
+                        _ => format!("(({}).some)", code),
+                    }
+                }
+                _ => {
+                    let operands = Self::generate_in_order(
+                        vec![],
+                        &[&binary.left, &binary.right],
+                        false,
+                        ccode,
+                        |_, expression, ccode| Self::generate(expression, Rc::clone(&scope), ccode),
+                        |_, expression| {
+                            ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type
+                        },
+                    );
+
+                    match binary.operator.token.kind {
+                        TokenKind::InKeyword => {
+                            ccode.get_type(SemanticType::Range);
+                            format!("__range_contains({},{})", operands[1], operands[0])
+                        }
+                        _ => format!(
+                            "{}{}{}",
+                            operands[0], binary.operator.token.value, operands[1]
+                        ),
+                    }
+                }
+            },
+            Expression::Range(range) => {
+                let c_type = ccode.get_type(SemanticType::Range);
+
+                let step = match &range.step {
+                    Some(step) => Self::generate(step, Rc::clone(&scope), ccode),
+                    None => String::from("1"),
+                };
+
+                let inclusive = match range.operator.token.kind {
+                    TokenKind::DotDotEquals => 1,
+                    _ => 0,
+                };
+
                 format!(
-                    "{}{}{}",
-                    Self::generate(&binary.left, Rc::clone(&scope), ccode),
-                    binary.operator.token.value,
-                    Self::generate(&binary.right, Rc::clone(&scope), ccode)
+                    "(({}){{{},{},{},{}}})",
+                    c_type,
+                    Self::generate(&range.left, Rc::clone(&scope), ccode),
+                    Self::generate(&range.right, Rc::clone(&scope), ccode),
+                    step,
+                    inclusive
                 )
             }
             Expression::Parenthesized(parenthesized, meta) => {
                 if let Some(meta) = &meta {
-                    format!(
-                        "({}){}",
-                        Self::generate(&parenthesized.expression, Rc::clone(&scope), ccode),
-                        ExpressionMetaGenerator::generate(meta, Rc::clone(&scope), ccode)
+                    let r#type =
+                        ExpressionAnalyzer::analyze(&parenthesized.expression, Rc::clone(&scope))
+                            .return_type;
+
+                    // A new value is released after the statement once the meta reads it.
+                    let code = format!(
+                        "({})",
+                        Self::generate_moved(&parenthesized.expression, Rc::clone(&scope), ccode)
+                    );
+
+                    // Only the values read from bindings can be taken by reference.
+                    let is_new = !matches!(
+                        parenthesized.expression.as_ref(),
+                        Expression::Identifier(..)
+                    ) || Self::is_new_value(&parenthesized.expression);
+
+                    ExpressionMetaGenerator::generate(
+                        code,
+                        &r#type,
+                        meta,
+                        is_new,
+                        Rc::clone(&scope),
+                        ccode,
                     )
                 } else {
                     format!(
@@ -59,26 +537,278 @@ impl ExpressionGenerator {
                 }
             }
             Expression::Identifier(identifier, meta) => {
-                if let Some(meta) = &meta {
-                    format!(
-                        "{}{}",
-                        identifier.name.clone(),
-                        ExpressionMetaGenerator::generate(meta, Rc::clone(&scope), ccode)
-                    )
+                let symbol = scope.borrow().get(&identifier.name);
+
+                // A builtin function is used as the overload taking its arguments.
+                let overload = symbol.as_ref().and_then(|symbol| {
+                    BuiltinGenerator::generate_function(symbol, meta, Rc::clone(&scope), ccode)
+                });
+
+                if symbol.is_none() && BuiltinAnalyzer::is_builtin(&identifier.name) {
+                    BuiltinGenerator::generate(identifier, meta, Rc::clone(&scope), ccode)
+                } else if let Some(Symbol::SizeParameter {
+                    size: Some(size), ..
+                }) = symbol
+                {
+                    size.to_string()
+                } else if let Some((name, symbol_type)) = overload {
+                    match meta {
+                        Some(meta) => ExpressionMetaGenerator::generate_direct_call(
+                            &name,
+                            &symbol_type,
+                            meta,
+                            Rc::clone(&scope),
+                            ccode,
+                        ),
+                        None => ccode.get_function_value(&name, &symbol_type),
+                    }
+                } else if let (
+                    Some(Symbol::Function {
+                        symbol_type,
+                        external,
+                        ..
+                    }),
+                    Some(meta),
+                ) = (&symbol, meta)
+                {
+                    if symbol_type.is_generic() {
+                        Self::generate_generic_call(
+                            identifier,
+                            symbol_type,
+                            meta,
+                            Rc::clone(&scope),
+                            ccode,
+                        )
+                    } else {
+                        ExpressionMetaGenerator::generate_direct_call(
+                            &Self::get_function_name(&identifier.name, *external),
+                            symbol_type,
+                            meta,
+                            Rc::clone(&scope),
+                            ccode,
+                        )
+                    }
+                } else if let Some(Symbol::Function {
+                    symbol_type,
+                    external,
+                    ..
+                }) = &symbol
+                {
+                    let name = Self::get_function_name(&identifier.name, *external);
+                    ccode.get_function_value(&name, symbol_type)
                 } else {
-                    format!("{}", identifier.name.clone())
+                    let mut r#type = match symbol {
+                        Some(Symbol::Variable { symbol_type, .. })
+                        | Some(Symbol::Parameter { symbol_type, .. }) => symbol_type,
+                        _ => SemanticType::Any,
+                    };
+
+                    // The values captured by a lambda are read from its environment.
+                    let captured = scope.borrow().get_captured(&identifier.name);
+
+                    let mut code = match captured {
+                        Some(_) => format!("__env->{}", identifier.name),
+                        None => Self::generate_binding(&identifier.name, &r#type),
+                    };
+
+                    // An optional that was checked against none is read as its value.
+                    let checked = scope.borrow().is_checked(identifier.get_position());
+
+                    if let (SemanticType::Optional(value_type), true) = (&r#type, checked) {
+                        code = format!("{}.value", code);
+                        r#type = value_type.as_ref().clone();
+                    }
+
+                    match meta {
+                        Some(meta) => ExpressionMetaGenerator::generate(
+                            code,
+                            &r#type,
+                            meta,
+                            false,
+                            Rc::clone(&scope),
+                            ccode,
+                        ),
+                        None => code,
+                    }
                 }
             }
+            Expression::Tuple(tuple, meta) => {
+                let r#type = TupleAnalyzer::analyze(tuple, Rc::clone(&scope)).return_type;
+
+                let types = match &r#type {
+                    SemanticType::Tuple(types) => types.clone(),
+                    _ => vec![],
+                };
+
+                let elements = Self::generate_in_order(
+                    vec![],
+                    &tuple.expressions.iter().collect::<Vec<&Expression>>(),
+                    true,
+                    ccode,
+                    |index, expression, ccode| {
+                        Self::generate_owned(expression, &types[index], Rc::clone(&scope), ccode)
+                    },
+                    |index, _| types[index].clone(),
+                );
+
+                let c_type = ccode.get_type(r#type.clone());
+                let code = format!("{}_new({})", c_type, elements.join(","));
+
+                match meta {
+                    Some(meta) => {
+                        ExpressionMetaGenerator::generate(code, &r#type, meta, true, scope, ccode)
+                    }
+                    None => code,
+                }
+            }
+            Expression::Lambda(lambda) => LambdaGenerator::generate(lambda, scope, ccode),
+            Expression::Loop(r#loop) => LoopGenerator::generate(r#loop, scope, ccode),
+            Expression::If(r#if) => IfGenerator::generate(r#if, scope, ccode),
+            Expression::Block(block, position) => {
+                BlockGenerator::generate_expression(block, *position, scope, ccode)
+            }
+            Expression::Interpolation(interpolation) => {
+                InterpolationGenerator::generate(interpolation, scope, ccode)
+            }
+            Expression::Alloc(alloc) => BuiltinGenerator::generate_alloc(alloc, scope, ccode),
             Expression::Literal(literal) => match literal {
                 Literal::Number(token) => token.value.clone(),
-                Literal::Char(token) => format!("'{}'", token.value),
-                Literal::String(token) => format!("\"{}\"", token.value),
+                // A character is written as the code of its Unicode scalar.
+                Literal::Char(token) => {
+                    let char = token.value.chars().next().unwrap_or('\0');
+                    format!("{}u", char as u32)
+                }
+                Literal::String(token) => format!("\"{}\"", CCode::escape_string(&token.value)),
                 Literal::Boolean(token) => match &token.value[..] {
                     "true" => "1".to_string(),
                     _ => "0".to_string(),
                 },
+                // The type of a `none` is only known where an optional is expected.
+                Literal::None(_) => "{0}".to_string(),
             },
-            _ => panic!(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::generators::test_helpers::generate;
+
+    const MAKE: &str =
+        "fun make(n: i32) -> vec<i32> { let mut v: vec<i32> = []; push(v, n); return v; }";
+
+    #[test]
+    fn test_temporaries() {
+        // A new vector that is only read is released after the statement.
+        let code = generate(&format!("{} fun main() {{ println len(make(5)); }}", MAKE));
+        let start = code.find("__temporary_").unwrap();
+        let name = &code[start..start + code[start..].find('=').unwrap()];
+
+        let stored = code.find(&format!("{}=__x_make(5)", name)).unwrap();
+        let printed = code.find("printf(\"\\n\");").unwrap();
+        let released = code.rfind(&format!("_free({});", name)).unwrap();
+        assert!(start < stored && stored < printed && printed < released);
+
+        // A returned temporary is released before the function returns.
+        let code = generate(&format!(
+            "{} fun first() -> i32 {{ return make(1)[0]; }} fun main() {{ println first(); }}",
+            MAKE
+        ));
+        assert!(code.contains("__return=("));
+
+        // A new vector that is stored is owned by its binding instead.
+        let code = generate(&format!(
+            "{} fun main() {{ let v = make(5); println len(v); }}",
+            MAKE
+        ));
+        assert!(!code.contains("__temporary"));
+    }
+
+    #[test]
+    fn test_vec_of_arrays() {
+        // The elements are copied into the vector, so changing the source array after the push
+        // does not change them.
+        let code = generate(
+            "fun main() { let mut a = [1, 2]; let mut v: vec<[i32; 2]> = []; push(v, a); \
+            a[0] = 99; println v[0][0]; }",
+        );
+        let start = code.find("typedef signed int __vec_").unwrap() + 19;
+        let element = &code[start..start + code[start..].find('[').unwrap()];
+
+        assert!(code.contains(&format!("{}* data;", element)));
+        assert!(code.contains("memmove(v->data[v->len],value,sizeof(signed int[2]));v->len++;"));
+    }
+
+    #[test]
+    fn test_method_names() {
+        // Methods are named apart from functions, and receivers whose names differ only in
+        // their symbols are named apart from each other.
+        let code = generate(
+            "impl i32 { fun double(self) -> i32 { return self * 2; } } \
+            fun i32_double(x: i32) -> i32 { return x * 3; } \
+            impl ?[i32; 1] { fun m(self) -> i32 { return 1; } } \
+            impl [?i32; 1] { fun m(self) -> i32 { return 2; } } \
+            fun main() { let a = 5; println a.double() + i32_double(a); }",
+        );
+        assert!(code.contains("signed int __m_i32__double(const signed int* self)"));
+        assert!(code.contains("signed int __x_i32_double(const signed int x)"));
+        assert!(code.contains("__m__3f_5bi32_3b_201_5d__m("));
+        assert!(code.contains("__m__5b_3fi32_3b_201_5d__m("));
+    }
+
+    #[test]
+    fn test_generic_instances() {
+        let function = "fun max<T: numeric>(a: T, b: T) -> T { if a > b { return a; } return b; }";
+
+        // An instance is generated once for each type the function is called with.
+        let code = generate(&format!(
+            "{} fun main() {{ println max(1, 2); println max(3, 4); println max(1.5, 2.5); }}",
+            function
+        ));
+        assert_eq!(
+            code.matches("signed int __g_max__i32(signed int,signed int);")
+                .count(),
+            1
+        );
+        assert_eq!(code.matches("float __g_max__f32(float,float);").count(), 1);
+        assert!(code.contains("__g_max__i32(1,2)"));
+        assert!(code.contains("__g_max__f32(1.5,2.5)"));
+
+        // A function that is never called has no instance.
+        let code = generate(&format!("{} fun main() {{ }}", function));
+        assert!(!code.contains("__g_max"));
+
+        // Instances are named apart from the functions declared in the code.
+        let code = generate(
+            "fun id<T>(x: T) -> T { return x; } fun id__i32(x: i32) -> i32 { return x; } \
+            fun main() { println id(1) + id__i32(2); }",
+        );
+        assert!(code.contains("signed int __g_id__i32(signed int);"));
+        assert!(code.contains("signed int __x_id__i32(signed int);"));
+    }
+
+    #[test]
+    fn test_evaluation_order() {
+        let functions = "fun a() -> i32 { return 1; } \
+            fun b() -> result<i32, string> { return ok(2); }";
+
+        // The left operand is kept before the right one returns early.
+        let code = generate(&format!(
+            "{} fun sum() -> result<i32, string> {{ return ok(a() + b()?); }} \
+            fun main() {{ let s = sum(); }}",
+            functions
+        ));
+        let kept = code.find("__operand_").unwrap();
+        let called = code[kept..].find("=__x_a()").unwrap() + kept;
+        let tried = code.find("=__x_b()").unwrap();
+        assert!(kept < tried && called < tried);
+
+        // Nothing is kept when no operand hoists code.
+        let code = generate(&format!(
+            "{} fun main() {{ println a() + a(); }}",
+            functions
+        ));
+        assert!(!code.contains("__operand_"));
+        assert!(code.contains("__x_a()+__x_a()"));
+    }
+}
@@ -1,55 +1,312 @@
 use super::{c_code_generator2::CCode, expression_generator::ExpressionGenerator};
 
+use crate::lang::position::{Position, Positioned};
 use crate::lang::semantic::scope::Scope;
-use crate::lang::syntax::expressions::expression::ExpressionMeta;
+use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::symbol::Symbol;
+use crate::lang::syntax::expressions::expression::{Expression, ExpressionMeta};
 
 use std::{cell::RefCell, rc::Rc};
 
 pub struct ExpressionMetaGenerator;
 
 impl ExpressionMetaGenerator {
-    pub fn generate(meta: &ExpressionMeta, scope: Rc<RefCell<Scope>>, ccode: &mut CCode) -> String {
-        let mut code = String::new();
+    /// Generates the indexes and calls applied to the value generated as `code`, which is a new
+    /// value when it is not read from a binding. The values of function types are called along
+    /// with the values they capture.
+    pub fn generate(
+        code: String,
+        r#type: &SemanticType,
+        meta: &ExpressionMeta,
+        is_new: bool,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        // A new value that is only read by the meta is released after the statement, unless a
+        // `?` takes its value out of it.
+        let code = if is_new
+            && r#type.is_owned()
+            && !r#type.is_partial()
+            && !matches!(meta, ExpressionMeta::Try(..))
+        {
+            let position = meta.get_position();
+            let name = format!("__temporary_{}_{}", position.line, position.column);
+
+            ccode.add_temporary(&name, r#type, &code)
+        } else {
+            code
+        };
 
         match meta {
             ExpressionMeta::Call(expressions, meta, _) => {
-                code.push_str("(");
+                let (params, return_type) = match r#type {
+                    SemanticType::Function(params, return_type) => {
+                        (params.clone(), return_type.as_ref().clone())
+                    }
+                    _ => (vec![], SemanticType::Any),
+                };
 
-                for (index, expression) in expressions.iter().enumerate() {
-                    code.push_str(&ExpressionGenerator::generate(
-                        expression,
-                        Rc::clone(&scope),
-                        ccode,
-                    ));
+                let c_type = ccode.get_type(r#type.clone());
 
-                    if index != expressions.len() - 1 {
-                        code.push_str(",");
+                // The function value is evaluated before the arguments.
+                let operands = Self::generate_arguments(
+                    vec![(code, r#type.clone())],
+                    &params,
+                    expressions,
+                    Rc::clone(&scope),
+                    ccode,
+                );
+
+                let code = format!("{}_call({})", c_type, operands.join(","));
+
+                Self::generate_next(code, &return_type, meta, true, scope, ccode)
+            }
+            ExpressionMeta::Field(field, meta, _) => {
+                let element_type = match (r#type, field.value.parse::<usize>()) {
+                    (SemanticType::Tuple(types), Ok(index)) => {
+                        types.get(index).cloned().unwrap_or(SemanticType::Any)
                     }
-                }
+                    _ => SemanticType::Any,
+                };
 
-                code.push_str(")");
+                let code = format!("{}.e{}", code, field.value);
 
-                if let Some(meta) = meta.as_ref() {
-                    code.push_str(&Self::generate(meta, Rc::clone(&scope), ccode));
-                }
+                Self::generate_next(code, &element_type, meta, false, scope, ccode)
             }
             ExpressionMeta::Index(expression, meta, _) => {
-                code.push_str("[");
+                // The indexed value is evaluated before the index.
+                let operands = ExpressionGenerator::generate_in_order(
+                    vec![(code, r#type.clone())],
+                    &[expression],
+                    false,
+                    ccode,
+                    |_, expression, ccode| {
+                        ExpressionGenerator::generate(expression, Rc::clone(&scope), ccode)
+                    },
+                    |_, _| SemanticType::I32,
+                );
 
-                code.push_str(&ExpressionGenerator::generate(
-                    expression,
+                let mut code = operands[0].clone();
+
+                let element_type = match r#type {
+                    SemanticType::Array(element_type, _) | SemanticType::Ref(element_type) => {
+                        element_type.as_ref().clone()
+                    }
+                    SemanticType::Slice(element_type) => {
+                        // The elements of a slice are reached through its data pointer.
+                        code.push_str(".data");
+                        element_type.as_ref().clone()
+                    }
+                    SemanticType::Vec(element_type) => {
+                        // Vectors are pointers to a structure that holds their elements.
+                        code.push_str("->data");
+                        element_type.as_ref().clone()
+                    }
+                    SemanticType::String => {
+                        // Strings are indexed by their UTF-8 bytes, which are read unsigned.
+                        code = format!("((unsigned char*)({}))", code);
+                        SemanticType::U8
+                    }
+                    _ => SemanticType::Any,
+                };
+
+                code.push_str(&format!("[{}]", operands[1]));
+
+                Self::generate_next(code, &element_type, meta, false, scope, ccode)
+            }
+            ExpressionMeta::Method(identifier, expressions, meta, _) => {
+                let name = r#type.get_method_name(&identifier.name);
+                let symbol = scope.borrow().get(&name);
+
+                let function_type = match symbol {
+                    Some(Symbol::Function { symbol_type, .. }) => symbol_type,
+                    _ => SemanticType::Any,
+                };
+
+                let (params, return_type) = match &function_type {
+                    SemanticType::Function(params, return_type) => {
+                        (params[1..].to_vec(), return_type.as_ref().clone())
+                    }
+                    _ => (vec![], SemanticType::Any),
+                };
+
+                let mut function_name = CCode::get_function_name(&name);
+
+                // The value is passed by reference, so that the method can change it. Arrays and
+                // strings are already passed as pointers, and a new value is given to a function
+                // that takes it by value instead, as only bound values can be changed.
+                let is_mutating = scope.borrow().is_mutating(&name);
+
+                let receiver = match r#type {
+                    SemanticType::Array(..) => (code, r#type.clone()),
+                    SemanticType::String => (
+                        format!("(__string*)({})", code),
+                        SemanticType::Ref(Box::new(SemanticType::String)),
+                    ),
+                    _ if is_new && !is_mutating => {
+                        function_name = ccode.get_value_method(&function_name, &function_type);
+                        (code, r#type.clone())
+                    }
+                    _ => (
+                        format!("&({})", code),
+                        SemanticType::Ref(Box::new(r#type.clone())),
+                    ),
+                };
+
+                let operands = Self::generate_arguments(
+                    vec![receiver],
+                    &params,
+                    expressions,
                     Rc::clone(&scope),
                     ccode,
-                ));
+                );
+
+                let code = format!("{}({})", function_name, operands.join(","));
 
-                code.push_str("]");
+                Self::generate_next(code, &return_type, meta, true, scope, ccode)
+            }
+            ExpressionMeta::Try(meta, position) => {
+                Self::generate_try(code, r#type, meta, *position, is_new, scope, ccode)
+            }
+        }
+    }
 
-                if let Some(meta) = meta.as_ref() {
-                    code.push_str(&Self::generate(meta, Rc::clone(&scope), ccode));
-                }
+    /// Generates a call to the function with the given name, followed by the rest of the meta.
+    pub fn generate_direct_call(
+        name: &str,
+        r#type: &SemanticType,
+        meta: &ExpressionMeta,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        match (meta, r#type) {
+            (
+                ExpressionMeta::Call(expressions, meta, _),
+                SemanticType::Function(params, return_type),
+            ) => {
+                let code = format!(
+                    "{}({})",
+                    name,
+                    Self::generate_arguments(vec![], params, expressions, Rc::clone(&scope), ccode)
+                        .join(",")
+                );
+
+                Self::generate_next(code, return_type, meta, true, scope, ccode)
             }
+            _ => Self::generate(String::from(name), r#type, meta, false, scope, ccode),
         }
+    }
+
+    /// Generates the rest of the meta applied to the value generated as `code`, which is a new
+    /// value when it is not read from a binding.
+    fn generate_next(
+        code: String,
+        r#type: &SemanticType,
+        meta: &Option<ExpressionMeta>,
+        is_new: bool,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        match meta {
+            Some(ExpressionMeta::Try(meta, position)) => {
+                Self::generate_try(code, r#type, meta, *position, is_new, scope, ccode)
+            }
+            Some(meta) => Self::generate(code, r#type, meta, is_new, scope, ccode),
+            None => code,
+        }
+    }
+
+    /// Generates a `?` applied to the result or the optional generated as `code`. It is kept
+    /// in a hidden variable before the statement, which returns the error, or `none`, from
+    /// the function when there is no value, and the value is read from it.
+    fn generate_try(
+        code: String,
+        r#type: &SemanticType,
+        meta: &Option<ExpressionMeta>,
+        position: Position,
+        is_new: bool,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        let name = format!("__try_{}_{}", position.line, position.column);
+        let c_type = ccode.get_type(r#type.clone());
+
+        let function_type = scope.borrow().get_function_type();
 
-        code
+        let return_type = match function_type {
+            Some(SemanticType::Function(_, return_type)) => *return_type,
+            _ => SemanticType::Any,
+        };
+        let c_return_type = ccode.get_type(return_type);
+
+        let (tag, value_type, value) = match r#type {
+            SemanticType::Result(value_type, error_type) => {
+                // The error is moved to the returned result when nothing else owns it.
+                let error = if error_type.is_owned() && !is_new {
+                    let c_error_type = ccode.get_type(error_type.as_ref().clone());
+                    format!("{}_copy({}.error)", c_error_type, name)
+                } else {
+                    format!("{}.error", name)
+                };
+
+                (
+                    "ok",
+                    value_type.as_ref().clone(),
+                    format!("{}_err({})", c_return_type, error),
+                )
+            }
+            SemanticType::Optional(value_type) => (
+                "some",
+                value_type.as_ref().clone(),
+                format!("(({}){{0}})", c_return_type),
+            ),
+            _ => unreachable!(),
+        };
+
+        let r#return = ccode.get_return(&value, &c_return_type);
+
+        ccode.hoist(&format!(
+            "{} {}={};if(!{}.{}){}",
+            c_type, name, code, name, tag, r#return
+        ));
+
+        let code = match value_type {
+            SemanticType::Void => String::from("(void)0"),
+            _ => format!("{}.value", name),
+        };
+
+        Self::generate_next(code, &value_type, meta, is_new, scope, ccode)
+    }
+
+    /// Generates the arguments of a call from left to right, after the value the function is
+    /// called on, if any, which is given along with its type.
+    ///
+    /// # Returns
+    ///
+    /// The code of the value the function is called on followed by the code of the arguments.
+    fn generate_arguments(
+        operands: Vec<(String, SemanticType)>,
+        params: &[SemanticType],
+        expressions: &[Expression],
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> Vec<String> {
+        let expected_type = |index: usize| params.get(index).cloned().unwrap_or(SemanticType::Any);
+
+        ExpressionGenerator::generate_in_order(
+            operands,
+            &expressions.iter().collect::<Vec<&Expression>>(),
+            false,
+            ccode,
+            |index, expression, ccode| {
+                ExpressionGenerator::generate_coerced(
+                    expression,
+                    &expected_type(index),
+                    Rc::clone(&scope),
+                    ccode,
+                )
+            },
+            |index, _| expected_type(index),
+        )
     }
 }
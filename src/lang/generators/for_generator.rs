@@ -1,9 +1,15 @@
-use std::rc::Rc;
+use std::{cell::RefCell, rc::Rc};
 
 use crate::lang::{
     lexer::token_kind::TokenKind,
-    semantic::{analyzer::Scopes, symbol::Symbol},
-    syntax::{expressions::expression::Expression, statements::r#for::For},
+    semantic::{
+        analyzer::Scopes, expressions::expression_analyzer::ExpressionAnalyzer, scope::Scope,
+        semantic_type::SemanticType, symbol::Symbol,
+    },
+    syntax::{
+        expressions::{expression::Expression, range::Range},
+        statements::r#for::For,
+    },
 };
 
 use super::{
@@ -17,33 +23,203 @@ impl ForGenerator {
     pub fn generate(r#for: &For, scopes: &Scopes, ccode: &mut CCode) {
         let scope = scopes.get(&r#for.block.id).unwrap().clone();
 
-        if let Expression::Range(range) = &r#for.expression {
-            let symbol = scope.borrow().get(&r#for.identifier.name).unwrap();
+        // The loop is wrapped in its own block so the helper variables do not leak.
+        ccode.push("{");
 
-            ccode.push("for(");
+        let iterable_type =
+            ExpressionAnalyzer::analyze(&r#for.expression, Rc::clone(&scope)).return_type;
 
-            if let Symbol::Variable { symbol_type, .. } = &symbol {
-                let c_type = ccode.get_type(symbol_type.clone());
-                ccode.push(&format!("{} {}=", c_type, r#for.identifier.name))
+        match &r#for.expression {
+            Expression::Range(range) if range.step.is_none() => {
+                Self::generate_range(r#for, range, Rc::clone(&scope), scopes, ccode)
             }
+            _ if iterable_type == SemanticType::Range => {
+                Self::generate_range_value(r#for, Rc::clone(&scope), scopes, ccode)
+            }
+            _ => Self::generate_iterable(r#for, iterable_type, Rc::clone(&scope), scopes, ccode),
+        }
 
-            let code = ExpressionGenerator::generate(&range.left, Rc::clone(&scope), ccode);
-            ccode.push(&code);
+        ccode.push("}");
+    }
 
-            ccode.push(";");
+    fn generate_range(
+        r#for: &For,
+        range: &Range,
+        scope: Rc<RefCell<Scope>>,
+        scopes: &Scopes,
+        ccode: &mut CCode,
+    ) {
+        let identifier_name = r#for.identifier.name.clone();
+        let symbol = scope.borrow().get(&identifier_name).unwrap();
 
-            match range.operator.token.kind {
-                TokenKind::DotDot => ccode.push(&format!("{}<", r#for.identifier.name)),
-                TokenKind::DotDotEquals => ccode.push(&format!("{}<=", r#for.identifier.name)),
-                _ => unreachable!(),
-            }
+        if let Some(index) = &r#for.index {
+            ccode.push(&format!("signed int {}=0;", index.name));
+        }
+
+        ccode.push("for(");
+
+        if let Symbol::Variable { symbol_type, .. } = &symbol {
+            let c_type = ccode.get_type(symbol_type.clone());
+            ccode.push(&format!("{} {}=", c_type, identifier_name))
+        }
+
+        let code = ExpressionGenerator::generate(&range.left, Rc::clone(&scope), ccode);
+        ccode.push(&code);
+
+        ccode.push(";");
 
-            let code = ExpressionGenerator::generate(&range.right, Rc::clone(&scope), ccode);
-            ccode.push(&code);
+        match range.operator.token.kind {
+            TokenKind::DotDot => ccode.push(&format!("{}<", identifier_name)),
+            TokenKind::DotDotEquals => ccode.push(&format!("{}<=", identifier_name)),
+            _ => unreachable!(),
+        }
+
+        let code = ExpressionGenerator::generate(&range.right, Rc::clone(&scope), ccode);
+        ccode.push(&code);
+
+        ccode.push(";");
+        ccode.push(&format!("{}++", identifier_name));
+
+        if let Some(index) = &r#for.index {
+            ccode.push(&format!(",{}++", index.name));
+        }
 
-            ccode.push(";");
-            ccode.push(&format!("{}++)", r#for.identifier.name));
-            BlockGenerator::generate(&r#for.block, scopes, ccode);
+        ccode.push(")");
+        BlockGenerator::generate_loop_body(&r#for.block, &r#for.label, scopes, ccode);
+    }
+
+    /// Generates a loop over a range with a step or over a range stored in a value. The
+    /// direction of the loop is only known at runtime, given by the sign of the step.
+    fn generate_range_value(
+        r#for: &For,
+        scope: Rc<RefCell<Scope>>,
+        scopes: &Scopes,
+        ccode: &mut CCode,
+    ) {
+        let identifier_name = r#for.identifier.name.clone();
+        let range_name = format!("__iterable_{}", identifier_name);
+        let symbol = scope.borrow().get(&identifier_name).unwrap();
+
+        let c_range_type = ccode.get_type(SemanticType::Range);
+        let code = ExpressionGenerator::generate(&r#for.expression, Rc::clone(&scope), ccode);
+        ccode.push(&format!("{} {}={};", c_range_type, range_name, code));
+
+        if let Some(index) = &r#for.index {
+            ccode.push(&format!("signed int {}=0;", index.name));
         }
+
+        if let Symbol::Variable { symbol_type, .. } = &symbol {
+            let c_type = ccode.get_type(symbol_type.clone());
+
+            ccode.push(&format!(
+                "for({} {}={}.start;__range_continues({},{});{}+={}.step",
+                c_type,
+                identifier_name,
+                range_name,
+                range_name,
+                identifier_name,
+                identifier_name,
+                range_name
+            ));
+        }
+
+        if let Some(index) = &r#for.index {
+            ccode.push(&format!(",{}++", index.name));
+        }
+
+        ccode.push(")");
+        BlockGenerator::generate_loop_body(&r#for.block, &r#for.label, scopes, ccode);
+    }
+
+    /// Generates a loop over the elements of an array, slice or vector, or over the characters of a string.
+    /// The iterable expression is evaluated once and stored before the loop starts.
+    fn generate_iterable(
+        r#for: &For,
+        iterable_type: SemanticType,
+        scope: Rc<RefCell<Scope>>,
+        scopes: &Scopes,
+        ccode: &mut CCode,
+    ) {
+        let identifier_name = r#for.identifier.name.clone();
+        let iterable_name = format!("__iterable_{}", identifier_name);
+        let index_name = match &r#for.index {
+            Some(index) => index.name.clone(),
+            None => format!("__index_{}", identifier_name),
+        };
+
+        let (c_iterable_type, condition, element_type) = match &iterable_type {
+            SemanticType::Array(element_type, size) => (
+                ccode.get_type(iterable_type.clone()),
+                format!("{}<{}", index_name, size),
+                element_type.as_ref().clone(),
+            ),
+            SemanticType::Slice(element_type) => (
+                ccode.get_type(iterable_type.clone()),
+                format!("{}<{}.len", index_name, iterable_name),
+                element_type.as_ref().clone(),
+            ),
+            SemanticType::Vec(element_type) => (
+                ccode.get_type(iterable_type.clone()),
+                format!("{}<{}->len", index_name, iterable_name),
+                element_type.as_ref().clone(),
+            ),
+            SemanticType::String => (
+                String::from("const char*"),
+                format!("{}[{}]!='\\0'", iterable_name, index_name),
+                SemanticType::Char,
+            ),
+            _ => unreachable!(),
+        };
+
+        let code = ExpressionGenerator::generate(&r#for.expression, Rc::clone(&scope), ccode);
+        ccode.push(&format!("{} {}={};", c_iterable_type, iterable_name, code));
+
+        // The characters of a string are decoded from its UTF-8 bytes, so the index advances by
+        // the size of each one.
+        if iterable_type == SemanticType::String {
+            ccode.push_typedef(String::from(
+                "unsigned int __utf8_decode(const char* s,signed int* size){const unsigned char* b=(const unsigned char*)s;if(b[0]<0x80){*size=1;return b[0];}if((b[0]&0xE0)==0xC0&&(b[1]&0xC0)==0x80){*size=2;return (b[0]&0x1F)<<6|(b[1]&0x3F);}if((b[0]&0xF0)==0xE0&&(b[1]&0xC0)==0x80&&(b[2]&0xC0)==0x80){*size=3;return (b[0]&0x0F)<<12|(b[1]&0x3F)<<6|(b[2]&0x3F);}if((b[0]&0xF8)==0xF0&&(b[1]&0xC0)==0x80&&(b[2]&0xC0)==0x80&&(b[3]&0xC0)==0x80){*size=4;return (b[0]&0x07)<<18|(b[1]&0x3F)<<12|(b[2]&0x3F)<<6|(b[3]&0x3F);}*size=1;return 0xFFFD;}",
+            ));
+
+            let size_name = format!("__size_{}", identifier_name);
+
+            ccode.push(&format!(
+                "for(signed int {}=0,{}=0;{};{}+={}){{",
+                index_name, size_name, condition, index_name, size_name
+            ));
+            ccode.push(&format!(
+                "const unsigned int {}=__utf8_decode({}+{},&{});",
+                identifier_name, iterable_name, index_name, size_name
+            ));
+
+            BlockGenerator::generate_loop_body(&r#for.block, &r#for.label, scopes, ccode);
+            ccode.push("}");
+            return;
+        }
+
+        ccode.push(&format!(
+            "for(signed int {}=0;{};{}++){{",
+            index_name, condition, index_name
+        ));
+
+        // Strings are arrays in C, so the element is kept as a pointer to the original one.
+        let c_element_type = match element_type {
+            SemanticType::String => String::from("const char*"),
+            element_type => ccode.get_binding_type(element_type, false),
+        };
+
+        let elements = match &iterable_type {
+            SemanticType::Slice(_) => format!("{}.data", iterable_name),
+            SemanticType::Vec(_) => format!("{}->data", iterable_name),
+            _ => iterable_name,
+        };
+
+        ccode.push(&format!(
+            "{} {}={}[{}];",
+            c_element_type, identifier_name, elements, index_name
+        ));
+
+        BlockGenerator::generate_loop_body(&r#for.block, &r#for.label, scopes, ccode);
+        ccode.push("}");
     }
 }
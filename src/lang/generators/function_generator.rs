@@ -1,6 +1,8 @@
 use crate::lang::semantic::analyzer::Scopes;
 use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::symbol::Symbol;
 use crate::lang::syntax::top_level_statements::function::Function;
+use crate::lang::syntax::top_level_statements::r#extern::Extern;
 
 use super::block_generator::BlockGenerator;
 use super::c_code_generator2::CCode;
@@ -9,27 +11,51 @@ pub struct FunctionGenerator;
 
 impl FunctionGenerator {
     pub fn generate(function: &Function, scopes: &Scopes, ccode: &mut CCode) {
-        let name = function.identifier.name.clone();
+        Self::generate_instance(function, &function.identifier.name, scopes, ccode)
+    }
+
+    /// Generates a function under the given name. The types of the parameters and of the return
+    /// are taken from the scope of the function, where the type parameters of a generic function
+    /// were replaced by the type arguments of the instance.
+    pub fn generate_instance(function: &Function, name: &str, scopes: &Scopes, ccode: &mut CCode) {
         let is_main = name == "main";
+        let scope = scopes.get(&function.block.id).unwrap().clone();
 
         // Already validated in the semantic analyses
         let return_type = if is_main {
             SemanticType::I32
         } else {
-            function.r#type.as_ref().map_or(SemanticType::Void, |id| {
-                SemanticType::from_syntax(id.clone())
-            })
+            match scope.borrow().get_function_type() {
+                Some(SemanticType::Function(_, return_type)) => *return_type,
+                _ => SemanticType::Void,
+            }
         };
 
         let c_return_type = ccode.get_type(return_type);
-        ccode.push(&format!("{} {}(", c_return_type, name));
+
+        let c_name = if function.export {
+            String::from(name)
+        } else {
+            CCode::get_function_name(name)
+        };
+
+        ccode.push(&format!("{} {}(", c_return_type, c_name));
 
         let mut params: Vec<String> = vec![];
 
         for param in &function.params_declaration.params {
-            let param_type = SemanticType::from_syntax(param.r#type.clone());
+            let param_type = match scope.borrow().get(&param.identifier.name) {
+                Some(Symbol::Parameter { symbol_type, .. }) => symbol_type,
+                _ => SemanticType::Any,
+            };
+
+            let is_receiver = Self::is_receiver(&param.identifier.name, &param_type);
+            let mut c_param_type = ccode.get_binding_type(param_type, param.mutable);
+
+            if is_receiver {
+                c_param_type.push('*');
+            }
 
-            let c_param_type = ccode.get_type(param_type);
             params.push(format!("{} {}", c_param_type, param.identifier.name));
         }
 
@@ -62,19 +88,96 @@ impl FunctionGenerator {
             });
 
         let c_return_type = ccode.get_type(return_type);
-        ccode.push(&format!("{} {}(", c_return_type, name));
+
+        let c_name = if function.export {
+            name
+        } else {
+            CCode::get_function_name(&name)
+        };
+
+        ccode.push(&format!("{} {}(", c_return_type, c_name));
 
         let mut params: Vec<String> = vec![];
 
         for param in &function.params_declaration.params {
             let param_type = SemanticType::from_syntax(param.r#type.clone());
 
-            let c_param_type = ccode.get_type(param_type);
-            params.push(c_param_type);
+            // The receiver points to a constant value unless it is declared as `mut self`.
+            if Self::is_receiver(&param.identifier.name, &param_type) {
+                let c_param_type = ccode.get_binding_type(param_type, param.mutable);
+                params.push(format!("{}*", c_param_type));
+            } else {
+                params.push(ccode.get_type(param_type));
+            }
         }
 
         ccode.push(&params.join(","));
 
         ccode.push(");");
     }
+
+    /// Declares a function implemented in C by including its header or, without one, with a
+    /// prototype made from its signature, where strings are taken as `const char*` and C strings
+    /// are returned as `char*`, as the functions of C declare them.
+    pub fn generate_extern(r#extern: &Extern, ccode: &mut CCode) {
+        if let Some(header) = &r#extern.header {
+            ccode.push_import(&format!("#include<{}>", header.value));
+            return;
+        }
+
+        let signature = &r#extern.signature;
+
+        let return_type = signature
+            .r#type
+            .as_ref()
+            .map_or(SemanticType::Void, |r#type| {
+                SemanticType::from_syntax(r#type.clone())
+            });
+
+        let c_return_type = match return_type {
+            SemanticType::CString => String::from("char*"),
+            return_type => ccode.get_type(return_type),
+        };
+
+        let params: Vec<String> = signature
+            .params_declaration
+            .params
+            .iter()
+            .map(
+                |param| match SemanticType::from_syntax(param.r#type.clone()) {
+                    SemanticType::String => String::from("const char*"),
+                    param_type => ccode.get_type(param_type),
+                },
+            )
+            .collect();
+
+        ccode.push(&format!(
+            "{} {}({});",
+            c_return_type,
+            signature.identifier.name,
+            params.join(",")
+        ));
+    }
+
+    /// Whether the parameter is the value a method is called on, which is taken by reference
+    /// unless it is an array, as arrays are already passed as pointers.
+    fn is_receiver(name: &str, r#type: &SemanticType) -> bool {
+        name == "self" && !matches!(r#type, SemanticType::Array(..))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::generators::test_helpers::generate;
+
+    #[test]
+    fn test_extern_declarations() {
+        // Returned C strings are declared as libc does, so the declarations agree with the
+        // headers a vec brings in.
+        let code = r#"extern fun getenv(name: cstring) -> cstring; fun main() { let name = "USER"; let user = "{getenv(name)}"; let mut v: vec<i32> = [1]; push(v, 2); }"#;
+        let c = generate(code);
+        assert!(c.contains("#include<stdlib.h>"));
+        assert!(c.contains("char* getenv(const char*);"));
+        assert!(!c.contains("const char* getenv("));
+    }
 }
@@ -0,0 +1,61 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::lang::position::Positioned;
+use crate::lang::semantic::{analyzer::Scopes, scope::Scope, statements::if_analyzer::IfAnalyzer};
+use crate::lang::syntax::statements::r#if::If;
+
+use super::{
+    block_generator::BlockGenerator, c_code_generator2::CCode,
+    expression_generator::ExpressionGenerator,
+};
+
+pub struct IfGenerator;
+
+impl IfGenerator {
+    /// Generates an `if` expression. One that only chooses between two values is a conditional
+    /// expression, otherwise it is generated before the statement where it is written and its
+    /// value is stored in a hidden variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `r#if` - The if expression to be generated.
+    /// * `scope` - A reference-counted reference to the scope in which the if is written.
+    /// * `ccode` - Where the code is generated.
+    ///
+    /// # Returns
+    ///
+    /// The code of the value of the if, such as `(a?b:c)` or `__if_3_13`.
+    pub fn generate(r#if: &If, scope: Rc<RefCell<Scope>>, ccode: &mut CCode) -> String {
+        let mut scopes = Scopes::new();
+        let return_type = IfAnalyzer::analyze(r#if, Rc::clone(&scope), &mut scopes).return_type;
+
+        if let (Some((then_value, else_value)), Some(r#else)) = (r#if.get_values(), &r#if.r#else) {
+            // The values are only computed in their own branch.
+            if then_value.find_hoisted().is_none() && else_value.find_hoisted().is_none() {
+                let condition = ExpressionGenerator::generate(&r#if.expression, scope, ccode);
+
+                let then_code = ExpressionGenerator::generate_owned(
+                    then_value,
+                    &return_type,
+                    Rc::clone(&scopes[&r#if.block.id]),
+                    ccode,
+                );
+                let else_code = ExpressionGenerator::generate_owned(
+                    else_value,
+                    &return_type,
+                    Rc::clone(&scopes[&r#else.block.id]),
+                    ccode,
+                );
+
+                return format!("({}?{}:{})", condition, then_code, else_code);
+            }
+        }
+
+        let position = r#if.get_position();
+        let name = format!("__if_{}_{}", position.line, position.column);
+
+        ccode.hoist_value(&name, &return_type, |ccode| {
+            BlockGenerator::generate_if(r#if, Some((&name, &return_type)), &scopes, scope, ccode);
+        })
+    }
+}
@@ -0,0 +1,184 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::lang::{
+    semantic::{
+        expressions::expression_analyzer::ExpressionAnalyzer, scope::Scope,
+        semantic_type::SemanticType,
+    },
+    syntax::expressions::{
+        expression::Expression,
+        interpolation::{Format, Interpolation, InterpolationPart},
+    },
+};
+
+use super::{c_code_generator2::CCode, expression_generator::ExpressionGenerator};
+
+pub struct InterpolationGenerator;
+
+impl InterpolationGenerator {
+    /// Generates an interpolated string, which is formatted into a buffer that lives until the
+    /// end of the statement where it is written, as the other strings are copied from it.
+    ///
+    /// # Arguments
+    ///
+    /// * `interpolation` - The interpolated string to be generated.
+    /// * `scope` - A reference-counted reference to the scope in which it is written.
+    /// * `ccode` - Where the code is generated.
+    ///
+    /// # Returns
+    ///
+    /// The code of the formatted string, such as `__format("%d items",count).data`.
+    pub fn generate(
+        interpolation: &Interpolation,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> String {
+        ccode.push_import("#include<stdio.h>");
+        ccode.push_import("#include<stdarg.h>");
+
+        ccode.push_typedef(String::from("typedef struct{char data[256];}__formatted;"));
+        ccode.push_typedef(String::from(
+            "__formatted __format(const char* format,...){__formatted s;va_list args;va_start(args,format);vsnprintf(s.data,sizeof(s.data),format,args);va_end(args);return s;}",
+        ));
+
+        let (format, arguments) = Self::generate_format(interpolation, scope, ccode);
+
+        format!("__format(\"{}\"{}).data", format, arguments)
+    }
+
+    /// Generates the `printf` format of an interpolated string along with its arguments, each
+    /// preceded by a comma.
+    pub fn generate_format(
+        interpolation: &Interpolation,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> (String, String) {
+        let mut format = String::new();
+        let mut arguments = String::new();
+
+        for part in &interpolation.parts {
+            match part {
+                InterpolationPart::Text(text) => {
+                    format.push_str(&CCode::escape_string(&text.replace('%', "%%")))
+                }
+                InterpolationPart::Value(expression, value_format) => {
+                    let (specifier, argument) =
+                        Self::generate_value(expression, value_format, Rc::clone(&scope), ccode);
+
+                    format.push_str(&specifier);
+                    arguments.push_str(&format!(",{}", argument));
+                }
+            }
+        }
+
+        (format, arguments)
+    }
+
+    /// Generates a value written with the given format.
+    ///
+    /// # Returns
+    ///
+    /// The `printf` specifier of the value, such as `%08.3lf`, and the code of the value.
+    pub fn generate_value(
+        expression: &Expression,
+        format: &Format,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) -> (String, String) {
+        let r#type = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type;
+        let value = ExpressionGenerator::generate(expression, scope, ccode);
+
+        let conversion = match (&r#type, format.hex) {
+            (SemanticType::I64 | SemanticType::U64, Some(false)) => "llx",
+            (SemanticType::I64 | SemanticType::U64, Some(true)) => "llX",
+            (_, Some(false)) => "x",
+            (_, Some(true)) => "X",
+            (
+                SemanticType::String
+                | SemanticType::CString
+                | SemanticType::Bool
+                | SemanticType::Char,
+                _,
+            ) => "s",
+            (SemanticType::I8 | SemanticType::I16 | SemanticType::I32, _) => "d",
+            (SemanticType::U8 | SemanticType::U16 | SemanticType::U32, _) => "u",
+            (SemanticType::I64, _) => "lld",
+            (SemanticType::U64, _) => "llu",
+            (SemanticType::F32, _) => "f",
+            (SemanticType::F64, _) => "lf",
+            (SemanticType::Ref(_), _) => "p",
+            _ => "",
+        };
+
+        let mut specifier = String::from("%");
+
+        if format.left {
+            specifier.push('-');
+        }
+
+        if format.zero {
+            specifier.push('0');
+        }
+
+        if let Some(width) = format.width {
+            specifier.push_str(&width.to_string());
+        }
+
+        if let Some(precision) = format.precision {
+            specifier.push_str(&format!(".{}", precision));
+        }
+
+        specifier.push_str(conversion);
+
+        // A character takes a single column whatever the number of its bytes, which `printf`
+        // would count instead, so it is padded with the spaces that are left.
+        let specifier = match (&r#type, format.width) {
+            (SemanticType::Char, Some(width)) => {
+                let padding = " ".repeat(width.saturating_sub(1));
+
+                if format.left {
+                    format!("%s{}", padding)
+                } else {
+                    format!("{}%s", padding)
+                }
+            }
+            _ => specifier,
+        };
+
+        // A null `cstring`, as C functions return when there is no string, is written as empty,
+        // and a character is written as its UTF-8 bytes.
+        let value = if r#type.is_bool() {
+            format!("({})?\"true\":\"false\"", value)
+        } else if r#type == SemanticType::Char {
+            ccode.push_typedef(String::from("typedef struct{char data[5];}__utf8;"));
+            ccode.push_typedef(String::from(
+                "__utf8 __utf8_encode(unsigned int c){__utf8 s={{0}};if(c<0x80){s.data[0]=c;}else if(c<0x800){s.data[0]=0xC0|c>>6;s.data[1]=0x80|(c&0x3F);}else if(c<0x10000){s.data[0]=0xE0|c>>12;s.data[1]=0x80|(c>>6&0x3F);s.data[2]=0x80|(c&0x3F);}else{s.data[0]=0xF0|c>>18;s.data[1]=0x80|(c>>12&0x3F);s.data[2]=0x80|(c>>6&0x3F);s.data[3]=0x80|(c&0x3F);}return s;}",
+            ));
+
+            format!("__utf8_encode({}).data", value)
+        } else if r#type == SemanticType::CString {
+            ccode.push_typedef(String::from(
+                "const char* __cstring(const char* s){return s?s:\"\";}",
+            ));
+
+            format!("__cstring({})", value)
+        } else {
+            value
+        };
+
+        (specifier, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::generators::test_helpers::generate;
+
+    #[test]
+    fn test_character_widths() {
+        let code = generate("fun main() { let c = 'é'; println \"[{c:3}] [{c:<3}] [{c:1}]\"; }");
+
+        // A character is padded by columns, as its bytes would be counted by `printf`.
+        assert!(code.contains("printf(\"[  %s] [%s  ] [%s]\""));
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::{cell::RefCell, rc::Rc};
+
+use crate::lang::semantic::{
+    analyzer::Scopes, expressions::lambda_analyzer::LambdaAnalyzer, scope::Scope,
+    semantic_type::SemanticType, symbol::Symbol,
+};
+use crate::lang::syntax::expressions::lambda::Lambda;
+
+use super::{
+    array_generator::ArrayGenerator, block_generator::BlockGenerator, c_code_generator2::CCode,
+    expression_generator::ExpressionGenerator,
+};
+
+pub struct LambdaGenerator;
+
+impl LambdaGenerator {
+    /// Generates a lambda expression as a function that receives the values it captures in an
+    /// environment, which is allocated when the lambda is created and released when the last
+    /// copy of the function value is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `lambda` - The lambda expression to be generated.
+    /// * `scope` - A reference-counted reference to the scope in which the lambda is written.
+    /// * `ccode` - Where the code is generated.
+    ///
+    /// # Returns
+    ///
+    /// The code of the function value, such as `((__fn_1){__env_2_new(x),__lambda_2})`.
+    pub fn generate(lambda: &Lambda, scope: Rc<RefCell<Scope>>, ccode: &mut CCode) -> String {
+        let mut scopes = Scopes::new();
+        let function_type =
+            LambdaAnalyzer::analyze_body(lambda, Rc::clone(&scope), &mut scopes).return_type;
+
+        let lambda_scope = scopes[&lambda.block.id].clone();
+
+        let captures: Vec<(String, SemanticType)> = lambda_scope
+            .borrow()
+            .get_captures()
+            .into_iter()
+            .map(|name| {
+                let symbol_type = match scope.borrow().get(&name) {
+                    Some(Symbol::Variable { symbol_type, .. })
+                    | Some(Symbol::Parameter { symbol_type, .. }) => symbol_type,
+                    _ => SemanticType::Any,
+                };
+
+                (name, symbol_type)
+            })
+            .collect();
+
+        // The same lambda is generated once for each instance of a generic function, where its
+        // types may differ.
+        let mut hasher = DefaultHasher::new();
+        lambda.block.id.hash(&mut hasher);
+        function_type.hash(&mut hasher);
+        captures.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let c_type = ccode.get_type(function_type.clone());
+        let name = format!("__lambda_{hash}");
+        let env = format!("__env_{hash}");
+
+        let return_type = match &function_type {
+            SemanticType::Function(_, return_type) => return_type.as_ref().clone(),
+            _ => SemanticType::Void,
+        };
+
+        let mut params: Vec<String> = vec![String::from("void* __env_ptr")];
+
+        for param in &lambda.params_declaration.params {
+            let param_type = match lambda_scope.borrow().get(&param.identifier.name) {
+                Some(Symbol::Parameter { symbol_type, .. }) => symbol_type,
+                _ => SemanticType::Any,
+            };
+
+            let c_param_type = ccode.get_binding_type(param_type, param.mutable);
+            params.push(format!("{} {}", c_param_type, param.identifier.name));
+        }
+
+        let c_return_type = ccode.get_type(return_type);
+        let prototype = format!("{} {}({})", c_return_type, name, params.join(","));
+
+        if !captures.is_empty() {
+            Self::generate_environment(&env, &captures, ccode);
+        }
+
+        if ccode.push_typedef(format!("{};", prototype)) {
+            ccode.push_function(|ccode| {
+                ccode.push(&prototype);
+                ccode.push("{");
+
+                if !captures.is_empty() {
+                    ccode.push(&format!("{}* __env=__env_ptr;", env));
+                }
+
+                BlockGenerator::generate(&lambda.block, &scopes, ccode);
+                ccode.push("}");
+            });
+        }
+
+        if captures.is_empty() {
+            return format!("(({}){{0,{}}})", c_type, name);
+        }
+
+        // The captured values are copied into the environment, along with the memory they own.
+        let mut values: Vec<String> = vec![];
+
+        for (capture, capture_type) in &captures {
+            let captured = scope.borrow().get_captured(capture);
+
+            let value = match captured {
+                Some(_) => format!("__env->{}", capture),
+                None => ExpressionGenerator::generate_binding(capture, capture_type),
+            };
+
+            values.push(if capture_type.is_owned() {
+                format!("{}_copy({})", ccode.get_type(capture_type.clone()), value)
+            } else {
+                value
+            });
+        }
+
+        format!(
+            "(({}){{{}_new({}),{}}})",
+            c_type,
+            env,
+            values.join(","),
+            name
+        )
+    }
+
+    /// Declares the structure that holds the values captured by a lambda, along with the
+    /// functions that allocate and release it.
+    fn generate_environment(env: &str, captures: &[(String, SemanticType)], ccode: &mut CCode) {
+        let mut fields = String::new();
+        let mut params: Vec<String> = vec![];
+        let mut stores = String::new();
+        let mut frees = String::new();
+
+        for (name, r#type) in captures {
+            let c_type = ccode.get_type(r#type.clone());
+            let target = format!("env->{}", name);
+
+            // An array is a pointer in C, so its elements are copied into the environment.
+            let (field, store) = match r#type {
+                SemanticType::Array(..) => (
+                    ArrayGenerator::generate_storage(name, r#type, ccode),
+                    ArrayGenerator::generate_assignment(&target, name, r#type, ccode),
+                ),
+                _ => (
+                    format!("{} {}", c_type, name),
+                    format!("{};", ccode.assign(&target, name, r#type)),
+                ),
+            };
+
+            fields.push_str(&format!("{};", field));
+            params.push(format!("{} {}", c_type, name));
+            stores.push_str(&store);
+
+            if r#type.is_owned() {
+                frees.push_str(&format!("{}_free(env->{});", c_type, name));
+            }
+        }
+
+        ccode.push_import("#include<stdlib.h>");
+
+        ccode.push_typedef(format!(
+            "typedef struct{{__env_header header;{}}}{};",
+            fields, env
+        ));
+        ccode.push_typedef(format!(
+            "void {env}_free(void* ptr){{{env}* env=ptr;{frees}free(env);}}"
+        ));
+        ccode.push_typedef(format!(
+            "{env}* {env}_new({}){{{env}* env=malloc(sizeof(*env));env->header=(__env_header){{1,{env}_free}};{stores}return env;}}",
+            params.join(",")
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::generators::test_helpers::generate;
+
+    #[test]
+    fn test_captures() {
+        // Only the bindings around the lambda are captured, once each.
+        let code = "fun main() { let a = 1; let b = 2; let f = fun(x: i32) -> i32 { let c = 3; return a + a + c + x; }; println f(b); }";
+        let content = generate(code);
+        assert!(content.contains("{__env_header header;signed int a;}"));
+
+        // The environment is released along with the memory of the values it holds.
+        let code = "fun main() { let v: vec<i32> = [1]; let f = fun() -> i32 { return len(v); }; println f(); }";
+        let content = generate(code);
+        assert!(content.contains("_free(env->v);free(env);"));
+        assert!(content.contains("_copy(v)"));
+    }
+
+    #[test]
+    fn test_array_captures() {
+        // The elements of a captured array are copied, so that the lambda does not read the
+        // frame it was created in.
+        let code = "fun make() -> () -> i32 { let mut a = [7, 8, 9]; let f = fun() -> i32 { return a[0]; }; a[0] = 99; return f; } fun main() { println make()(); }";
+        let content = generate(code);
+        assert!(content.contains("{__env_header header;signed int a[3];}"));
+        assert!(content.contains("memmove(env->a,a,sizeof(signed int[3]));"));
+
+        let code = "fun main() { let g = [[1, 2], [3, 4]]; let h = fun() -> i32 { return g[1][0]; }; println h(); }";
+        let content = generate(code);
+        assert!(content.contains("{__env_header header;signed int g[2][2];}"));
+    }
+}
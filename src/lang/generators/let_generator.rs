@@ -2,7 +2,9 @@ use std::{cell::RefCell, rc::Rc};
 
 use crate::lang::{
     semantic::{scope::Scope, semantic_type::SemanticType, symbol::Symbol},
-    syntax::statements::r#let::Let,
+    syntax::{
+        expressions::expression::Expression, shared::identifier::Identifier, statements::r#let::Let,
+    },
 };
 
 use super::{
@@ -17,15 +19,34 @@ impl LetGenerator {
         let identifier_name = r#let.identifier.name.clone();
         let type_identifier = scope.borrow().get(&identifier_name).unwrap();
 
-        if let Symbol::Variable { symbol_type, .. } = type_identifier {
-            let c_type = ccode.get_type(symbol_type.clone());
-            ccode.push(&format!("{} {}", c_type, identifier_name.clone()));
+        if let Symbol::Variable {
+            symbol_type,
+            mutable,
+            ..
+        } = type_identifier
+        {
+            let c_type = ccode.get_binding_type(symbol_type.clone(), mutable);
 
             if let Some(expression) = &r#let.expression {
-                ccode.push("=");
-                let code = ExpressionGenerator::generate(expression, Rc::clone(&scope), ccode);
-                ccode.push(&code);
+                let mut code = ExpressionGenerator::generate_owned(
+                    expression,
+                    &symbol_type,
+                    Rc::clone(&scope),
+                    ccode,
+                );
+
+                // Arrays are pointers in C, so the elements are copied instead of shared.
+                if matches!(symbol_type, SemanticType::Array(..))
+                    && !matches!(expression, Expression::Array(..))
+                {
+                    code = ArrayGenerator::generate_copy_of(&code, &symbol_type, ccode);
+                }
+
+                let declaration = ccode.declare(&c_type, &identifier_name, &code, &symbol_type);
+                ccode.push(&declaration);
             } else {
+                ccode.push(&format!("{} {}", c_type, identifier_name));
+
                 if let SemanticType::Array(_, _) = symbol_type {
                     ccode.push("=");
                     let value = &ArrayGenerator::generate_expression(
@@ -37,9 +58,91 @@ impl LetGenerator {
                     );
                     ccode.push(value);
                 }
+
+                // A value that was not assigned yet has nothing to be freed.
+                match symbol_type {
+                    SemanticType::Vec(_) => ccode.push("=0"),
+                    SemanticType::Tuple(_) | SemanticType::Optional(_)
+                        if symbol_type.is_owned() =>
+                    {
+                        ccode.push("={0}")
+                    }
+                    _ => {}
+                }
+            }
+
+            // The elements of a destructured tuple own its memory instead.
+            if symbol_type.is_owned() && r#let.elements.is_empty() {
+                let c_owned_type = ccode.get_type(symbol_type.clone());
+                ccode.add_drop(format!("{}_free({});", c_owned_type, identifier_name));
+            }
+        }
+
+        ccode.push(";");
+
+        for (index, element) in r#let.elements.iter().enumerate() {
+            Self::generate_element(element, index, &identifier_name, Rc::clone(&scope), ccode);
+        }
+    }
+
+    /// Generates a variable that receives an element of a destructured tuple.
+    fn generate_element(
+        element: &Identifier,
+        index: usize,
+        tuple_name: &str,
+        scope: Rc<RefCell<Scope>>,
+        ccode: &mut CCode,
+    ) {
+        let symbol = scope.borrow().get(&element.name);
+
+        if let Some(Symbol::Variable {
+            symbol_type,
+            mutable,
+            ..
+        }) = symbol
+        {
+            let c_type = ccode.get_binding_type(symbol_type.clone(), mutable);
+            let value = format!("{}.e{}", tuple_name, index);
+
+            let declaration = ccode.declare(&c_type, &element.name, &value, &symbol_type);
+            ccode.push(&format!("{};", declaration));
+
+            if symbol_type.is_owned() {
+                let c_owned_type = ccode.get_type(symbol_type);
+                ccode.add_drop(format!("{}_free({});", c_owned_type, element.name));
             }
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::generators::test_helpers::generate;
+
+    #[test]
+    fn test_array_copies() {
+        // A binding gets its own elements instead of a pointer to the ones of the original.
+        let code = generate("fun main() { let a = [1, 2]; let mut b = a; b[0] = 9; }");
+        assert!(code.contains("memcpy((signed int[2]){0},a,sizeof(signed int[2]))"));
+
+        let code = generate("fun main() { let g = [[1, 2], [3, 4]]; let mut row = g[0]; }");
+        assert!(code.contains("memcpy((signed int[2]){0},g[0],sizeof(signed int[2]))"));
+
+        // An assignment copies the elements into the ones the binding already has.
+        let code = generate("fun main() { let a = [1, 2]; let mut b = [0, 0]; b = a; }");
+        assert!(code.contains("memmove(b,a,sizeof(signed int[2]));"));
+
+        // A new array is not seen by anyone else.
+        let code = generate("fun main() { let a = [1, 2]; }");
+        assert!(!code.contains("memcpy"));
+    }
 
-        ccode.push(";")
+    #[test]
+    fn test_string_copies() {
+        // Strings are arrays in C, so they are copied into the bindings and the tuples.
+        let code = generate("fun main() { let (a, b) = (1, \"b\"); let c = b; }");
+        assert!(code.contains("strcpy(t.e1,e1);"));
+        assert!(code.contains("b;strcpy(b,"));
+        assert!(code.contains("c;strcpy(c,b);"));
     }
 }
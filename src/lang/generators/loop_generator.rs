@@ -0,0 +1,46 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::lang::position::Positioned;
+use crate::lang::semantic::{
+    analyzer::Scopes, scope::Scope, statements::loop_analyzer::LoopAnalyzer,
+};
+use crate::lang::syntax::statements::r#loop::Loop;
+
+use super::{block_generator::BlockGenerator, c_code_generator2::CCode};
+
+pub struct LoopGenerator;
+
+impl LoopGenerator {
+    /// Generates a `loop` expression before the statement where it is written, as loops are not
+    /// expressions in C. The value given to the `break` that leaves it is stored in a hidden
+    /// variable, which is read in place of the expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `r#loop` - The loop expression to be generated.
+    /// * `scope` - A reference-counted reference to the scope in which the loop is written.
+    /// * `ccode` - Where the code is generated.
+    ///
+    /// # Returns
+    ///
+    /// The code of the value of the loop, such as `__loop_3_13`.
+    pub fn generate(r#loop: &Loop, scope: Rc<RefCell<Scope>>, ccode: &mut CCode) -> String {
+        let mut scopes = Scopes::new();
+        let return_type = LoopAnalyzer::analyze_body(r#loop, scope, &mut scopes).return_type;
+
+        let position = r#loop.get_position();
+        let name = format!("__loop_{}_{}", position.line, position.column);
+        let label = r#loop.label.as_ref().map(|label| label.name.as_str());
+
+        ccode.hoist_value(&name, &return_type, |ccode| {
+            ccode.enter_loop_value(label, &name, return_type.clone());
+
+            BlockGenerator::generate_labeled(&r#loop.label, ccode, |ccode| {
+                ccode.push("while(1)");
+                BlockGenerator::generate_loop_body(&r#loop.block, &r#loop.label, &scopes, ccode);
+            });
+
+            ccode.exit_loop_value();
+        })
+    }
+}
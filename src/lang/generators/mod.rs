@@ -1,9 +1,16 @@
 pub mod array_generator;
 pub mod block_generator;
+pub mod builtin_generator;
 pub mod c_code_generator2;
 pub mod expression_generator;
 pub mod expression_meta_generator;
 pub mod for_generator;
 pub mod function_generator;
+pub mod if_generator;
+pub mod interpolation_generator;
+pub mod lambda_generator;
 pub mod let_generator;
+pub mod loop_generator;
 pub mod print_generator;
+#[cfg(test)]
+pub mod test_helpers;
@@ -0,0 +1,14 @@
+//! Helpers shared by the tests of the generators.
+
+use crate::lang::{semantic::analyzer::Analyzer, syntax::parser::Parser};
+
+use super::c_code_generator2::CCodeGenerator2;
+
+/// Generates the C code of a program.
+pub fn generate(code: &str) -> String {
+    let ast = Parser::from_code(code).parse().unwrap();
+    let analyzer = Analyzer::analyze(&ast);
+    CCodeGenerator2::new(&ast, &analyzer.scopes)
+        .generate()
+        .content()
+}
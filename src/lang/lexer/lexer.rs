@@ -4,24 +4,43 @@ use super::token::Token;
 use super::token_kind::TokenKind;
 
 pub struct Lexer {
-    text: String,
+    /// The characters of the code, which are indexed by their positions, as a character of
+    /// UTF-8 text may take more than one byte.
+    text: Vec<char>,
     position: usize,
     current_position: Position,
 }
 
 impl Lexer {
     pub fn new(text: &str) -> Self {
+        Self::with_position(text, Position::new(1, 1))
+    }
+
+    /// Creates a lexer for a text found at the given position of the code, such as a value
+    /// written in an interpolated string, so that its tokens are positioned in the code.
+    pub fn with_position(text: &str, position: Position) -> Self {
         Self {
-            current_position: Position::new(1, 1),
-            text: text.to_string(),
+            current_position: position,
+            text: text.chars().collect(),
             position: 0,
         }
     }
 
     fn get_current_char(&self) -> char {
-        let current_char = self.text.chars().nth(self.position).unwrap_or('\0');
+        self.peek_char(0)
+    }
 
-        current_char
+    /// Gets the character the given number of characters after the current one.
+    fn peek_char(&self, offset: usize) -> char {
+        self.text
+            .get(self.position + offset)
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    /// Gets the text between the given positions.
+    fn get_text(&self, start: usize, end: usize) -> String {
+        self.text[start..end].iter().collect()
     }
 
     fn next_char(&mut self) -> char {
@@ -44,6 +63,13 @@ impl Lexer {
         let mut token = self.next();
 
         while token.kind != TokenKind::EndOfFile {
+            if token.kind == TokenKind::InvalidEscape {
+                return Err(format!(
+                    "Invalid escape sequence '{}' found at Line {} and Column {}",
+                    token.value, token.position.line, token.position.column
+                ));
+            }
+
             if token.kind == TokenKind::BadToken {
                 return Err(format!(
                     "Invalid token found at Line {} and Column {}",
@@ -75,6 +101,14 @@ impl Lexer {
         }
 
         if self.get_current_char() == '\'' {
+            let next_char = self.peek_char(1);
+            let closing_char = self.peek_char(2);
+
+            // A name that is not closed by a "'" is a label, as in `'outer: for ...`.
+            if (next_char.is_alphabetic() || next_char == '_') && closing_char != '\'' {
+                return self.read_label();
+            }
+
             return self.read_char();
         }
 
@@ -101,6 +135,10 @@ impl Lexer {
                 self.next_char();
                 Token::new(TokenKind::Colon, position, ":")
             }
+            '?' => {
+                self.next_char();
+                Token::new(TokenKind::Question, position, "?")
+            }
             '{' => {
                 self.next_char();
                 Token::new(TokenKind::LeftBrace, position, "{")
@@ -312,7 +350,7 @@ impl Lexer {
         let position = self.current_position;
         let start = self.position;
         let mut end = self.position;
-        let number: &str;
+        let number: String;
 
         while self.get_current_char().is_digit(10) {
             end += 1;
@@ -321,15 +359,13 @@ impl Lexer {
 
         // decimal value
         if self.get_current_char() != '.' {
-            number = &self.text[start..end];
-            return Token::new(TokenKind::NumberLiteral, position, number);
+            number = self.get_text(start, end);
+            return Token::new(TokenKind::NumberLiteral, position, &number);
         }
 
-        if let Some(c) = self.text.chars().nth(self.position + 1) {
-            if c == '.' {
-                number = &self.text[start..end];
-                return Token::new(TokenKind::NumberLiteral, position, number);
-            }
+        if self.peek_char(1) == '.' {
+            number = self.get_text(start, end);
+            return Token::new(TokenKind::NumberLiteral, position, &number);
         }
 
         end += 1;
@@ -344,66 +380,157 @@ impl Lexer {
             self.next_char();
         }
 
-        number = &self.text[start..end];
-        Token::new(TokenKind::NumberLiteral, position, number)
+        number = self.get_text(start, end);
+        Token::new(TokenKind::NumberLiteral, position, &number)
     }
 
+    /// Reads a character, whose value is the Unicode scalar it writes, with its escape
+    /// sequence decoded.
     fn read_char(&mut self) -> Token {
         let position = self.current_position;
-        let mut start = self.position;
-        let mut end = self.position;
 
         // consumes the "'"
-        end += 1;
-        start += 1;
         self.next_char();
 
-        if self.get_current_char() == '\\' {
-            end += 1;
+        let value = if self.get_current_char() == '\\' {
+            match self.read_escape() {
+                Ok(value) => value,
+                Err(token) => return token,
+            }
+        } else {
+            // consumes the char
+            self.next_char()
+        };
 
-            // consumes the "\"
+        if self.get_current_char() == '\'' {
+            // consumes the "'"
             self.next_char();
+
+            return Token::new(TokenKind::CharLiteral, position, &value.to_string());
         }
 
-        end += 1;
-        // consumes the char
+        Token::new(TokenKind::BadToken, self.current_position, "")
+    }
+
+    /// Reads the escape sequence at the current "\", which is one of `\n`, `\t`, `\r`, `\0`,
+    /// `\\`, `\'`, `\"` or `\u{...}`, with the hexadecimal code of a Unicode scalar.
+    ///
+    /// # Returns
+    /// - `Ok(char)`: The character written by the escape sequence.
+    /// - `Err(Token)`: An invalid escape token, positioned at the "\".
+    fn read_escape(&mut self) -> Result<char, Token> {
+        let position = self.current_position;
+        let start = self.position;
+
+        // consumes the "\"
         self.next_char();
 
-        if self.get_current_char() == '\'' {
-            // consumes the "'"
+        let value = match self.next_char() {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            '0' => Some('\0'),
+            char @ ('\\' | '\'' | '"') => Some(char),
+            'u' if self.get_current_char() == '{' => {
+                self.next_char();
+
+                let mut code = String::new();
+
+                while self.get_current_char().is_ascii_hexdigit() && code.len() < 6 {
+                    code.push(self.next_char());
+                }
+
+                if self.get_current_char() == '}' {
+                    self.next_char();
+                    u32::from_str_radix(&code, 16).ok().and_then(char::from_u32)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        value.ok_or_else(|| {
+            let end = self.position.min(self.text.len());
+            Token::new(
+                TokenKind::InvalidEscape,
+                position,
+                &self.get_text(start, end),
+            )
+        })
+    }
+
+    fn read_label(&mut self) -> Token {
+        let position = self.current_position;
+
+        // consumes the "'"
+        self.next_char();
+
+        let start = self.position;
+        let mut end = self.position;
+
+        while self.get_current_char().is_alphanumeric() || self.get_current_char() == '_' {
             self.next_char();
+            end += 1;
+        }
 
-            let c = &self.text[start..end];
-            return Token::new(TokenKind::CharLiteral, position, c);
+        // A label is always followed by a colon or by the end of a statement.
+        if self.get_current_char() == '\0' {
+            return Token::new(TokenKind::BadToken, self.current_position, "");
         }
 
-        Token::new(TokenKind::BadToken, self.current_position, "")
+        let label = self.get_text(start, end);
+        Token::new(TokenKind::Label, position, &label)
     }
 
+    /// Reads a string, whose escape sequences are validated but kept in its value, as the
+    /// values of an interpolated string are parsed by their positions in the code. They are
+    /// decoded with `unescape`.
     fn read_str(&mut self) -> Token {
         let position = self.current_position;
-        let mut start = self.position;
-        let mut end = self.position;
 
         // consumes the '"'
-        end += 1;
-        start += 1;
         self.next_char();
 
+        let start = self.position;
+
         while self.get_current_char() != '"' && self.get_current_char() != '\0' {
-            end += 1;
-            self.next_char();
+            if self.get_current_char() == '\\' {
+                if let Err(token) = self.read_escape() {
+                    return token;
+                }
+            } else {
+                self.next_char();
+            }
         }
 
         if self.get_current_char() != '"' {
             return Token::new(TokenKind::BadToken, self.current_position, "");
         }
 
+        let text = self.get_text(start, self.position);
+
         // consumes the '"'
         self.next_char();
 
-        let text = &self.text[start..end];
-        Token::new(TokenKind::StringLiteral, position, text)
+        Token::new(TokenKind::StringLiteral, position, &text)
+    }
+
+    /// Decodes the escape sequences of the text of a string, which were validated when the
+    /// string was read.
+    pub fn unescape(text: &str) -> String {
+        let mut lexer = Lexer::new(text);
+        let mut value = String::new();
+
+        while lexer.position < lexer.text.len() {
+            if lexer.get_current_char() == '\\' {
+                value.extend(lexer.read_escape().ok());
+            } else {
+                value.push(lexer.next_char());
+            }
+        }
+
+        value
     }
 
     fn read_single_line_comment(&mut self) {
@@ -415,11 +542,7 @@ impl Lexer {
 
     fn read_multi_line_comment(&mut self) {
         loop {
-            let pos = self.position;
-            let current_char = self.text.chars().nth(pos).unwrap_or(' ');
-            let next_char = self.text.chars().nth(pos + 1).unwrap_or(' ');
-
-            if current_char == '*' && next_char == '/' {
+            if self.get_current_char() == '*' && self.peek_char(1) == '/' {
                 self.next_char();
                 self.next_char();
                 break;
@@ -439,27 +562,40 @@ impl Lexer {
             end += 1;
         }
 
-        let id = &self.text[start..end];
+        let id = self.get_text(start, end);
 
-        match id {
+        match &id[..] {
             "fun" => Token::new(TokenKind::FunKeyword, position, "fun"),
+            "impl" => Token::new(TokenKind::ImplKeyword, position, "impl"),
+            "trait" => Token::new(TokenKind::TraitKeyword, position, "trait"),
+            "extern" => Token::new(TokenKind::ExternKeyword, position, "extern"),
+            "export" => Token::new(TokenKind::ExportKeyword, position, "export"),
+            "alloc" => Token::new(TokenKind::AllocKeyword, position, "alloc"),
+            "defer" => Token::new(TokenKind::DeferKeyword, position, "defer"),
+            "self" => Token::new(TokenKind::SelfKeyword, position, "self"),
             "let" => Token::new(TokenKind::LetKeyword, position, "let"),
+            "mut" => Token::new(TokenKind::MutKeyword, position, "mut"),
             "return" => Token::new(TokenKind::ReturnKeyword, position, "return"),
             "while" => Token::new(TokenKind::WhileKeyword, position, "while"),
             "do" => Token::new(TokenKind::DoKeyword, position, "do"),
+            "loop" => Token::new(TokenKind::LoopKeyword, position, "loop"),
             "for" => Token::new(TokenKind::ForKeyword, position, "for"),
             "in" => Token::new(TokenKind::InKeyword, position, "in"),
+            "step" => Token::new(TokenKind::StepKeyword, position, "step"),
             "if" => Token::new(TokenKind::IfKeyword, position, "if"),
             "else" => Token::new(TokenKind::ElseKeyword, position, "else"),
             "true" => Token::new(TokenKind::BooleanLiteral, position, "true"),
             "false" => Token::new(TokenKind::BooleanLiteral, position, "false"),
+            "none" => Token::new(TokenKind::NoneLiteral, position, "none"),
             "break" => Token::new(TokenKind::BreakKeyword, position, "break"),
             "continue" => Token::new(TokenKind::ContinueKeyword, position, "continue"),
             "print" => Token::new(TokenKind::PrintKeyword, position, "print"),
             "println" => Token::new(TokenKind::PrintlnKeyword, position, "println"),
+            "eprint" => Token::new(TokenKind::EprintKeyword, position, "eprint"),
+            "eprintln" => Token::new(TokenKind::EprintlnKeyword, position, "eprintln"),
             "ref" => Token::new(TokenKind::Ref, position, "ref"),
             "deref" => Token::new(TokenKind::Deref, position, "deref"),
-            _ => Token::new(TokenKind::Identifier, position, id),
+            _ => Token::new(TokenKind::Identifier, position, &id),
         }
     }
 
@@ -539,6 +675,15 @@ mod tests {
 
         assert_eq!(lexer.next().kind, TokenKind::DotDotEquals);
         assert_eq!(lexer.next().kind, TokenKind::NumberLiteral);
+
+        let code = "0..10 step 2";
+        let mut lexer = Lexer::new(code);
+
+        assert_eq!(lexer.next().kind, TokenKind::NumberLiteral);
+        assert_eq!(lexer.next().kind, TokenKind::DotDot);
+        assert_eq!(lexer.next().kind, TokenKind::NumberLiteral);
+        lexer.next();
+        assert_eq!(lexer.next().kind, TokenKind::StepKeyword);
     }
 
     #[test]
@@ -788,7 +933,7 @@ mod tests {
 
     #[test]
     fn test_identifier_token() {
-        let code = "while true for variable";
+        let code = "while true none for extern export alloc defer variable";
         let mut token: Token;
         let mut lexer = Lexer::new(code);
 
@@ -804,12 +949,42 @@ mod tests {
         // white space
         lexer.next();
 
+        token = lexer.next();
+        assert_eq!(token.kind, TokenKind::NoneLiteral);
+
+        // white space
+        lexer.next();
+
         token = lexer.next();
         assert_eq!(token.kind, TokenKind::ForKeyword);
 
         // white space
         lexer.next();
 
+        token = lexer.next();
+        assert_eq!(token.kind, TokenKind::ExternKeyword);
+
+        // white space
+        lexer.next();
+
+        token = lexer.next();
+        assert_eq!(token.kind, TokenKind::ExportKeyword);
+
+        // white space
+        lexer.next();
+
+        token = lexer.next();
+        assert_eq!(token.kind, TokenKind::AllocKeyword);
+
+        // white space
+        lexer.next();
+
+        token = lexer.next();
+        assert_eq!(token.kind, TokenKind::DeferKeyword);
+
+        // white space
+        lexer.next();
+
         token = lexer.next();
         assert_eq!(token.kind, TokenKind::Identifier);
     }
@@ -867,6 +1042,21 @@ mod tests {
         assert_eq!(token.kind, TokenKind::BadToken);
     }
 
+    #[test]
+    fn test_label_token() {
+        let code = "'outer: 'a' 'b;";
+        let mut lexer = Lexer::new(code);
+
+        let tokens = lexer.lex().unwrap();
+
+        assert_eq!(tokens[0].kind, TokenKind::Label);
+        assert_eq!(tokens[0].value, "outer");
+        assert_eq!(tokens[1].kind, TokenKind::Colon);
+        assert_eq!(tokens[2].kind, TokenKind::CharLiteral);
+        assert_eq!(tokens[3].kind, TokenKind::Label);
+        assert_eq!(tokens[3].value, "b");
+    }
+
     #[test]
     fn test_string_token() {
         let mut code = "\"test string\"";
@@ -890,4 +1080,43 @@ mod tests {
         token = lexer.next();
         assert_eq!(token.kind, TokenKind::BadToken);
     }
+
+    #[test]
+    fn test_escape_sequences() {
+        let mut code = "'\\u{1F600}'";
+        let mut token: Token;
+        let mut lexer = Lexer::new(code);
+
+        token = lexer.next();
+        assert_eq!(token.kind, TokenKind::CharLiteral);
+        assert_eq!(token.value, "😀");
+
+        code = "'é'";
+        lexer = Lexer::new(code);
+
+        token = lexer.next();
+        assert_eq!(token.kind, TokenKind::CharLiteral);
+        assert_eq!(token.value, "é");
+
+        code = "\"a\\\"b\\tc\"";
+        lexer = Lexer::new(code);
+
+        token = lexer.next();
+        assert_eq!(token.kind, TokenKind::StringLiteral);
+        assert_eq!(Lexer::unescape(&token.value), "a\"b\tc");
+
+        code = "\"olá \\q\"";
+        lexer = Lexer::new(code);
+
+        token = lexer.next();
+        assert_eq!(token.kind, TokenKind::InvalidEscape);
+        assert_eq!(token.value, "\\q");
+        assert_eq!(token.position.column, 6);
+
+        code = "'\\u{D800}'";
+        lexer = Lexer::new(code);
+
+        token = lexer.next();
+        assert_eq!(token.kind, TokenKind::InvalidEscape);
+    }
 }
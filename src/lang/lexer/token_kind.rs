@@ -3,10 +3,12 @@ use std::fmt::Display;
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
 pub enum TokenKind {
     Identifier,
+    Label,
     NumberLiteral,
     BooleanLiteral,
     CharLiteral,
     StringLiteral,
+    NoneLiteral,
     LeftBrace,
     RightBrace,
     LeftParenthesis,
@@ -27,19 +29,31 @@ pub enum TokenKind {
     EndOfFile,
     Semicolon,
     Colon,
+    Question,
     Comma,
     LetKeyword,
+    MutKeyword,
     FunKeyword,
+    ImplKeyword,
+    TraitKeyword,
+    ExternKeyword,
+    ExportKeyword,
+    AllocKeyword,
+    DeferKeyword,
+    SelfKeyword,
     IfKeyword,
     ElseKeyword,
     WhileKeyword,
     DoKeyword,
+    LoopKeyword,
     ForKeyword,
     InKeyword,
+    StepKeyword,
     ReturnKeyword,
     BreakKeyword,
     ContinueKeyword,
     BadToken,
+    InvalidEscape,
     Equals,
     EqualsEquals,
     Pipe,
@@ -65,6 +79,8 @@ pub enum TokenKind {
     DotDotEquals,
     PrintKeyword,
     PrintlnKeyword,
+    EprintKeyword,
+    EprintlnKeyword,
     Ref,
     Deref,
     ArrowRight,
@@ -1,8 +1,14 @@
+use super::builtin_function::BuiltinFunction;
+use super::flow::usage_analyzer::UsageAnalyzer;
 use super::scope::Scope;
 use super::semantic_error::SemanticError;
 use super::semantic_type::SemanticType;
+use super::semantic_warning::SemanticWarning;
 use super::symbol::Symbol;
+use super::top_level_statements::extern_analyzer::ExternAnalyzer;
 use super::top_level_statements::function_analyzer::FunctionAnalyzer;
+use super::top_level_statements::impl_analyzer::ImplAnalyzer;
+use super::top_level_statements::trait_analyzer::TraitAnalyzer;
 
 use crate::lang::syntax::compilation_unit::CompilationUnit;
 use crate::lang::syntax::top_level_statements::top_level_statement::TopLevelStatement;
@@ -15,6 +21,7 @@ pub type Scopes = HashMap<Uuid, Rc<RefCell<Scope>>>;
 pub struct Analyzer {
     pub scopes: Scopes,
     pub diagnosis: Vec<SemanticError>,
+    pub warnings: Vec<SemanticWarning>,
 }
 
 impl Analyzer {
@@ -39,6 +46,9 @@ impl Analyzer {
             SemanticType::Bool,
             SemanticType::Char,
             SemanticType::String,
+            SemanticType::Range,
+            SemanticType::File,
+            SemanticType::CString,
         ];
 
         for default_type in default_types {
@@ -47,6 +57,25 @@ impl Analyzer {
             })
         }
 
+        for function in BuiltinFunction::all() {
+            global_scope.borrow_mut().insert(Symbol::Function {
+                name: String::from(function.name),
+                symbol_type: function.get_type(),
+                builtin: true,
+                external: false,
+            })
+        }
+
+        // The traits are known before the functions bounded by them and the implementations.
+        for statement in &ast.statements {
+            if let TopLevelStatement::Trait(r#trait) = statement {
+                let analyzer =
+                    TraitAnalyzer::analyze_declaration(r#trait, Rc::clone(&global_scope));
+
+                diagnosis.extend(analyzer.diagnosis);
+            }
+        }
+
         for statement in &ast.statements {
             match statement {
                 TopLevelStatement::Function(function) => {
@@ -55,6 +84,19 @@ impl Analyzer {
 
                     diagnosis.extend(analyzer.diagnosis);
                 }
+                TopLevelStatement::Impl(r#impl) => {
+                    let analyzer =
+                        ImplAnalyzer::analyze_declaration(r#impl, Rc::clone(&global_scope));
+
+                    diagnosis.extend(analyzer.diagnosis);
+                }
+                TopLevelStatement::Extern(r#extern) => {
+                    let analyzer =
+                        ExternAnalyzer::analyze_declaration(r#extern, Rc::clone(&global_scope));
+
+                    diagnosis.extend(analyzer.diagnosis);
+                }
+                TopLevelStatement::Trait(_) => {}
             }
         }
 
@@ -66,9 +108,23 @@ impl Analyzer {
 
                     diagnosis.extend(analyzer.diagnosis);
                 }
+                TopLevelStatement::Impl(r#impl) => {
+                    let analyzer =
+                        ImplAnalyzer::analyze(r#impl, Rc::clone(&global_scope), &mut scopes);
+
+                    diagnosis.extend(analyzer.diagnosis);
+                }
+                TopLevelStatement::Trait(_) | TopLevelStatement::Extern(_) => {}
             }
         }
 
-        Self { scopes, diagnosis }
+        let analyzer = UsageAnalyzer::analyze(Rc::clone(&global_scope), &scopes);
+        let warnings = analyzer.warnings;
+
+        Self {
+            scopes,
+            diagnosis,
+            warnings,
+        }
     }
 }
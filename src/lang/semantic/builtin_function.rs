@@ -0,0 +1,269 @@
+use super::semantic_type::SemanticType;
+
+/// A variant of a builtin function for some types of arguments, which calls the C function
+/// with the given name.
+#[derive(Clone, Debug)]
+pub struct Overload {
+    pub params: Vec<SemanticType>,
+    pub return_type: SemanticType,
+    pub c_name: &'static str,
+}
+
+impl Overload {
+    fn new(params: Vec<SemanticType>, return_type: SemanticType, c_name: &'static str) -> Self {
+        Self {
+            params,
+            return_type,
+            c_name,
+        }
+    }
+
+    pub fn get_type(&self) -> SemanticType {
+        SemanticType::Function(self.params.clone(), Box::new(self.return_type.clone()))
+    }
+}
+
+/// A function provided by the compiler and implemented in the generated code. It is declared
+/// in the global scope with the type of its first overload, which is the one used when it is
+/// not called, and its calls use the overload resolved by the types of their arguments.
+#[derive(Clone, Debug)]
+pub struct BuiltinFunction {
+    pub name: &'static str,
+    pub overloads: Vec<Overload>,
+}
+
+impl BuiltinFunction {
+    fn new(name: &'static str, overloads: Vec<Overload>) -> Self {
+        Self { name, overloads }
+    }
+
+    /// Gets every builtin function.
+    pub fn all() -> Vec<Self> {
+        use SemanticType::{File, String, Void, F32, F64, I32, I64, U64};
+
+        let optional = |r#type: SemanticType| SemanticType::Optional(Box::new(r#type));
+
+        // The file functions report their errors with the message of `strerror`.
+        let result =
+            |r#type: SemanticType| SemanticType::Result(Box::new(r#type), Box::new(String));
+
+        // Functions of a float, such as `sqrt` and `sqrtf`.
+        let float_function = |name: &'static str, f32_name: &'static str| {
+            Self::new(
+                name,
+                vec![
+                    Overload::new(vec![F64], F64, name),
+                    Overload::new(vec![F32], F32, f32_name),
+                ],
+            )
+        };
+
+        // `min` and `max`, whose integer overloads are generated.
+        let comparison = |name: &'static str, names: [&'static str; 5]| {
+            Self::new(
+                name,
+                vec![
+                    Overload::new(vec![F64, F64], F64, names[0]),
+                    Overload::new(vec![F32, F32], F32, names[1]),
+                    Overload::new(vec![I32, I32], I32, names[2]),
+                    Overload::new(vec![I64, I64], I64, names[3]),
+                    Overload::new(vec![U64, U64], U64, names[4]),
+                ],
+            )
+        };
+
+        vec![
+            Self::new(
+                "readLine",
+                vec![Overload::new(vec![], optional(String), "readLine")],
+            ),
+            Self::new(
+                "readInt",
+                vec![Overload::new(vec![], optional(I64), "readInt")],
+            ),
+            Self::new(
+                "readFloat",
+                vec![Overload::new(vec![], optional(F64), "readFloat")],
+            ),
+            Self::new(
+                "openFile",
+                vec![Overload::new(
+                    vec![String, String],
+                    result(File),
+                    "__file_open",
+                )],
+            ),
+            Self::new(
+                "readAll",
+                vec![Overload::new(vec![File], result(String), "__file_read_all")],
+            ),
+            Self::new(
+                "writeAll",
+                vec![Overload::new(
+                    vec![File, String],
+                    result(Void),
+                    "__file_write_all",
+                )],
+            ),
+            Self::new(
+                "appendLine",
+                vec![Overload::new(
+                    vec![File, String],
+                    result(Void),
+                    "__file_append_line",
+                )],
+            ),
+            Self::new(
+                "close",
+                vec![Overload::new(vec![File], result(Void), "__file_close")],
+            ),
+            float_function("sqrt", "sqrtf"),
+            float_function("floor", "floorf"),
+            float_function("ceil", "ceilf"),
+            float_function("sin", "sinf"),
+            float_function("cos", "cosf"),
+            float_function("log", "logf"),
+            Self::new(
+                "pow",
+                vec![
+                    Overload::new(vec![F64, F64], F64, "pow"),
+                    Overload::new(vec![F32, F32], F32, "powf"),
+                ],
+            ),
+            Self::new(
+                "abs",
+                vec![
+                    Overload::new(vec![I32], I32, "abs"),
+                    Overload::new(vec![I64], I64, "llabs"),
+                    Overload::new(vec![F64], F64, "fabs"),
+                    Overload::new(vec![F32], F32, "fabsf"),
+                ],
+            ),
+            comparison(
+                "min",
+                ["fmin", "fminf", "__min_i32", "__min_i64", "__min_u64"],
+            ),
+            comparison(
+                "max",
+                ["fmax", "fmaxf", "__max_i32", "__max_i64", "__max_u64"],
+            ),
+        ]
+    }
+
+    /// Gets the builtin function with the given name, if any.
+    pub fn find(name: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|function| function.name == name)
+    }
+
+    /// Gets the type the function is declared with.
+    pub fn get_type(&self) -> SemanticType {
+        self.overloads[0].get_type()
+    }
+
+    /// Resolves the overload called with arguments of the given types. An overload taking
+    /// exactly the types is preferred. Otherwise, the first one that holds every argument
+    /// without cutting or changing its sign is used, taking integers if the arguments are
+    /// integers and the function has integer overloads. There is none when no overload holds
+    /// the arguments.
+    pub fn resolve(&self, types: &[SemanticType]) -> Option<&Overload> {
+        let float = types.iter().any(is_float);
+        let integer = !float
+            && self
+                .overloads
+                .iter()
+                .any(|overload| overload.params.iter().all(SemanticType::is_integer));
+
+        let fits = |overload: &&Overload| {
+            overload.params.len() == types.len()
+                && overload
+                    .params
+                    .iter()
+                    .zip(types)
+                    .all(|(param, r#type)| holds(param, r#type))
+                && (!integer || overload.params.iter().all(SemanticType::is_integer))
+        };
+
+        self.overloads
+            .iter()
+            .find(|overload| overload.params == types)
+            .or_else(|| self.overloads.iter().find(fits))
+    }
+}
+
+/// Whether a parameter of the given type holds any value of an argument of the other one. An
+/// unsigned integer is only held by a wider signed one, and a signed one by no unsigned one.
+fn holds(param: &SemanticType, r#type: &SemanticType) -> bool {
+    match (param, r#type) {
+        (_, SemanticType::Any) => true,
+        (param, r#type) if param.is_integer() && r#type.is_integer() => {
+            match (is_signed(param), is_signed(r#type)) {
+                (true, false) => get_width(param) > get_width(r#type),
+                (false, true) => false,
+                _ => get_width(param) >= get_width(r#type),
+            }
+        }
+        (param, r#type) if is_float(param) && r#type.is_number() => {
+            get_width(param) >= get_width(r#type)
+        }
+        (param, r#type) if param.is_integer() && is_float(r#type) => false,
+        _ => param.accepts(r#type),
+    }
+}
+
+fn is_float(r#type: &SemanticType) -> bool {
+    matches!(r#type, SemanticType::F32 | SemanticType::F64)
+}
+
+fn is_signed(r#type: &SemanticType) -> bool {
+    matches!(
+        r#type,
+        SemanticType::I8 | SemanticType::I16 | SemanticType::I32 | SemanticType::I64
+    )
+}
+
+/// The width in bytes of a number type.
+fn get_width(r#type: &SemanticType) -> usize {
+    match r#type {
+        SemanticType::U8 | SemanticType::I8 => 1,
+        SemanticType::U16 | SemanticType::I16 => 2,
+        SemanticType::U32 | SemanticType::I32 | SemanticType::F32 => 4,
+        SemanticType::U64 | SemanticType::I64 | SemanticType::F64 => 8,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BuiltinFunction;
+    use crate::lang::semantic::semantic_type::SemanticType::{self, *};
+
+    fn resolve(name: &str, types: &[SemanticType]) -> Option<&'static str> {
+        let function = BuiltinFunction::find(name).unwrap();
+        function.resolve(types).map(|overload| overload.c_name)
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(resolve("max", &[I32, I32]), Some("__max_i32"));
+        assert_eq!(resolve("max", &[F32, F32]), Some("fmaxf"));
+        assert_eq!(resolve("max", &[F32, I32]), Some("fmax"));
+
+        // Integers keep their sign, and an unsigned one needs a wider signed overload.
+        assert_eq!(resolve("max", &[U32, U32]), Some("__max_i64"));
+        assert_eq!(resolve("max", &[U16, U64]), Some("__max_u64"));
+        assert_eq!(resolve("max", &[U8, I8]), Some("__max_i32"));
+        assert_eq!(resolve("max", &[U32, I32]), Some("__max_i64"));
+        assert_eq!(resolve("abs", &[U32]), Some("llabs"));
+
+        // Integers are only taken as floats by functions of floats.
+        assert_eq!(resolve("sqrt", &[I32]), Some("sqrt"));
+
+        // No overload holds every value.
+        assert_eq!(resolve("max", &[U64, I32]), None);
+        assert_eq!(resolve("abs", &[U64]), None);
+        assert_eq!(resolve("sqrt", &[String]), None);
+        assert_eq!(resolve("max", &[I32]), None);
+    }
+}
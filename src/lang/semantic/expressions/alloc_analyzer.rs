@@ -0,0 +1,80 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::lang::position::Positioned;
+use crate::lang::semantic::scope::Scope;
+use crate::lang::semantic::semantic_error::SemanticError;
+use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::shared::type_analyzer::TypeAnalyzer;
+use crate::lang::syntax::expressions::alloc::Alloc;
+
+use super::expression_analyzer::ExpressionAnalyzer;
+
+/// Analyzer responsible for the allocation of values on the heap, which are reached through
+/// the reference it gives.
+pub struct AllocAnalyzer {
+    pub return_type: SemanticType,
+    pub diagnosis: Vec<SemanticError>,
+}
+
+impl AllocAnalyzer {
+    /// Analyzes the provided allocation within a given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `alloc` - A reference to the allocation to be analyzed.
+    /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
+    ///
+    /// # Returns
+    ///
+    /// An `AllocAnalyzer` instance containing the analysis results.
+    pub fn analyze(alloc: &Alloc, scope: Rc<RefCell<Scope>>) -> Self {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        let analyzer = TypeAnalyzer::analyze(&alloc.r#type, Rc::clone(&scope));
+        diagnosis.extend(analyzer.diagnosis);
+
+        let element_type = analyzer.result_type;
+
+        // The values are released as a whole by `free`, which would leave the memory owned
+        // by each of them behind.
+        if element_type.is_owned() {
+            diagnosis.push(SemanticError::OwnedAllocation {
+                found: element_type.clone(),
+                position: alloc.r#type.get_position(),
+            });
+        }
+
+        let analyzer = ExpressionAnalyzer::analyze(&alloc.count, Rc::clone(&scope));
+        diagnosis.extend(analyzer.diagnosis);
+
+        if !analyzer.return_type.is_integer() {
+            diagnosis.push(SemanticError::ExpectedType {
+                expected: SemanticType::I32,
+                found: analyzer.return_type,
+                position: alloc.count.get_position(),
+            });
+        }
+
+        Self {
+            return_type: SemanticType::Ref(Box::new(element_type)),
+            diagnosis,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    #[test]
+    fn test_owned_allocations() {
+        let code = "fun main() { let grid = alloc<[i32; 100]>(100); free(grid); }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let vs = alloc<vec<i32>>(10); free(vs); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::OwnedAllocation { .. }]
+        ));
+    }
+}
@@ -1,5 +1,7 @@
+use crate::lang::lexer::token_kind::TokenKind;
 use crate::lang::position::Positioned;
 use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::symbol::Symbol;
 use crate::lang::semantic::{scope::Scope, semantic_error::SemanticError};
 use crate::lang::syntax::expressions::array::Array;
 
@@ -65,8 +67,29 @@ impl ArrayAnalyzer {
                 }
             }
 
-            return_type =
-                SemanticType::Array(Box::new(first_element_type), array.expressions.len());
+            // The repeat form `[a; 10]` has the size written explicitly, which the parser only
+            // accepts when it is a valid size or the name of a size parameter, as in `[a; N]`.
+            return_type = match &array.size {
+                Some(size) if size.kind == TokenKind::NumberLiteral => SemanticType::Array(
+                    Box::new(first_element_type),
+                    size.value.parse::<usize>().unwrap(),
+                ),
+                Some(size) => match scope.borrow().get(&size.value) {
+                    Some(Symbol::SizeParameter {
+                        size: Some(number), ..
+                    }) => SemanticType::Array(Box::new(first_element_type), number),
+                    Some(Symbol::SizeParameter { name, size: None }) => {
+                        SemanticType::GenericArray(Box::new(first_element_type), name)
+                    }
+                    _ => {
+                        diagnosis.push(SemanticError::IdentifierNotFound {
+                            position: size.position,
+                        });
+                        SemanticType::Any
+                    }
+                },
+                None => SemanticType::Array(Box::new(first_element_type), array.expressions.len()),
+            };
         } else {
             // If the array is empty then its type is array of any.
             return_type = SemanticType::Array(Box::new(SemanticType::Any), 0);
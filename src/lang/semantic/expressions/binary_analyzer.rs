@@ -32,9 +32,28 @@ impl BinaryAnalyzer {
 
         match &binary.operator.token.kind {
             TokenKind::EqualsEquals | TokenKind::ExclamationEquals => {
+                let none = SemanticType::Optional(Box::new(SemanticType::Any));
+                let is_optional = |r#type: &SemanticType| {
+                    matches!(r#type, SemanticType::Optional(_)) && *r#type != none
+                };
+
                 if left_return_type.is_number() && right_return_type.is_number() {
                     return_type = SemanticType::Bool;
-                } else if left_return_type == right_return_type {
+                } else if (left_return_type == none && is_optional(&right_return_type))
+                    || (right_return_type == none && is_optional(&left_return_type))
+                {
+                    // Optionals are only compared with `none`, to check whether they hold a value.
+                    return_type = SemanticType::Bool;
+                } else if left_return_type == right_return_type
+                    && !matches!(
+                        left_return_type,
+                        SemanticType::Tuple(_)
+                            | SemanticType::Optional(_)
+                            | SemanticType::Result(..)
+                    )
+                {
+                    // Tuples, optionals and results are structures in C, which cannot be
+                    // compared.
                     return_type = SemanticType::Bool;
                 } else {
                     diagnosis.push(SemanticError::EqualityTypeMismatch {
@@ -90,7 +109,26 @@ impl BinaryAnalyzer {
                     })
                 }
             }
+            TokenKind::InKeyword => {
+                if left_return_type.is_integer()
+                    && matches!(right_return_type, SemanticType::Range | SemanticType::Any)
+                {
+                    return_type = SemanticType::Bool;
+                } else {
+                    diagnosis.push(SemanticError::InvalidOperator {
+                        position: binary.operator.get_position(),
+                        left: left_return_type,
+                        right: right_return_type,
+                    })
+                }
+            }
             TokenKind::AmpersandAmpersand | TokenKind::PipePipe => {
+                // The right operand is not always evaluated, while a `?` or a `loop` runs before
+                // the statement.
+                if let Some(position) = binary.right.find_hoisted() {
+                    diagnosis.push(SemanticError::HoistedNotAllowed { position });
+                }
+
                 if left_return_type == SemanticType::Bool && right_return_type == SemanticType::Bool
                 {
                     return_type = SemanticType::Bool;
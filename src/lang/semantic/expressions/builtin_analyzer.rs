@@ -0,0 +1,423 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::lang::position::{Position, Positioned};
+use crate::lang::semantic::scope::Scope;
+use crate::lang::semantic::semantic_error::SemanticError;
+use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::syntax::expressions::expression::{Expression, ExpressionMeta};
+use crate::lang::syntax::shared::identifier::Identifier;
+
+use super::expression_analyzer::ExpressionAnalyzer;
+use super::expression_meta_analyzer::ExpressionMetaAnalyzer;
+
+/// Analyzer responsible for the functions provided by the compiler itself, whose
+/// signatures cannot be declared in the language.
+pub struct BuiltinAnalyzer {
+    pub changeable: bool,
+    pub return_type: SemanticType,
+    pub diagnosis: Vec<SemanticError>,
+}
+
+impl BuiltinAnalyzer {
+    /// Whether the given name refers to a builtin function.
+    pub fn is_builtin(name: &str) -> bool {
+        matches!(
+            name,
+            "len" | "push" | "pop" | "ok" | "err" | "error" | "panic" | "free"
+        )
+    }
+
+    /// Analyzes the usage of a builtin function, which must always be called.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - A reference to the identifier naming the builtin function.
+    /// * `meta` - The meta that follows the identifier.
+    /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
+    ///
+    /// # Returns
+    ///
+    /// A `BuiltinAnalyzer` instance containing the analysis results.
+    pub fn analyze(
+        identifier: &Identifier,
+        meta: &Option<ExpressionMeta>,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Self {
+        if let Some(ExpressionMeta::Call(expressions, meta, position)) = meta {
+            let mut analyzer =
+                Self::analyze_call(identifier, expressions, *position, Rc::clone(&scope));
+
+            if let Some(meta) = meta.as_ref() {
+                let meta_analyzer =
+                    ExpressionMetaAnalyzer::analyze(&analyzer.return_type, meta, false, scope);
+
+                analyzer.diagnosis.extend(meta_analyzer.diagnosis);
+                analyzer.changeable = meta_analyzer.changeable;
+                analyzer.return_type = meta_analyzer.return_type;
+            }
+
+            analyzer
+        } else {
+            Self {
+                changeable: false,
+                return_type: SemanticType::Any,
+                diagnosis: vec![SemanticError::BuiltinNotCalled {
+                    name: identifier.name.clone(),
+                    position: identifier.get_position(),
+                }],
+            }
+        }
+    }
+
+    /// Analyzes the call of a builtin function, without the meta that may follow it.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - A reference to the identifier naming the builtin function.
+    /// * `expressions` - The arguments of the call.
+    /// * `position` - The position of the call.
+    /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
+    ///
+    /// # Returns
+    ///
+    /// A `BuiltinAnalyzer` instance containing the analysis results.
+    pub fn analyze_call(
+        identifier: &Identifier,
+        expressions: &Vec<Expression>,
+        position: Position,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Self {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+        let mut arguments: Vec<ExpressionAnalyzer> = vec![];
+
+        for expression in expressions {
+            let mut analyzer = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope));
+            diagnosis.append(&mut analyzer.diagnosis);
+            arguments.push(analyzer);
+        }
+
+        let types: Vec<SemanticType> = arguments
+            .iter()
+            .map(|argument| argument.return_type.clone())
+            .collect();
+
+        let return_type = match &identifier.name[..] {
+            "len" => Self::analyze_len(expressions, &types, position, &mut diagnosis),
+            "free" => Self::analyze_free(expressions, &types, position, &mut diagnosis),
+            "push" | "pop" => {
+                let expected = if identifier.name == "push" { 2 } else { 1 };
+
+                if arguments.len() != expected {
+                    diagnosis.push(SemanticError::InvalidNumberOfParameters {
+                        expected,
+                        found: arguments.len(),
+                        position,
+                    });
+
+                    SemanticType::Any
+                } else {
+                    Self::analyze_vec_change(identifier, expressions, &arguments, &mut diagnosis)
+                }
+            }
+            "ok" | "err" | "error" | "panic" => Self::analyze_error_handling(
+                identifier,
+                expressions,
+                &types,
+                position,
+                &mut diagnosis,
+            ),
+            _ => unreachable!(),
+        };
+
+        Self {
+            changeable: false,
+            return_type,
+            diagnosis,
+        }
+    }
+
+    // push(vec, value) and pop(vec) -> value
+    fn analyze_vec_change(
+        identifier: &Identifier,
+        expressions: &[Expression],
+        arguments: &[ExpressionAnalyzer],
+        diagnosis: &mut Vec<SemanticError>,
+    ) -> SemanticType {
+        let element_type = match &arguments[0].return_type {
+            SemanticType::Vec(element_type) => element_type.as_ref().clone(),
+            SemanticType::Any => SemanticType::Any,
+            found => {
+                diagnosis.push(SemanticError::InvalidParameterType {
+                    expected: SemanticType::Vec(Box::new(SemanticType::Any)),
+                    found: found.clone(),
+                    position: expressions[0].get_position(),
+                });
+
+                SemanticType::Any
+            }
+        };
+
+        // Both change the vector, so it must be bound mutably.
+        if !arguments[0].changeable {
+            diagnosis.push(SemanticError::ValueCannotBeReassigned {
+                position: expressions[0].get_position(),
+            });
+        }
+
+        if identifier.name == "push" {
+            if !element_type.accepts(&arguments[1].return_type) {
+                diagnosis.push(SemanticError::InvalidParameterType {
+                    expected: element_type,
+                    found: arguments[1].return_type.clone(),
+                    position: expressions[1].get_position(),
+                });
+            }
+
+            SemanticType::Void
+        } else {
+            element_type
+        }
+    }
+
+    // ok() -> result<void, any>, ok(value) -> result<T, any>, err(error) -> result<any, E>,
+    // error(result) -> ?E and panic(message)
+    fn analyze_error_handling(
+        identifier: &Identifier,
+        expressions: &[Expression],
+        types: &[SemanticType],
+        position: Position,
+        diagnosis: &mut Vec<SemanticError>,
+    ) -> SemanticType {
+        // The value of a result may be left out when there is none.
+        if identifier.name == "ok" && types.is_empty() {
+            return SemanticType::Result(Box::new(SemanticType::Void), Box::new(SemanticType::Any));
+        }
+
+        if types.len() != 1 {
+            diagnosis.push(SemanticError::InvalidNumberOfParameters {
+                expected: 1,
+                found: types.len(),
+                position,
+            });
+
+            return SemanticType::Any;
+        }
+
+        let found = types[0].clone();
+
+        match &identifier.name[..] {
+            "ok" => SemanticType::Result(Box::new(found), Box::new(SemanticType::Any)),
+            "err" => SemanticType::Result(Box::new(SemanticType::Any), Box::new(found)),
+            "error" => match found {
+                SemanticType::Result(_, error) => SemanticType::Optional(error),
+                SemanticType::Any => SemanticType::Any,
+                found => {
+                    diagnosis.push(SemanticError::InvalidParameterType {
+                        expected: SemanticType::Result(
+                            Box::new(SemanticType::Any),
+                            Box::new(SemanticType::Any),
+                        ),
+                        found,
+                        position: expressions[0].get_position(),
+                    });
+
+                    SemanticType::Any
+                }
+            },
+            _ => {
+                if !SemanticType::String.accepts(&found) {
+                    diagnosis.push(SemanticError::InvalidParameterType {
+                        expected: SemanticType::String,
+                        found,
+                        position: expressions[0].get_position(),
+                    });
+                }
+
+                SemanticType::Void
+            }
+        }
+    }
+
+    // free(reference)
+    fn analyze_free(
+        expressions: &[Expression],
+        types: &[SemanticType],
+        position: Position,
+        diagnosis: &mut Vec<SemanticError>,
+    ) -> SemanticType {
+        if types.len() != 1 {
+            diagnosis.push(SemanticError::InvalidNumberOfParameters {
+                expected: 1,
+                found: types.len(),
+                position,
+            });
+        } else if !matches!(types[0], SemanticType::Ref(_) | SemanticType::Any) {
+            diagnosis.push(SemanticError::InvalidParameterType {
+                expected: SemanticType::Ref(Box::new(SemanticType::Any)),
+                found: types[0].clone(),
+                position: expressions[0].get_position(),
+            });
+        }
+
+        SemanticType::Void
+    }
+
+    // len(value) -> i32
+    fn analyze_len(
+        expressions: &[Expression],
+        types: &[SemanticType],
+        position: Position,
+        diagnosis: &mut Vec<SemanticError>,
+    ) -> SemanticType {
+        if types.len() != 1 {
+            diagnosis.push(SemanticError::InvalidNumberOfParameters {
+                expected: 1,
+                found: types.len(),
+                position,
+            });
+        } else if !matches!(
+            types[0],
+            SemanticType::Array(..)
+                | SemanticType::GenericArray(..)
+                | SemanticType::Slice(_)
+                | SemanticType::Vec(_)
+                | SemanticType::String
+                | SemanticType::CString
+                | SemanticType::Any
+        ) {
+            diagnosis.push(SemanticError::InvalidParameterType {
+                expected: SemanticType::Slice(Box::new(SemanticType::Any)),
+                found: types[0].clone(),
+                position: expressions[0].get_position(),
+            });
+        }
+
+        SemanticType::I32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    #[test]
+    fn test_slices() {
+        let function = "fun sum(values: [i32]) -> i32 { let mut s = 0; for v in values { s += v; } return s; }";
+
+        // Arrays of any size are accepted where a slice is expected.
+        let code = format!(
+            "{} fun main() {{ println sum([1, 2]); println sum([1, 2, 3]); println sum([]); }}",
+            function
+        );
+        assert!(analyze(&code).is_empty());
+
+        let code = format!("{} fun main() {{ println sum([1.5, 2.5]); }}", function);
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::InvalidParameterType { .. }]
+        ));
+
+        let code = "fun first(rows: [[i32; 2]]) -> i32 { return rows[0][1]; } fun main() { println first([[1, 2]]); }";
+        assert!(analyze(code).is_empty());
+    }
+
+    #[test]
+    fn test_len() {
+        let code = "fun main() { let a = [1, 2]; let s: [i32] = a; println len(a) + len(s) + len(\"abc\"); }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { println len(5); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidParameterType { .. }]
+        ));
+
+        let code = "fun main() { println len([1], [2]); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidNumberOfParameters {
+                expected: 1,
+                found: 2,
+                ..
+            }]
+        ));
+
+        let code = "fun main() { let f = len; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::BuiltinNotCalled { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_vecs() {
+        let code = "fun main() { let mut v: vec<i32> = [1]; push(v, 2); let x: i32 = pop(v); println x + len(v); }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let mut v: vec<i32> = []; push(v); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidNumberOfParameters {
+                expected: 2,
+                found: 1,
+                ..
+            }]
+        ));
+
+        let code = "fun main() { let mut v: vec<i32> = []; let x: string = pop(v); println x; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ExpectedType { .. }]
+        ));
+
+        // Arrays cannot grow.
+        let code = "fun main() { let mut a = [1, 2]; push(a, 3); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidParameterType { .. }]
+        ));
+
+        let code = "fun main() { let v: vec<i32> = []; push(v, 3); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ValueCannotBeReassigned { .. }]
+        ));
+    }
+    #[test]
+    fn test_files() {
+        let code = "fun copy() -> result<void, string> { let h = openFile(\"a.txt\", \"r\")?; let text: string = readAll(h)?; let g = h; close(g)?; return close(h); } fun main() { let r = copy(); }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let r = close(5); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::OverloadNotFound { .. }]
+        ));
+
+        // A file is shared by its copies, so it is not passed to C as a `FILE*`.
+        let code = "extern fun fflush(f: file) -> i32; fun main() { }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidForeignType { .. }]
+        ));
+    }
+    #[test]
+    fn test_reads() {
+        let code = "fun main() { if let line = readLine() { let s: string = line; } if let n = readInt() { let m: i64 = n; } if let x = readFloat() { let y: f64 = x; } }";
+        assert!(analyze(code).is_empty());
+
+        // The values are optional, as the input may end or not be a number.
+        let code = "fun main() { let n: i64 = readInt(); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ExpectedType { .. }]
+        ));
+
+        let code = "fun main() { let line = readLine(1); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::OverloadNotFound { .. }]
+        ));
+    }
+}
@@ -1,15 +1,24 @@
 use std::{cell::RefCell, rc::Rc};
 
 use crate::lang::{
-    semantic::{scope::Scope, semantic_error::SemanticError, semantic_type::SemanticType},
+    position::Positioned,
+    semantic::{
+        scope::Scope,
+        semantic_error::SemanticError,
+        semantic_type::SemanticType,
+        statements::{
+            block_analyzer::BlockAnalyzer, if_analyzer::IfAnalyzer, loop_analyzer::LoopAnalyzer,
+        },
+    },
     syntax::expressions::{expression::Expression, literal::Literal},
 };
 
 use super::{
-    array_analyzer::ArrayAnalyzer, binary_analyzer::BinaryAnalyzer,
+    alloc_analyzer::AllocAnalyzer, array_analyzer::ArrayAnalyzer, binary_analyzer::BinaryAnalyzer,
     expression_meta_analyzer::ExpressionMetaAnalyzer, identifier_analyzer::IdentifierAnalyzer,
+    interpolation_analyzer::InterpolationAnalyzer, lambda_analyzer::LambdaAnalyzer,
     parenthesized_analyzer::ParenthesizedAnalyzer, range_analyzer::RangeAnalyzer,
-    unary_analyzer::UnaryAnalyzer,
+    tuple_analyzer::TupleAnalyzer, unary_analyzer::UnaryAnalyzer,
 };
 
 pub struct ExpressionAnalyzer {
@@ -35,6 +44,7 @@ impl ExpressionAnalyzer {
                     let analyzer = ExpressionMetaAnalyzer::analyze(
                         &analyzer.return_type,
                         &meta,
+                        false,
                         Rc::clone(&scope),
                     );
                     diagnosis.extend(analyzer.diagnosis);
@@ -66,6 +76,9 @@ impl ExpressionAnalyzer {
                     Literal::String(_) => return_type = SemanticType::String,
                     Literal::Char(_) => return_type = SemanticType::Char,
                     Literal::Boolean(_) => return_type = SemanticType::Bool,
+                    Literal::None(_) => {
+                        return_type = SemanticType::Optional(Box::new(SemanticType::Any))
+                    }
                     Literal::Number(token) => {
                         return_type = if token.value.contains(".") {
                             SemanticType::F32
@@ -96,6 +109,77 @@ impl ExpressionAnalyzer {
                 changeable = false;
                 return_type = analyzer.return_type;
             }
+            Expression::Tuple(tuple, meta) => {
+                let analyzer = TupleAnalyzer::analyze(tuple, Rc::clone(&scope));
+                diagnosis.extend(analyzer.diagnosis);
+
+                changeable = false;
+
+                if let Some(meta) = meta {
+                    let analyzer = ExpressionMetaAnalyzer::analyze(
+                        &analyzer.return_type,
+                        meta,
+                        false,
+                        Rc::clone(&scope),
+                    );
+                    diagnosis.extend(analyzer.diagnosis);
+
+                    return_type = analyzer.return_type;
+                } else {
+                    return_type = analyzer.return_type;
+                }
+            }
+            Expression::Interpolation(interpolation) => {
+                let analyzer = InterpolationAnalyzer::analyze(interpolation, Rc::clone(&scope));
+                diagnosis.extend(analyzer.diagnosis);
+
+                changeable = false;
+                return_type = analyzer.return_type;
+            }
+            Expression::Alloc(alloc) => {
+                let analyzer = AllocAnalyzer::analyze(alloc, Rc::clone(&scope));
+                diagnosis.extend(analyzer.diagnosis);
+
+                changeable = false;
+                return_type = analyzer.return_type;
+            }
+            Expression::Lambda(lambda) => {
+                let analyzer = LambdaAnalyzer::analyze(lambda, Rc::clone(&scope));
+                diagnosis.extend(analyzer.diagnosis);
+
+                changeable = false;
+                return_type = analyzer.return_type;
+            }
+            Expression::Loop(r#loop) => {
+                let analyzer = LoopAnalyzer::analyze_expression(r#loop, Rc::clone(&scope));
+                diagnosis.extend(analyzer.diagnosis);
+
+                changeable = false;
+                return_type = analyzer.return_type;
+            }
+            Expression::If(r#if) => {
+                let analyzer = IfAnalyzer::analyze_expression(r#if, Rc::clone(&scope));
+                diagnosis.extend(analyzer.diagnosis);
+
+                changeable = false;
+                return_type = analyzer.return_type;
+            }
+            Expression::Block(block, _) => {
+                let analyzer = BlockAnalyzer::analyze_expression(block, Rc::clone(&scope));
+                diagnosis.extend(analyzer.diagnosis);
+
+                changeable = false;
+                return_type = analyzer.return_type;
+            }
+        }
+
+        // The blocks of an `if` or a block written as an expression must end with a value.
+        if matches!(expression, Expression::If(_) | Expression::Block(..))
+            && return_type == SemanticType::Void
+        {
+            diagnosis.push(SemanticError::ExpressionWithoutValue {
+                position: expression.get_position(),
+            });
         }
 
         Self {
@@ -1,13 +1,15 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::lang::position::Positioned;
+use crate::lang::position::{Position, Positioned};
 use crate::lang::semantic::scope::Scope;
 use crate::lang::semantic::semantic_error::SemanticError;
-use crate::lang::semantic::semantic_type::SemanticType;
-use crate::lang::syntax::expressions::expression::ExpressionMeta;
+use crate::lang::semantic::semantic_type::{SemanticType, TypeArguments};
+use crate::lang::semantic::symbol::Symbol;
+use crate::lang::syntax::expressions::expression::{Expression, ExpressionMeta};
 
 use super::expression_analyzer::ExpressionAnalyzer;
+use super::identifier_analyzer::IdentifierAnalyzer;
 
 pub struct ExpressionMetaAnalyzer {
     pub changeable: bool,
@@ -16,9 +18,52 @@ pub struct ExpressionMetaAnalyzer {
 }
 
 impl ExpressionMetaAnalyzer {
+    /// Infers the types given to the type parameters of a generic function from the
+    /// arguments of a call.
+    ///
+    /// # Arguments
+    ///
+    /// * `function_type` - The type of the generic function.
+    /// * `expressions` - The arguments of the call.
+    /// * `scope` - A reference-counted reference to the scope in which the call occurs.
+    ///
+    /// # Returns
+    ///
+    /// The type arguments, by the names of the type parameters.
+    pub fn infer_type_arguments(
+        function_type: &SemanticType,
+        expressions: &[Expression],
+        scope: Rc<RefCell<Scope>>,
+    ) -> TypeArguments {
+        let mut type_arguments = TypeArguments::new();
+
+        if let SemanticType::Function(params, _) = function_type {
+            for (param, expression) in params.iter().zip(expressions) {
+                let found = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type;
+                param.infer(&found, &mut type_arguments);
+            }
+        }
+
+        type_arguments
+    }
+
+    /// Analyzes the indexes, calls and methods applied to a value of the given type.
+    ///
+    /// # Arguments
+    ///
+    /// * `r#type` - The type of the value the meta is applied to.
+    /// * `meta` - The meta applied to the value.
+    /// * `receiver_changeable` - Whether the value the meta is applied to can be changed, as
+    ///   required by the methods declared with `mut self`.
+    /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
+    ///
+    /// # Returns
+    ///
+    /// An `ExpressionMetaAnalyzer` instance containing the analysis results.
     pub fn analyze(
         r#type: &SemanticType,
         meta: &ExpressionMeta,
+        receiver_changeable: bool,
         scope: Rc<RefCell<Scope>>,
     ) -> Self {
         let changeable: bool;
@@ -32,15 +77,21 @@ impl ExpressionMetaAnalyzer {
 
                 match r#type {
                     SemanticType::Any => changeable = true,
+                    // A string is indexed by its UTF-8 bytes, which are not always characters.
                     SemanticType::String => {
                         changeable = true;
-                        return_type = SemanticType::Char;
-                    },
-                    SemanticType::Array(array_type, _) => {
+                        return_type = SemanticType::U8;
+                    }
+                    SemanticType::Array(array_type, _)
+                    | SemanticType::GenericArray(array_type, _)
+                    | SemanticType::Slice(array_type)
+                    | SemanticType::Vec(array_type)
+                    | SemanticType::Ref(array_type) => {
                         if let Some(meta) = &meta.as_ref() {
                             let analyzer = ExpressionMetaAnalyzer::analyze(
                                 &array_type,
                                 &meta,
+                                receiver_changeable,
                                 Rc::clone(&scope),
                             );
 
@@ -61,46 +112,99 @@ impl ExpressionMetaAnalyzer {
                     }
                 }
             }
+            ExpressionMeta::Field(field, meta, position) => {
+                let element_type = match r#type {
+                    SemanticType::Tuple(types) => field
+                        .value
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| types.get(index)),
+                    _ => None,
+                };
+
+                match (r#type, element_type) {
+                    (SemanticType::Any, _) => changeable = true,
+                    (_, Some(element_type)) => {
+                        if let Some(meta) = &meta.as_ref() {
+                            let analyzer = ExpressionMetaAnalyzer::analyze(
+                                element_type,
+                                meta,
+                                receiver_changeable,
+                                Rc::clone(&scope),
+                            );
+
+                            diagnosis.extend(analyzer.diagnosis);
+
+                            changeable = analyzer.changeable;
+                            return_type = analyzer.return_type;
+                        } else {
+                            changeable = true;
+                            return_type = element_type.clone();
+                        }
+                    }
+                    (_, None) => {
+                        diagnosis.push(SemanticError::FieldNotFound {
+                            found: r#type.clone(),
+                            field: field.value.clone(),
+                            position: *position,
+                        });
+                        changeable = true;
+                    }
+                }
+            }
             ExpressionMeta::Call(expressions, meta, position) => {
                 for expression in expressions {
                     let analyzer = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope));
                     diagnosis.extend(analyzer.diagnosis);
                 }
 
+                // A generic function is called with its type parameters replaced by the
+                // types inferred from the arguments.
+                let instance_type;
+                let mut r#type = r#type;
+
+                if r#type.is_generic() {
+                    let type_arguments =
+                        Self::infer_type_arguments(r#type, expressions, Rc::clone(&scope));
+
+                    let mut type_parameters = vec![];
+                    r#type.get_type_parameters(&mut type_parameters);
+
+                    for (name, bound) in type_parameters {
+                        if let (Some(bound), Some(found)) = (bound, type_arguments.types.get(&name)) {
+                            let satisfies = scope.borrow().satisfies(found, &bound);
+
+                            if !satisfies {
+                                diagnosis.push(SemanticError::UnsatisfiedTypeBound {
+                                    found: found.clone(),
+                                    bound,
+                                    position: *position,
+                                });
+                            } else {
+                                Self::mark_trait_methods_read(found, &bound, Rc::clone(&scope));
+                            }
+                        }
+                    }
+
+                    instance_type = r#type.substitute(&type_arguments);
+                    r#type = &instance_type;
+                }
+
                 match r#type {
                     SemanticType::Any => changeable = true,
                     SemanticType::Function(params, function_return_type) => {
-                        if params.len() != expressions.len() {
-                            diagnosis.push(SemanticError::InvalidNumberOfParameters {
-                                expected: params.len(),
-                                found: expressions.len(),
-                                position: *position,
-                            });
-                        } else {
-                            for i in 0..params.len() {
-                                let expected_param_type = params.get(i).unwrap();
-                                let expression = expressions.get(i).unwrap();
-
-                                let analyzer =
-                                    ExpressionAnalyzer::analyze(expression, Rc::clone(&scope));
-
-                                if expected_param_type.clone() != analyzer.return_type
-                                    && (!expected_param_type.is_number()
-                                        || !analyzer.return_type.is_number())
-                                {
-                                    diagnosis.push(SemanticError::InvalidParameterType {
-                                        expected: expected_param_type.clone(),
-                                        found: analyzer.return_type,
-                                        position: expression.get_position(),
-                                    });
-                                }
-                            }
-                        }
+                        diagnosis.extend(Self::analyze_arguments(
+                            params,
+                            expressions,
+                            *position,
+                            Rc::clone(&scope),
+                        ));
 
                         if let Some(meta) = &meta.as_ref() {
                             let analyzer = ExpressionMetaAnalyzer::analyze(
                                 &function_return_type,
                                 &meta,
+                                false,
                                 Rc::clone(&scope),
                             );
 
@@ -121,6 +225,126 @@ impl ExpressionMetaAnalyzer {
                     }
                 }
             }
+            ExpressionMeta::Method(identifier, expressions, meta, position) => {
+                for expression in expressions {
+                    let analyzer = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope));
+                    diagnosis.extend(analyzer.diagnosis);
+                }
+
+                let method = Self::get_method(r#type, &identifier.name, Rc::clone(&scope));
+
+                match (r#type, method) {
+                    (SemanticType::Any, _) => changeable = true,
+                    (
+                        _,
+                        Some((SemanticType::Function(params, function_return_type), is_mutating)),
+                    ) => {
+                        // The value is changed in place, so it must be bound mutably.
+                        if is_mutating && !receiver_changeable {
+                            diagnosis.push(SemanticError::ValueCannotBeReassigned {
+                                position: identifier.get_position(),
+                            });
+                        }
+
+                        diagnosis.extend(Self::analyze_arguments(
+                            &params[1..],
+                            expressions,
+                            *position,
+                            Rc::clone(&scope),
+                        ));
+
+                        let name = r#type.get_method_name(&identifier.name);
+                        let mutable_params = scope.borrow().get_mutable_params(&name);
+
+                        if let Some((_, mutable_params)) = mutable_params.split_first() {
+                            diagnosis.extend(Self::analyze_mutable_arguments(
+                                mutable_params,
+                                expressions,
+                                Rc::clone(&scope),
+                            ));
+                        }
+
+                        if let Some(meta) = &meta.as_ref() {
+                            let analyzer = ExpressionMetaAnalyzer::analyze(
+                                &function_return_type,
+                                meta,
+                                false,
+                                Rc::clone(&scope),
+                            );
+
+                            diagnosis.extend(analyzer.diagnosis);
+
+                            changeable = analyzer.changeable;
+                            return_type = analyzer.return_type;
+                        } else {
+                            changeable = false;
+                            return_type = function_return_type.as_ref().clone();
+                        }
+                    }
+                    _ => {
+                        diagnosis.push(SemanticError::MethodNotFound {
+                            found: r#type.clone(),
+                            name: identifier.name.clone(),
+                            position: identifier.get_position(),
+                        });
+                        changeable = true;
+                    }
+                }
+            }
+            ExpressionMeta::Try(meta, position) => {
+                // The error, or the missing value, is returned by the enclosing function, which
+                // must return a result of the same error or an optional.
+                let function_type = scope.borrow().get_function_type();
+
+                if scope.borrow().is_deferred() {
+                    diagnosis.push(SemanticError::InvalidDeferredExit {
+                        position: *position,
+                    });
+                }
+
+                let expected = match function_type {
+                    Some(SemanticType::Function(_, return_type)) => *return_type,
+                    _ => SemanticType::Any,
+                };
+
+                let value_type = match (r#type, &expected) {
+                    (SemanticType::Any, _) => SemanticType::Any,
+                    (SemanticType::Result(value_type, error), SemanticType::Result(_, expected))
+                        if expected.accepts(error) =>
+                    {
+                        value_type.as_ref().clone()
+                    }
+                    (SemanticType::Optional(value_type), SemanticType::Optional(_)) => {
+                        value_type.as_ref().clone()
+                    }
+                    _ => {
+                        diagnosis.push(SemanticError::InvalidTry {
+                            found: r#type.clone(),
+                            expected,
+                            position: *position,
+                        });
+
+                        SemanticType::Any
+                    }
+                };
+
+                if let Some(meta) = &meta.as_ref() {
+                    let analyzer = ExpressionMetaAnalyzer::analyze(
+                        &value_type,
+                        meta,
+                        false,
+                        Rc::clone(&scope),
+                    );
+
+                    diagnosis.extend(analyzer.diagnosis);
+
+                    changeable = analyzer.changeable;
+                    return_type = analyzer.return_type;
+                } else {
+                    changeable = false;
+                    return_type = value_type;
+                }
+            }
         }
 
         Self {
@@ -129,4 +353,283 @@ impl ExpressionMetaAnalyzer {
             diagnosis,
         }
     }
+
+    /// Gets the type of a method of a value of the given type, along with whether it changes
+    /// the value. The methods are declared as functions named after the type, whose first
+    /// parameter is the value they are called on, while the ones of a type parameter are the
+    /// methods of the trait bounding it.
+    fn get_method(
+        r#type: &SemanticType,
+        name: &str,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Option<(SemanticType, bool)> {
+        if let SemanticType::TypeParameter(_, Some(bound)) = r#type {
+            let symbol = scope.borrow().get(bound);
+
+            return match symbol {
+                Some(Symbol::Trait { methods, .. }) => methods
+                    .into_iter()
+                    .find(|method| method.name == name)
+                    .map(|method| {
+                        let mut type_arguments = TypeArguments::new();
+                        type_arguments
+                            .types
+                            .insert(String::from("Self"), r#type.clone());
+
+                        (method.symbol_type.substitute(&type_arguments), method.mutating)
+                    }),
+                _ => None,
+            };
+        }
+
+        let name = r#type.get_method_name(name);
+        let symbol = scope.borrow().get(&name);
+
+        match symbol {
+            Some(Symbol::Function { symbol_type, .. }) => {
+                scope.borrow_mut().mark_read(&name);
+
+                let is_mutating = scope.borrow().is_mutating(&name);
+                Some((symbol_type, is_mutating))
+            }
+            _ => None,
+        }
+    }
+
+    /// Marks the methods of a trait as read for the type given to a type parameter bounded by
+    /// it, as the instance of the generic function calls them.
+    fn mark_trait_methods_read(r#type: &SemanticType, bound: &str, scope: Rc<RefCell<Scope>>) {
+        let symbol = scope.borrow().get(bound);
+
+        if let Some(Symbol::Trait { methods, .. }) = symbol {
+            for method in methods {
+                scope
+                    .borrow_mut()
+                    .mark_read(&r#type.get_method_name(&method.name));
+            }
+        }
+    }
+
+    /// Verifies that the arguments given to `mut` parameters can be changed when they are arrays,
+    /// slices, vectors or strings, which are passed by pointer and so are changed in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `mutable_params` - Whether each parameter is declared as `mut`.
+    /// * `expressions` - The arguments of the call.
+    /// * `scope` - A reference-counted reference to the scope in which the call occurs.
+    ///
+    /// # Returns
+    ///
+    /// A `ValueCannotBeReassigned` error for each argument that cannot be changed.
+    pub fn analyze_mutable_arguments(
+        mutable_params: &[bool],
+        expressions: &[Expression],
+        scope: Rc<RefCell<Scope>>,
+    ) -> Vec<SemanticError> {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        for (mutable, expression) in mutable_params.iter().zip(expressions) {
+            // Only the values kept by a binding are seen after the call, the others are new.
+            if let (true, Expression::Identifier(identifier, meta)) = (mutable, expression) {
+                if !Self::is_stored(meta) {
+                    continue;
+                }
+
+                let analyzer = IdentifierAnalyzer::analyze(identifier, meta, Rc::clone(&scope));
+                let by_pointer = matches!(
+                    analyzer.return_type,
+                    SemanticType::Array(..)
+                        | SemanticType::GenericArray(..)
+                        | SemanticType::Slice(_)
+                        | SemanticType::Vec(_)
+                        | SemanticType::String
+                );
+
+                if by_pointer && !analyzer.changeable {
+                    diagnosis.push(SemanticError::ValueCannotBeReassigned {
+                        position: expression.get_position(),
+                    });
+                }
+            }
+        }
+
+        diagnosis
+    }
+
+    /// Whether the accesses reach a value stored in the binding they are made on, rather than
+    /// one returned by a call.
+    fn is_stored(meta: &Option<ExpressionMeta>) -> bool {
+        match meta {
+            None => true,
+            Some(ExpressionMeta::Index(_, meta, _) | ExpressionMeta::Field(_, meta, _)) => {
+                Self::is_stored(meta)
+            }
+            Some(_) => false,
+        }
+    }
+
+    /// Verifies that the arguments of a call are given to the parameters of the given types.
+    fn analyze_arguments(
+        params: &[SemanticType],
+        expressions: &[Expression],
+        position: Position,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Vec<SemanticError> {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        if params.len() != expressions.len() {
+            diagnosis.push(SemanticError::InvalidNumberOfParameters {
+                expected: params.len(),
+                found: expressions.len(),
+                position,
+            });
+
+            return diagnosis;
+        }
+
+        for (expected_param_type, expression) in params.iter().zip(expressions) {
+            let analyzer = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope));
+
+            if !expected_param_type.accepts(&analyzer.return_type) {
+                diagnosis.push(SemanticError::InvalidParameterType {
+                    expected: expected_param_type.clone(),
+                    found: analyzer.return_type,
+                    position: expression.get_position(),
+                });
+            }
+        }
+
+        diagnosis
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    #[test]
+    fn test_mutable_arguments() {
+        let function = "fun change(mut a: [i32; 3]) { a[0] = 100; }";
+
+        let code = format!("{} fun main() {{ let a = [1, 2, 3]; change(a); }}", function);
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::ValueCannotBeReassigned { .. }]
+        ));
+
+        let code = format!("{} fun main() {{ let mut a = [1, 2, 3]; change(a); }}", function);
+        assert!(analyze(&code).is_empty());
+
+        // A new value is not seen by anyone else.
+        let code = format!("{} fun main() {{ change([1, 2, 3]); }}", function);
+        assert!(analyze(&code).is_empty());
+
+        // Scalars are copied into the parameter.
+        let code = "fun count(mut n: i32) { n += 1; } fun main() { let n = 1; count(n); }";
+        assert!(analyze(code).is_empty());
+    }
+
+    #[test]
+    fn test_methods() {
+        let methods = "impl i32 { fun double(self) -> i32 { return self * 2; } } \
+            impl [i32] { fun first(self) -> i32 { return self[0]; } }";
+
+        let code = format!(
+            "{} fun first_of(s: [i32]) -> i32 {{ return s.first(); }} fun main() {{ let n = 2; println n.double(); println first_of([1, 2]); }}",
+            methods
+        );
+        assert!(analyze(&code).is_empty());
+
+        let code = format!(
+            "{} fun main() {{ let n = 2; println n.triple(); }}",
+            methods
+        );
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::MethodNotFound { ref name, .. }] if name == "triple"
+        ));
+
+        // The methods of slices are only found on slices, as arrays and vectors are only
+        // converted into them when passed to a parameter.
+        let code = format!(
+            "{} fun main() {{ let a = [1, 2]; println a.first(); }}",
+            methods
+        );
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::MethodNotFound { .. }]
+        ));
+
+        let code = format!(
+            "{} fun main() {{ let v: vec<i32> = [1, 2]; println v.first(); }}",
+            methods
+        );
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::MethodNotFound { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_string_indexes() {
+        // The bytes of a string are read and written as numbers, as a character may take more
+        // than one of them.
+        let code = "fun main() { let mut s = \"olá\"; let b: u8 = s[2]; s[0] = 79; }";
+        assert!(analyze(code).is_empty());
+
+        // An ASCII character takes a single byte.
+        let code = "fun main() { let mut s = \"olá\"; s[0] = 'O'; }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let mut s = \"olá\"; s[0] = 'é'; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::TypeMismatch { .. }]
+        ));
+
+        let code = "fun main() { let c = 'O'; let mut s = \"olá\"; s[0] = c; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::TypeMismatch { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_try() {
+        let function =
+            "fun get(n: i32) -> result<i32, string> { if n > 0 { return ok(n); } return err(\"no\"); }";
+
+        let code = format!(
+            "{} fun sum() -> result<i32, string> {{ return ok(get(1)? + get(2)?); }}",
+            function
+        );
+        assert!(analyze(&code).is_empty());
+
+        // The error is returned by the enclosing function, which must return it too.
+        let code = format!("{} fun sum() -> i32 {{ return get(1)?; }}", function);
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::InvalidTry { .. }]
+        ));
+
+        let code = format!(
+            "{} fun sum() -> result<i32, i32> {{ return ok(get(1)?); }}",
+            function
+        );
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::InvalidTry { .. }]
+        ));
+
+        // A loop condition is evaluated again and again, so nothing can be hoisted from it.
+        let code = format!(
+            "{} fun sum() -> result<i32, string> {{ while get(1)? > 0 {{ }} return ok(0); }}",
+            function
+        );
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::HoistedNotAllowed { .. }]
+        ));
+    }
 }
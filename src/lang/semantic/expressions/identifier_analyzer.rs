@@ -2,6 +2,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use crate::lang::position::Positioned;
+use crate::lang::semantic::builtin_function::{BuiltinFunction, Overload};
 use crate::lang::semantic::scope::Scope;
 use crate::lang::semantic::semantic_error::SemanticError;
 use crate::lang::semantic::semantic_type::SemanticType;
@@ -9,6 +10,8 @@ use crate::lang::semantic::symbol::Symbol;
 use crate::lang::syntax::expressions::expression::ExpressionMeta;
 use crate::lang::syntax::shared::identifier::Identifier;
 
+use super::builtin_analyzer::BuiltinAnalyzer;
+use super::expression_analyzer::ExpressionAnalyzer;
 use super::expression_meta_analyzer::ExpressionMetaAnalyzer;
 
 pub struct IdentifierAnalyzer {
@@ -23,29 +26,146 @@ impl IdentifierAnalyzer {
         meta: &Option<ExpressionMeta>,
         scope: Rc<RefCell<Scope>>,
     ) -> Self {
-        let changeable: bool;
+        let analyzer = Self::resolve(identifier, meta, true, Rc::clone(&scope));
+        scope.borrow_mut().mark_read(&identifier.name);
+
+        let captured = scope.borrow().get_captured(&identifier.name);
+
+        if captured.is_some() {
+            scope.borrow_mut().mark_captured(&identifier.name);
+        }
+
+        analyzer
+    }
+
+    /// Analyzes an identifier that is the target of a plain assignment, which
+    /// writes to the symbol without reading its value. An optional is assigned as a whole even
+    /// where it was checked against none.
+    pub fn analyze_assignee(identifier: &Identifier, scope: Rc<RefCell<Scope>>) -> Self {
+        Self::resolve(identifier, &None, false, scope)
+    }
+
+    fn resolve(
+        identifier: &Identifier,
+        meta: &Option<ExpressionMeta>,
+        unwrap_checked: bool,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Self {
+        let mut changeable: bool;
         let mut return_type = SemanticType::Any;
         let mut diagnosis: Vec<SemanticError> = vec![];
 
         let identifier_name = identifier.name.clone();
 
-        if let Some(symbol) = scope.borrow().get(&identifier_name) {
+        let symbol = scope.borrow().get(&identifier_name);
+
+        // Lambda expressions capture values, so the captured bindings cannot be changed.
+        let captured = scope.borrow().get_captured(&identifier_name).is_some();
+
+        if symbol.is_none() && BuiltinAnalyzer::is_builtin(&identifier_name) {
+            let analyzer = BuiltinAnalyzer::analyze(identifier, meta, Rc::clone(&scope));
+
+            return Self {
+                changeable: analyzer.changeable,
+                return_type: analyzer.return_type,
+                diagnosis: analyzer.diagnosis,
+            };
+        }
+
+        if let Some(symbol) = symbol {
             match &symbol {
                 Symbol::Variable { symbol_type, .. }
                 | Symbol::Parameter { symbol_type, .. }
                 | Symbol::Function { symbol_type, .. } => {
+                    // An optional that was checked against none is read as its value.
+                    let symbol_type = match symbol_type {
+                        SemanticType::Optional(inner_type)
+                            if unwrap_checked
+                                && scope.borrow().is_checked(identifier.get_position()) =>
+                        {
+                            inner_type.as_ref()
+                        }
+                        _ => symbol_type,
+                    };
+
+                    // A builtin function is called with the overload taking its arguments.
+                    let overload_type =
+                        match Self::resolve_overload(&symbol, meta, Rc::clone(&scope)) {
+                            Some(Ok(overload)) => Some(overload.get_type()),
+                            Some(Err(found)) => {
+                                diagnosis.push(SemanticError::OverloadNotFound {
+                                    name: identifier_name.clone(),
+                                    found,
+                                    position: identifier.get_position(),
+                                });
+
+                                return Self {
+                                    changeable: false,
+                                    return_type: SemanticType::Any,
+                                    diagnosis,
+                                };
+                            }
+                            None => None,
+                        };
+                    let symbol_type = overload_type.as_ref().unwrap_or(symbol_type);
+
+                    // The instance of a generic function is only known from the arguments of a
+                    // call.
+                    if matches!(symbol, Symbol::Function { .. })
+                        && symbol_type.is_generic()
+                        && !matches!(meta, Some(ExpressionMeta::Call(..)))
+                    {
+                        diagnosis.push(SemanticError::GenericNotCalled {
+                            name: identifier_name.clone(),
+                            position: identifier.get_position(),
+                        });
+
+                        return Self {
+                            changeable: false,
+                            return_type: SemanticType::Any,
+                            diagnosis,
+                        };
+                    }
+
                     if let Some(meta) = &meta {
-                        let analyzer =
-                            ExpressionMetaAnalyzer::analyze(&symbol_type, &meta, Rc::clone(&scope));
+                        let analyzer = ExpressionMetaAnalyzer::analyze(
+                            symbol_type,
+                            meta,
+                            symbol.is_mutable() && !captured,
+                            Rc::clone(&scope),
+                        );
                         diagnosis.extend(analyzer.diagnosis);
 
-                        changeable = analyzer.changeable;
+                        if let (Symbol::Function { .. }, ExpressionMeta::Call(expressions, ..)) =
+                            (&symbol, meta)
+                        {
+                            let mutable_params =
+                                scope.borrow().get_mutable_params(&identifier_name);
+
+                            diagnosis.extend(ExpressionMetaAnalyzer::analyze_mutable_arguments(
+                                &mutable_params,
+                                expressions,
+                                Rc::clone(&scope),
+                            ));
+                        }
+
+                        // Writing to an element also requires the binding to be mutable, except
+                        // through a reference, which does not own what it points to.
+                        changeable = analyzer.changeable
+                            && (symbol.is_mutable()
+                                || matches!(symbol, Symbol::Function { .. })
+                                || matches!(symbol_type, SemanticType::Ref(_)));
                         return_type = analyzer.return_type;
                     } else {
-                        changeable = !matches!(symbol, Symbol::Function { .. });
+                        changeable = symbol.is_mutable();
                         return_type = symbol_type.clone();
                     }
                 }
+                // The size parameters of a generic function are read as numbers.
+                Symbol::SizeParameter { .. } if meta.is_none() => {
+                    changeable = false;
+                    return_type = SemanticType::I32;
+                }
                 _ => {
                     changeable = true;
                     diagnosis.push(SemanticError::IdentifierNotVariableOrParam {
@@ -53,6 +173,10 @@ impl IdentifierAnalyzer {
                     });
                 }
             }
+
+            if captured {
+                changeable = false;
+            }
         } else {
             // The variable/constant/param/function is not registered on the symbol table
 
@@ -68,4 +192,46 @@ impl IdentifierAnalyzer {
             diagnosis,
         }
     }
+
+    /// Gets the overload of a builtin function that is called with the arguments given to it, or
+    /// its first overload when it is not called, if the symbol is a builtin function. The types
+    /// of the arguments are given back when no overload holds them.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - The symbol the identifier refers to.
+    /// * `meta` - The meta that follows the identifier.
+    /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
+    ///
+    /// # Returns
+    ///
+    /// The overload, such as the one of `sqrtf` for `sqrt(1.5)`.
+    pub fn resolve_overload(
+        symbol: &Symbol,
+        meta: &Option<ExpressionMeta>,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Option<Result<Overload, Vec<SemanticType>>> {
+        let function = match symbol {
+            Symbol::Function {
+                name,
+                builtin: true,
+                ..
+            } => BuiltinFunction::find(name)?,
+            _ => return None,
+        };
+
+        match meta {
+            Some(ExpressionMeta::Call(expressions, ..)) => {
+                let types: Vec<SemanticType> = expressions
+                    .iter()
+                    .map(|expression| {
+                        ExpressionAnalyzer::analyze(expression, Rc::clone(&scope)).return_type
+                    })
+                    .collect();
+
+                Some(function.resolve(&types).cloned().ok_or(types))
+            }
+            _ => Some(Ok(function.overloads[0].clone())),
+        }
+    }
 }
@@ -0,0 +1,112 @@
+use crate::lang::position::{Position, Positioned};
+use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::{scope::Scope, semantic_error::SemanticError};
+use crate::lang::syntax::expressions::interpolation::{Format, Interpolation};
+
+use std::{cell::RefCell, rc::Rc};
+
+use super::expression_analyzer::ExpressionAnalyzer;
+
+/// Analyzer that performs the semantic analysis for interpolated strings, whose values must be
+/// printable with their formats.
+pub struct InterpolationAnalyzer {
+    /// The inferred return type after semantic analyses.
+    pub(crate) return_type: SemanticType,
+
+    /// A collection of semantic errors found during analysis.
+    pub(crate) diagnosis: Vec<SemanticError>,
+}
+
+impl InterpolationAnalyzer {
+    /// Analyzes the provided interpolated string within a given scope.
+    /// # Arguments
+    ///
+    /// * `interpolation` - A reference to the interpolated string to be analyzed.
+    /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
+    ///
+    /// # Returns
+    ///
+    /// An `InterpolationAnalyzer` instance containing the analysis results.
+    pub fn analyze(interpolation: &Interpolation, scope: Rc<RefCell<Scope>>) -> Self {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        for (expression, format) in interpolation.get_values() {
+            let analyzer = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope));
+            diagnosis.extend(analyzer.diagnosis);
+
+            if let Some(error) =
+                Self::analyze_format(&analyzer.return_type, format, expression.get_position())
+            {
+                diagnosis.push(error);
+            }
+        }
+
+        Self {
+            return_type: SemanticType::String,
+            diagnosis,
+        }
+    }
+
+    /// Verifies whether a value of the given type can be written with the format. Only numbers
+    /// are padded with zeros, only integers are written in hexadecimal and only floats and
+    /// strings have a precision, which cuts strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `r#type` - The type of the value.
+    /// * `format` - The format of the value.
+    /// * `position` - The position of the value.
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidFormat` error if the value cannot be written with the format.
+    pub fn analyze_format(
+        r#type: &SemanticType,
+        format: &Format,
+        position: Position,
+    ) -> Option<SemanticError> {
+        let valid = match r#type {
+            // The error was already reported.
+            SemanticType::Any => true,
+            // The instances of generic functions are only known when they are generated.
+            SemanticType::TypeParameter(..) => format.precision.is_none() && format.hex.is_none(),
+            _ if r#type.is_integer() => format.precision.is_none(),
+            SemanticType::F32 | SemanticType::F64 => format.hex.is_none(),
+            SemanticType::String | SemanticType::CString => !format.zero && format.hex.is_none(),
+            SemanticType::Bool | SemanticType::Char | SemanticType::Ref(_) => {
+                !format.zero && format.precision.is_none() && format.hex.is_none()
+            }
+            _ => false,
+        };
+
+        if valid {
+            return None;
+        }
+
+        Some(SemanticError::InvalidFormat {
+            format: format.to_string(),
+            found: r#type.clone(),
+            position,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    #[test]
+    fn test_formats() {
+        let code = "fun main() { let c = 'a'; let s = \"ab\"; println \"{1:04} {255:x} {2.5:.2} {s:<5.1} {c:3}\"; }";
+        assert!(analyze(code).is_empty());
+
+        // Only integers are written in hexadecimal, and only numbers are padded with zeros.
+        for format in ["{1.5:x}", "{s:04}", "{'a':.1}", "{true:x}", "{1:.2}"] {
+            let code = format!("fun main() {{ let s = \"a\"; println \"{}\"; }}", format);
+            assert!(matches!(
+                analyze(&code)[..],
+                [SemanticError::InvalidFormat { .. }]
+            ));
+        }
+    }
+}
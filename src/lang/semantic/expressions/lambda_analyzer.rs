@@ -0,0 +1,149 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::lang::position::Positioned;
+use crate::lang::semantic::analyzer::Scopes;
+use crate::lang::semantic::scope::{Func, Scope};
+use crate::lang::semantic::semantic_error::SemanticError;
+use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::shared::type_analyzer::TypeAnalyzer;
+use crate::lang::semantic::statements::block_analyzer::BlockAnalyzer;
+use crate::lang::semantic::symbol::Symbol;
+use crate::lang::syntax::expressions::lambda::Lambda;
+
+pub struct LambdaAnalyzer {
+    pub return_type: SemanticType,
+    pub diagnosis: Vec<SemanticError>,
+}
+
+impl LambdaAnalyzer {
+    pub fn analyze(lambda: &Lambda, scope: Rc<RefCell<Scope>>) -> Self {
+        Self::analyze_body(lambda, scope, &mut Scopes::new())
+    }
+
+    /// Analyzes a lambda expression along with its body, which is analyzed as the body of a
+    /// function whose scope is nested in the scope where the lambda is written, so it can read
+    /// the locals around it.
+    ///
+    /// # Arguments
+    ///
+    /// * `lambda` - The lambda expression to be analyzed.
+    /// * `scope` - A reference-counted reference to the scope in which the lambda is written.
+    /// * `scopes` - Where the scopes of the blocks of the lambda are stored.
+    ///
+    /// # Returns
+    ///
+    /// A `LambdaAnalyzer` instance containing the analysis results.
+    pub fn analyze_body(lambda: &Lambda, scope: Rc<RefCell<Scope>>, scopes: &mut Scopes) -> Self {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        let mut params: Vec<(String, SemanticType, bool)> = vec![];
+
+        for param_declaration in &lambda.params_declaration.params {
+            let param_name = param_declaration.identifier.name.clone();
+
+            // Verify if the parameter was already declared, in the lambda or around it.
+            if scope.borrow().is_declared(&param_name)
+                || params.iter().any(|(name, ..)| *name == param_name)
+            {
+                diagnosis.push(SemanticError::DuplicatedIdentifier {
+                    position: param_declaration.identifier.get_position(),
+                });
+            }
+
+            let analyzer = TypeAnalyzer::analyze(&param_declaration.r#type, Rc::clone(&scope));
+            diagnosis.extend(analyzer.diagnosis);
+
+            params.push((param_name, analyzer.result_type, param_declaration.mutable));
+        }
+
+        let lambda_return_type = match &lambda.r#type {
+            Some(r#type) => {
+                let analyzer = TypeAnalyzer::analyze(r#type, Rc::clone(&scope));
+                diagnosis.extend(analyzer.diagnosis);
+
+                analyzer.result_type
+            }
+            None => SemanticType::Void,
+        };
+
+        let return_type = SemanticType::Function(
+            params.iter().map(|(_, r#type, _)| r#type.clone()).collect(),
+            Box::new(lambda_return_type),
+        );
+
+        // The reads inside the lambda count as reads of the function where it is written.
+        let function_name = scope.borrow().get_function_name().unwrap_or_default();
+
+        let lambda_scope = Rc::new(RefCell::new(Scope::new(
+            Rc::clone(&scope),
+            false,
+            Some(Func {
+                name: function_name,
+                return_type: return_type.clone(),
+                is_lambda: true,
+            }),
+        )));
+
+        for ((name, symbol_type, mutable), param_declaration) in
+            params.into_iter().zip(&lambda.params_declaration.params)
+        {
+            lambda_scope.borrow_mut().declare(
+                Symbol::Parameter {
+                    name,
+                    symbol_type,
+                    mutable,
+                },
+                param_declaration.identifier.get_position(),
+            );
+        }
+
+        let analyzer = BlockAnalyzer::analyze_within_scope(&lambda.block, lambda_scope, scopes);
+        diagnosis.extend(analyzer.diagnosis);
+
+        Self {
+            return_type,
+            diagnosis,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    #[test]
+    fn test_captures() {
+        let code = "fun main() { let a = 1; let f = fun(x: i32) -> i32 { let c = 3; return a + c + x; }; println f(a); }";
+        assert!(analyze(code).is_empty());
+
+        // The captured values are copied into the lambda, so they cannot be changed by it.
+        let code = "fun main() { let mut a = 1; let f = fun() { a = 2; }; f(); println a; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ValueCannotBeReassigned { .. }]
+        ));
+
+        let code = "fun main() { let f = fun() -> i32 { return a; }; let a = 1; println f() + a; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::IdentifierNotFound { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_params() {
+        let code =
+            "fun main() { let a = 1; let f = fun(a: i32) -> i32 { return a; }; println f(a); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::DuplicatedIdentifier { .. }]
+        ));
+
+        let code = "fun main() { let f = fun(x: i32) -> i32 { return x; }; println f(true); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidParameterType { .. }]
+        ));
+    }
+}
@@ -1,8 +1,13 @@
+pub mod alloc_analyzer;
 pub mod array_analyzer;
 pub mod parenthesized_analyzer;
 pub mod range_analyzer;
 pub mod unary_analyzer;
 pub mod binary_analyzer;
+pub mod builtin_analyzer;
 pub mod identifier_analyzer;
+pub mod interpolation_analyzer;
 pub mod expression_analyzer;
 pub mod expression_meta_analyzer;
+pub mod lambda_analyzer;
+pub mod tuple_analyzer;
@@ -31,8 +31,12 @@ impl ParenthesizedAnalyzer {
         diagnosis.extend(analyzer.diagnosis);
 
         if let Some(meta) = &meta {
-            let analyzer =
-                ExpressionMetaAnalyzer::analyze(&analyzer.return_type, &meta, Rc::clone(&scope));
+            let analyzer = ExpressionMetaAnalyzer::analyze(
+                &analyzer.return_type,
+                meta,
+                analyzer.changeable,
+                Rc::clone(&scope),
+            );
             diagnosis.extend(analyzer.diagnosis);
 
             changeable = analyzer.changeable;
@@ -5,7 +5,7 @@ use crate::lang::position::Positioned;
 use crate::lang::semantic::scope::Scope;
 use crate::lang::semantic::semantic_error::SemanticError;
 use crate::lang::semantic::semantic_type::SemanticType;
-use crate::lang::syntax::expressions::range::Range;
+use crate::lang::syntax::expressions::{expression::Expression, literal::Literal, range::Range};
 
 use super::expression_analyzer::ExpressionAnalyzer;
 
@@ -29,6 +29,10 @@ impl RangeAnalyzer {
 
         diagnosis.extend(analyzer.diagnosis);
 
+        if let Some(step) = &range.step {
+            diagnosis.extend(Self::analyze_step(step, Rc::clone(&scope)));
+        }
+
         if let TokenKind::DotDot | TokenKind::DotDotEquals = &range.operator.token.kind {
             if left_return_type.is_number() && right_return_type.is_number() {
                 return_type = SemanticType::Range;
@@ -46,4 +50,75 @@ impl RangeAnalyzer {
             diagnosis,
         }
     }
+
+    /// Analyzes the `step` clause of a range, which must be an integer other than zero. A step
+    /// that is only known to be zero at runtime ends the iteration instead.
+    pub fn analyze_step(step: &Expression, scope: Rc<RefCell<Scope>>) -> Vec<SemanticError> {
+        let analyzer = ExpressionAnalyzer::analyze(step, scope);
+        let mut diagnosis = analyzer.diagnosis;
+
+        if !analyzer.return_type.is_integer() {
+            diagnosis.push(SemanticError::InvalidRangeStep {
+                found: analyzer.return_type,
+                position: step.get_position(),
+            });
+        }
+
+        if let Expression::Literal(Literal::Number(token)) = step {
+            if token.value.parse::<i64>() == Ok(0) {
+                diagnosis.push(SemanticError::ZeroRangeStep {
+                    position: step.get_position(),
+                });
+            }
+        }
+
+        diagnosis
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{
+        semantic_error::SemanticError, semantic_type::SemanticType, test_helpers::analyze,
+    };
+
+    #[test]
+    fn test_range_values() {
+        let code =
+            "fun main() { let r = 10..=0 step -2; for i in r { println i; } println 4 in r; }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let r = 0..true; println r; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidRangeOperands { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_range_steps() {
+        let code = "fun main() { let s = 3; for i in 0..10 step s { println i; } }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { for i in 0..10 step 0.5 { println i; } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidRangeStep {
+                found: SemanticType::F32,
+                ..
+            }]
+        ));
+
+        let code = "fun main() { for i in 10..0 step 0 { println i; } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ZeroRangeStep { .. }]
+        ));
+
+        let code = "fun main() { let r = 10..0 step 0; println 10 in r; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ZeroRangeStep { .. }]
+        ));
+    }
 }
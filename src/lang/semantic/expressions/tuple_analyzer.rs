@@ -0,0 +1,82 @@
+use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::{scope::Scope, semantic_error::SemanticError};
+use crate::lang::syntax::expressions::tuple::Tuple;
+
+use std::{cell::RefCell, rc::Rc};
+
+use super::expression_analyzer::ExpressionAnalyzer;
+
+/// Analyzer that performs the semantic analysis for tuples.
+pub struct TupleAnalyzer {
+    /// The inferred return type after semantic analyses.
+    pub(crate) return_type: SemanticType,
+
+    /// A collection of semantic errors found during analysis.
+    pub(crate) diagnosis: Vec<SemanticError>,
+}
+
+impl TupleAnalyzer {
+    /// Analyzes the provided tuple within a given scope.
+    /// # Arguments
+    ///
+    /// * `tuple` - A reference to the tuple to be analyzed.
+    /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
+    ///
+    /// # Returns
+    ///
+    /// A `TupleAnalyzer` instance containing the analysis results.
+    pub fn analyze(tuple: &Tuple, scope: Rc<RefCell<Scope>>) -> Self {
+        let mut types: Vec<SemanticType> = vec![];
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        for expression in &tuple.expressions {
+            let analyzer = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope));
+            diagnosis.extend(analyzer.diagnosis);
+
+            types.push(analyzer.return_type);
+        }
+
+        Self {
+            return_type: SemanticType::Tuple(types),
+            diagnosis,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    #[test]
+    fn test_fields() {
+        let code = "fun main() { let t = (1, (true, \"a\")); let a: i32 = t.0; let b: bool = t.1.0; println a; println b; }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let t = (1, true); println t.2; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::FieldNotFound { .. }]
+        ));
+
+        let code = "fun main() { let t = (1, true); let a: bool = t.0; println a; }";
+        assert!(!analyze(code).is_empty());
+    }
+
+    #[test]
+    fn test_destructuring() {
+        let code = "fun main() { let (a, b) = (1, \"b\"); println a; println b; }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let (a, b) = (1, 2, 3); println a + b; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidDestructuring { .. }]
+        ));
+
+        let code = "fun main() { let (a, b) = 1; println a + b; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidDestructuring { .. }]
+        ));
+    }
+}
@@ -0,0 +1,417 @@
+use crate::lang::position::Positioned;
+use crate::lang::semantic::semantic_error::SemanticError;
+use crate::lang::syntax::expressions::expression::{Expression, ExpressionMeta};
+use crate::lang::syntax::shared::{block::Block, identifier::Identifier};
+use crate::lang::syntax::statements::{r#if::If, r#loop::Loop, statement::Statement};
+use crate::lang::syntax::top_level_statements::function::Function;
+
+use std::collections::HashSet;
+
+/// The set of variables that may not have been assigned at a given point of
+/// the function.
+#[derive(Clone, Debug)]
+struct State {
+    /// Variables declared without a value that are not assigned in at least
+    /// one of the paths that reach this point.
+    uninitialized: HashSet<String>,
+
+    /// Whether this point can be reached at all. It becomes `false` after a
+    /// `return`, `break` or `continue` statement.
+    reachable: bool,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            uninitialized: HashSet::new(),
+            reachable: true,
+        }
+    }
+
+    fn unreachable() -> Self {
+        Self {
+            uninitialized: HashSet::new(),
+            reachable: false,
+        }
+    }
+
+    /// Merges two paths of the control flow graph. A variable is only
+    /// considered initialized if it was assigned in every reachable path.
+    fn join(&self, other: &State) -> State {
+        if !self.reachable {
+            return other.clone();
+        }
+
+        if !other.reachable {
+            return self.clone();
+        }
+
+        Self {
+            uninitialized: self
+                .uninitialized
+                .union(&other.uninitialized)
+                .cloned()
+                .collect(),
+            reachable: true,
+        }
+    }
+}
+
+/// The states that leave a loop through `break` and `continue` statements.
+#[derive(Default)]
+struct LoopExits {
+    label: Option<String>,
+    breaks: Vec<State>,
+    continues: Vec<State>,
+}
+
+/// Analyzer responsible for verifying that every variable is definitely
+/// assigned, in all the paths of a function, before being read.
+pub struct InitializationAnalyzer {
+    /// A collection of semantic errors found during analysis.
+    pub(crate) diagnosis: Vec<SemanticError>,
+
+    state: State,
+    loops: Vec<LoopExits>,
+
+    /// Variables that were already reported, so that the same error is not
+    /// reported for every following read.
+    reported: HashSet<String>,
+}
+
+impl InitializationAnalyzer {
+    /// Analyzes the body of the provided function.
+    ///
+    /// # Arguments
+    ///
+    /// * `function` - A reference to the function to be analyzed.
+    ///
+    /// # Returns
+    ///
+    /// An `InitializationAnalyzer` instance containing the analysis results.
+    pub fn analyze(function: &Function) -> Self {
+        let mut analyzer = Self {
+            diagnosis: vec![],
+            state: State::new(),
+            loops: vec![],
+            reported: HashSet::new(),
+        };
+
+        analyzer.analyze_block(&function.block);
+        analyzer
+    }
+
+    fn analyze_block(&mut self, block: &Block) {
+        for statement in &block.statements {
+            self.analyze_statement(statement);
+        }
+
+        if let Some(expression) = &block.expression {
+            self.analyze_expression(expression);
+        }
+    }
+
+    fn analyze_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block(block) => self.analyze_block(block),
+            Statement::Let(r#let) => {
+                let name = r#let.identifier.name.clone();
+
+                if let Some(expression) = &r#let.expression {
+                    self.analyze_expression(expression);
+                    self.state.uninitialized.remove(&name);
+                } else {
+                    self.state.uninitialized.insert(name);
+                }
+            }
+            Statement::Assignment(assignment) => {
+                self.analyze_expression(&assignment.right);
+
+                match &assignment.left {
+                    // Only a plain assignment to the whole variable initializes it,
+                    // compound assignments and element writes read it first.
+                    Expression::Identifier(identifier, None) if assignment.operator.name == "=" => {
+                        self.state.uninitialized.remove(&identifier.name);
+                    }
+                    left => self.analyze_expression(left),
+                }
+            }
+            Statement::Expression(expression) => self.analyze_expression(expression),
+            Statement::Print(print) => {
+                for expression in &print.expressions {
+                    self.analyze_expression(expression);
+                }
+            }
+            // The deferred block runs when the enclosing block is left, but the variables it
+            // reads must already be assigned where it is written.
+            Statement::Defer(defer) => {
+                let state = self.state.clone();
+                self.analyze_block(&defer.block);
+                self.state = state;
+            }
+            Statement::Return(r#return) => {
+                if let Some(expression) = &r#return.expression {
+                    self.analyze_expression(expression);
+                }
+
+                self.state = State::unreachable();
+            }
+            Statement::Break(r#break) => {
+                if let Some(expression) = &r#break.expression {
+                    self.analyze_expression(expression);
+                }
+
+                let state = std::mem::replace(&mut self.state, State::unreachable());
+
+                if let Some(exits) = self.get_loop_exits(&r#break.label) {
+                    exits.breaks.push(state);
+                }
+            }
+            Statement::Continue(r#continue) => {
+                let state = std::mem::replace(&mut self.state, State::unreachable());
+
+                if let Some(exits) = self.get_loop_exits(&r#continue.label) {
+                    exits.continues.push(state);
+                }
+            }
+            Statement::If(r#if) => self.analyze_if(r#if),
+            Statement::While(r#while) => {
+                self.analyze_expression(&r#while.expression);
+
+                // The body may never be executed.
+                let before = self.state.clone();
+                let exits = self.analyze_loop_body(&r#while.block, &r#while.label);
+
+                self.state = exits.breaks.iter().fold(before, |acc, s| acc.join(s));
+            }
+            Statement::For(r#for) => {
+                self.analyze_expression(&r#for.expression);
+
+                let before = self.state.clone();
+                let exits = self.analyze_loop_body(&r#for.block, &r#for.label);
+
+                self.state = exits.breaks.iter().fold(before, |acc, s| acc.join(s));
+            }
+            Statement::Loop(r#loop) => self.analyze_loop(r#loop),
+            Statement::DoWhile(do_while) => {
+                // The body is always executed at least once.
+                let exits = self.analyze_loop_body(&do_while.block, &do_while.label);
+
+                self.state = exits
+                    .continues
+                    .iter()
+                    .fold(self.state.clone(), |acc, s| acc.join(s));
+
+                self.analyze_expression(&do_while.expression);
+
+                self.state = exits
+                    .breaks
+                    .iter()
+                    .fold(self.state.clone(), |acc, s| acc.join(s));
+            }
+        }
+    }
+
+    fn analyze_if(&mut self, r#if: &If) {
+        self.analyze_expression(&r#if.expression);
+
+        let before = self.state.clone();
+        self.analyze_block(&r#if.block);
+        let then_state = std::mem::replace(&mut self.state, before);
+
+        if let Some(r#else) = &r#if.r#else {
+            self.analyze_block(&r#else.block);
+        }
+
+        self.state = then_state.join(&self.state);
+    }
+
+    /// Analyzes a `loop`, which is only left through its `break` statements.
+    fn analyze_loop(&mut self, r#loop: &Loop) {
+        let exits = self.analyze_loop_body(&r#loop.block, &r#loop.label);
+
+        self.state = exits
+            .breaks
+            .iter()
+            .fold(State::unreachable(), |acc, s| acc.join(s));
+    }
+
+    fn analyze_loop_body(&mut self, block: &Block, label: &Option<Identifier>) -> LoopExits {
+        self.loops.push(LoopExits {
+            label: label.as_ref().map(|label| label.name.clone()),
+            ..LoopExits::default()
+        });
+        self.analyze_block(block);
+        self.loops.pop().unwrap()
+    }
+
+    /// Gets the exits of the loop left by a `break` or a `continue`, which is the innermost
+    /// one unless a label is given.
+    fn get_loop_exits(&mut self, label: &Option<Identifier>) -> Option<&mut LoopExits> {
+        match label {
+            Some(label) => self
+                .loops
+                .iter_mut()
+                .rev()
+                .find(|exits| exits.label.as_ref() == Some(&label.name)),
+            None => self.loops.last_mut(),
+        }
+    }
+
+    fn analyze_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier(identifier, meta) => {
+                let name = &identifier.name;
+
+                if self.state.reachable
+                    && self.state.uninitialized.contains(name)
+                    && self.reported.insert(name.clone())
+                {
+                    self.diagnosis.push(SemanticError::VariableNotInitialized {
+                        name: identifier.name.clone(),
+                        position: identifier.get_position(),
+                    });
+                }
+
+                self.analyze_meta(meta);
+            }
+            Expression::Literal(_) => {}
+            Expression::Unary(unary) => self.analyze_expression(&unary.expression),
+            Expression::Alloc(alloc) => self.analyze_expression(&alloc.count),
+            Expression::Binary(binary) => {
+                self.analyze_expression(&binary.left);
+                self.analyze_expression(&binary.right);
+            }
+            Expression::Parenthesized(parenthesized, meta) => {
+                self.analyze_expression(&parenthesized.expression);
+                self.analyze_meta(meta);
+            }
+            Expression::Range(range) => {
+                self.analyze_expression(&range.left);
+                self.analyze_expression(&range.right);
+
+                if let Some(step) = &range.step {
+                    self.analyze_expression(step);
+                }
+            }
+            Expression::Array(array, meta) => {
+                for expression in &array.expressions {
+                    self.analyze_expression(expression);
+                }
+
+                self.analyze_meta(meta);
+            }
+            Expression::Interpolation(interpolation) => {
+                for (expression, _) in interpolation.get_values() {
+                    self.analyze_expression(expression);
+                }
+            }
+            Expression::Tuple(tuple, meta) => {
+                for expression in &tuple.expressions {
+                    self.analyze_expression(expression);
+                }
+
+                self.analyze_meta(meta);
+            }
+            // The captured values are read when the lambda is created, but its body runs apart
+            // from the flow of the function.
+            Expression::Lambda(lambda) => {
+                let state = self.state.clone();
+                let loops = std::mem::take(&mut self.loops);
+
+                self.analyze_block(&lambda.block);
+
+                self.state = state;
+                self.loops = loops;
+            }
+            Expression::Loop(r#loop) => self.analyze_loop(r#loop),
+            Expression::If(r#if) => self.analyze_if(r#if),
+            Expression::Block(block, _) => self.analyze_block(block),
+        }
+    }
+
+    fn analyze_meta(&mut self, meta: &Option<ExpressionMeta>) {
+        match meta {
+            Some(ExpressionMeta::Index(expression, meta, _)) => {
+                self.analyze_expression(expression);
+                self.analyze_meta(meta);
+            }
+            Some(ExpressionMeta::Field(_, meta, _)) | Some(ExpressionMeta::Try(meta, _)) => {
+                self.analyze_meta(meta)
+            }
+            Some(ExpressionMeta::Call(expressions, meta, _))
+            | Some(ExpressionMeta::Method(_, expressions, meta, _)) => {
+                for expression in expressions {
+                    self.analyze_expression(expression);
+                }
+
+                self.analyze_meta(meta);
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InitializationAnalyzer;
+    use crate::lang::semantic::semantic_error::SemanticError;
+    use crate::lang::syntax::{
+        parser::Parser, top_level_statements::top_level_statement::TopLevelStatement,
+    };
+
+    /// Analyzes the first function of the code, returning the variables reported as read
+    /// before being assigned.
+    fn analyze(code: &str) -> Vec<String> {
+        let ast = Parser::from_code(code).parse().unwrap();
+
+        match &ast.statements[0] {
+            TopLevelStatement::Function(function) => InitializationAnalyzer::analyze(function)
+                .diagnosis
+                .into_iter()
+                .map(|error| match error {
+                    SemanticError::VariableNotInitialized { name, .. } => name,
+                    error => panic!("unexpected error: {}", error),
+                })
+                .collect(),
+            _ => panic!("expected a function"),
+        }
+    }
+
+    #[test]
+    fn test_read_before_assignment() {
+        assert_eq!(analyze("fun main() { let x: i32; println x; }"), vec!["x"]);
+        assert!(analyze("fun main() { let x: i32; x = 1; println x; }").is_empty());
+
+        // Writing an element reads the array first.
+        let code = "fun main() { let a: [i32; 2]; a[0] = 1; }";
+        assert_eq!(analyze(code), vec!["a"]);
+    }
+
+    #[test]
+    fn test_if_branches() {
+        let code = "fun main() { let x: i32; if true { x = 1; } else { x = 2; } println x; }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let x: i32; if true { x = 1; } println x; }";
+        assert_eq!(analyze(code), vec!["x"]);
+
+        // A branch that returns does not reach the read.
+        let code = "fun main() { let x: i32; if true { x = 1; } else { return; } println x; }";
+        assert!(analyze(code).is_empty());
+    }
+
+    #[test]
+    fn test_loops() {
+        // The body of a `while` may never be executed.
+        let code = "fun main() { let x: i32; while false { x = 1; } println x; }";
+        assert_eq!(analyze(code), vec!["x"]);
+
+        // A `loop` is only left through its `break` statements.
+        let code = "fun main() { let x: i32; loop { x = 1; break; } println x; }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let x: i32; loop { if true { break; } x = 1; } println x; }";
+        assert_eq!(analyze(code), vec!["x"]);
+    }
+}
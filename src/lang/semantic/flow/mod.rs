@@ -0,0 +1,3 @@
+pub mod initialization_analyzer;
+pub mod none_check_analyzer;
+pub mod usage_analyzer;
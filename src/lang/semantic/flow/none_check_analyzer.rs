@@ -0,0 +1,621 @@
+use crate::lang::lexer::token_kind::TokenKind;
+use crate::lang::position::{Position, Positioned};
+use crate::lang::syntax::expressions::expression::{Expression, ExpressionMeta};
+use crate::lang::syntax::expressions::literal::Literal;
+use crate::lang::syntax::shared::{block::Block, identifier::Identifier};
+use crate::lang::syntax::statements::{r#if::If, r#loop::Loop, statement::Statement};
+use crate::lang::syntax::top_level_statements::function::Function;
+
+use std::collections::HashSet;
+
+/// The variables that are known not to be none at a given point of the
+/// function.
+#[derive(Clone, Debug, PartialEq)]
+struct State {
+    /// Variables checked against none, or assigned a value, in all the paths
+    /// that reach this point.
+    checked: HashSet<String>,
+
+    /// Whether this point can be reached at all. It becomes `false` after a
+    /// `return`, `break` or `continue` statement.
+    reachable: bool,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            checked: HashSet::new(),
+            reachable: true,
+        }
+    }
+
+    fn unreachable() -> Self {
+        Self {
+            checked: HashSet::new(),
+            reachable: false,
+        }
+    }
+
+    /// Merges two paths of the control flow graph. A variable is only
+    /// considered checked if it was checked in every reachable path.
+    fn join(&self, other: &State) -> State {
+        if !self.reachable {
+            return other.clone();
+        }
+
+        if !other.reachable {
+            return self.clone();
+        }
+
+        Self {
+            checked: self.checked.intersection(&other.checked).cloned().collect(),
+            reachable: true,
+        }
+    }
+
+    /// The state once the given variables are known not to be none.
+    fn with(&self, names: &HashSet<String>) -> State {
+        let mut state = self.clone();
+        state.checked.extend(names.iter().cloned());
+        state
+    }
+}
+
+/// The states that leave a loop through `break` and `continue` statements.
+#[derive(Default)]
+struct LoopExits {
+    label: Option<String>,
+    breaks: Vec<State>,
+    continues: Vec<State>,
+}
+
+/// The variables known not to be none when a condition is true and when it
+/// is false.
+#[derive(Default)]
+struct Facts {
+    when_true: HashSet<String>,
+    when_false: HashSet<String>,
+}
+
+/// Analyzer responsible for finding, in all the paths of a function, where
+/// an optional is read after being checked against none, so that its value
+/// can be used there.
+pub struct NoneCheckAnalyzer {
+    /// The positions where variables are read while known not to be none.
+    pub(crate) checked: Vec<Position>,
+
+    state: State,
+    loops: Vec<LoopExits>,
+
+    /// Whether the reads are recorded, which they are not while the state at
+    /// the start of a loop is still being computed.
+    recording: bool,
+}
+
+impl NoneCheckAnalyzer {
+    /// Analyzes the body of the provided function.
+    ///
+    /// # Arguments
+    ///
+    /// * `function` - A reference to the function to be analyzed.
+    ///
+    /// # Returns
+    ///
+    /// A `NoneCheckAnalyzer` instance containing the analysis results.
+    pub fn analyze(function: &Function) -> Self {
+        let mut analyzer = Self {
+            checked: vec![],
+            state: State::new(),
+            loops: vec![],
+            recording: true,
+        };
+
+        analyzer.analyze_block(&function.block);
+        analyzer
+    }
+
+    fn analyze_block(&mut self, block: &Block) {
+        for statement in &block.statements {
+            self.analyze_statement(statement);
+        }
+
+        if let Some(expression) = &block.expression {
+            self.analyze_expression(expression);
+        }
+    }
+
+    fn analyze_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block(block) => self.analyze_block(block),
+            Statement::Let(r#let) => {
+                if let Some(expression) = &r#let.expression {
+                    self.analyze_expression(expression);
+                }
+
+                let is_some = r#let
+                    .expression
+                    .as_ref()
+                    .is_some_and(|expression| self.is_some_value(expression));
+
+                if is_some && r#let.elements.is_empty() {
+                    self.state.checked.insert(r#let.identifier.name.clone());
+                } else {
+                    self.state.checked.remove(&r#let.identifier.name);
+                }
+
+                for element in &r#let.elements {
+                    self.state.checked.remove(&element.name);
+                }
+            }
+            Statement::Assignment(assignment) => {
+                self.analyze_expression(&assignment.right);
+
+                match &assignment.left {
+                    // Compound assignments and element writes keep the variable checked.
+                    Expression::Identifier(identifier, None) if assignment.operator.name == "=" => {
+                        if self.is_some_value(&assignment.right) {
+                            self.state.checked.insert(identifier.name.clone());
+                        } else {
+                            self.state.checked.remove(&identifier.name);
+                        }
+                    }
+                    left => self.analyze_expression(left),
+                }
+            }
+            Statement::Expression(expression) => self.analyze_expression(expression),
+            Statement::Print(print) => {
+                for expression in &print.expressions {
+                    self.analyze_expression(expression);
+                }
+            }
+            // The deferred block runs when the enclosing block is left, after the variables may
+            // have been assigned none, so it starts without any of them checked.
+            Statement::Defer(defer) => {
+                let state = std::mem::replace(&mut self.state, State::new());
+                self.analyze_block(&defer.block);
+                self.state = state;
+            }
+            Statement::Return(r#return) => {
+                if let Some(expression) = &r#return.expression {
+                    self.analyze_expression(expression);
+                }
+
+                self.state = State::unreachable();
+            }
+            Statement::Break(r#break) => {
+                if let Some(expression) = &r#break.expression {
+                    self.analyze_expression(expression);
+                }
+
+                let state = std::mem::replace(&mut self.state, State::unreachable());
+
+                if let Some(exits) = self.get_loop_exits(&r#break.label) {
+                    exits.breaks.push(state);
+                }
+            }
+            Statement::Continue(r#continue) => {
+                let state = std::mem::replace(&mut self.state, State::unreachable());
+
+                if let Some(exits) = self.get_loop_exits(&r#continue.label) {
+                    exits.continues.push(state);
+                }
+            }
+            Statement::If(r#if) => self.analyze_if(r#if),
+            Statement::While(r#while) => self.analyze_loop(|analyzer| {
+                let facts = analyzer.analyze_condition(&r#while.expression);
+                let state = analyzer.state.clone();
+
+                analyzer.state = state.with(&facts.when_true);
+                let exits = analyzer.analyze_loop_body(&r#while.block, &r#while.label);
+
+                let back = exits
+                    .continues
+                    .iter()
+                    .fold(analyzer.state.clone(), |acc, s| acc.join(s));
+                let after = exits
+                    .breaks
+                    .iter()
+                    .fold(state.with(&facts.when_false), |acc, s| acc.join(s));
+
+                (back, after)
+            }),
+            Statement::For(r#for) => {
+                self.analyze_expression(&r#for.expression);
+
+                self.analyze_loop(|analyzer| {
+                    // The body may never be executed.
+                    let state = analyzer.state.clone();
+
+                    analyzer.state.checked.remove(&r#for.identifier.name);
+                    let exits = analyzer.analyze_loop_body(&r#for.block, &r#for.label);
+
+                    let back = exits
+                        .continues
+                        .iter()
+                        .fold(analyzer.state.clone(), |acc, s| acc.join(s));
+                    let after = exits
+                        .breaks
+                        .iter()
+                        .fold(state.join(&back), |acc, s| acc.join(s));
+
+                    (back, after)
+                })
+            }
+            Statement::Loop(r#loop) => {
+                self.analyze_loop(|analyzer| analyzer.analyze_loop_iteration(r#loop))
+            }
+            Statement::DoWhile(do_while) => self.analyze_loop(|analyzer| {
+                // The body is always executed at least once.
+                let exits = analyzer.analyze_loop_body(&do_while.block, &do_while.label);
+
+                analyzer.state = exits
+                    .continues
+                    .iter()
+                    .fold(analyzer.state.clone(), |acc, s| acc.join(s));
+
+                let facts = analyzer.analyze_condition(&do_while.expression);
+
+                let back = analyzer.state.with(&facts.when_true);
+                let after = exits
+                    .breaks
+                    .iter()
+                    .fold(analyzer.state.with(&facts.when_false), |acc, s| acc.join(s));
+
+                (back, after)
+            }),
+        }
+    }
+
+    fn analyze_if(&mut self, r#if: &If) {
+        // The value bound by `if let` is never none, so only a condition checks. The optional
+        // bound by it is never read as its value.
+        let facts = match (&r#if.binding, &r#if.expression) {
+            (Some(_), Expression::Identifier(_, None)) => Facts::default(),
+            (Some(_), expression) => {
+                self.analyze_expression(expression);
+                Facts::default()
+            }
+            (None, expression) => self.analyze_condition(expression),
+        };
+
+        let before = self.state.clone();
+
+        self.state = before.with(&facts.when_true);
+        self.analyze_block(&r#if.block);
+        let then_state = std::mem::replace(&mut self.state, before.with(&facts.when_false));
+
+        if let Some(r#else) = &r#if.r#else {
+            self.analyze_block(&r#else.block);
+        }
+
+        self.state = then_state.join(&self.state);
+    }
+
+    /// Analyzes a loop, given the analysis of one of its iterations, which returns the state
+    /// that goes back to the start of the loop and the state after it. Since a variable checked
+    /// before the loop may be assigned in its body, the iterations are analyzed again until the
+    /// state at the start of the loop no longer changes.
+    fn analyze_loop(&mut self, iteration: impl Fn(&mut Self) -> (State, State)) {
+        let before = self.state.clone();
+        let recording = self.recording;
+
+        self.recording = false;
+
+        let mut start = before.clone();
+
+        loop {
+            self.state = start.clone();
+
+            let (back, _) = iteration(self);
+            let next = before.join(&back);
+
+            if next == start {
+                break;
+            }
+
+            start = next;
+        }
+
+        self.recording = recording;
+        self.state = start;
+
+        let (_, after) = iteration(self);
+        self.state = after;
+    }
+
+    /// Analyzes an iteration of a `loop`, which is only left through its `break` statements.
+    fn analyze_loop_iteration(&mut self, r#loop: &Loop) -> (State, State) {
+        let exits = self.analyze_loop_body(&r#loop.block, &r#loop.label);
+
+        let back = exits
+            .continues
+            .iter()
+            .fold(self.state.clone(), |acc, s| acc.join(s));
+        let after = exits
+            .breaks
+            .iter()
+            .fold(State::unreachable(), |acc, s| acc.join(s));
+
+        (back, after)
+    }
+
+    fn analyze_loop_body(&mut self, block: &Block, label: &Option<Identifier>) -> LoopExits {
+        self.loops.push(LoopExits {
+            label: label.as_ref().map(|label| label.name.clone()),
+            ..LoopExits::default()
+        });
+        self.analyze_block(block);
+        self.loops.pop().unwrap()
+    }
+
+    /// Gets the exits of the loop left by a `break` or a `continue`, which is the innermost
+    /// one unless a label is given.
+    fn get_loop_exits(&mut self, label: &Option<Identifier>) -> Option<&mut LoopExits> {
+        match label {
+            Some(label) => self
+                .loops
+                .iter_mut()
+                .rev()
+                .find(|exits| exits.label.as_ref() == Some(&label.name)),
+            None => self.loops.last_mut(),
+        }
+    }
+
+    /// Analyzes an expression used as a condition.
+    ///
+    /// # Returns
+    ///
+    /// The variables that the condition checks against none.
+    fn analyze_condition(&mut self, expression: &Expression) -> Facts {
+        match expression {
+            Expression::Binary(binary) => match binary.operator.token.kind {
+                TokenKind::AmpersandAmpersand => {
+                    let left = self.analyze_condition(&binary.left);
+
+                    // The right operand is only evaluated when the left one is true.
+                    let state = self.state.clone();
+                    self.state = state.with(&left.when_true);
+                    let right = self.analyze_condition(&binary.right);
+                    self.state = state;
+
+                    Facts {
+                        when_true: left.when_true.union(&right.when_true).cloned().collect(),
+                        when_false: left
+                            .when_false
+                            .intersection(&right.when_false)
+                            .cloned()
+                            .collect(),
+                    }
+                }
+                TokenKind::PipePipe => {
+                    let left = self.analyze_condition(&binary.left);
+
+                    // The right operand is only evaluated when the left one is false.
+                    let state = self.state.clone();
+                    self.state = state.with(&left.when_false);
+                    let right = self.analyze_condition(&binary.right);
+                    self.state = state;
+
+                    Facts {
+                        when_true: left
+                            .when_true
+                            .intersection(&right.when_true)
+                            .cloned()
+                            .collect(),
+                        when_false: left.when_false.union(&right.when_false).cloned().collect(),
+                    }
+                }
+                TokenKind::EqualsEquals | TokenKind::ExclamationEquals => {
+                    let name = match (binary.left.as_ref(), binary.right.as_ref()) {
+                        (
+                            Expression::Identifier(identifier, None),
+                            Expression::Literal(Literal::None(_)),
+                        )
+                        | (
+                            Expression::Literal(Literal::None(_)),
+                            Expression::Identifier(identifier, None),
+                        ) => identifier.name.clone(),
+                        _ => {
+                            self.analyze_expression(&binary.left);
+                            self.analyze_expression(&binary.right);
+
+                            return Facts::default();
+                        }
+                    };
+
+                    // The optional itself is compared, so it is never read as its value here.
+                    let names = HashSet::from([name]);
+
+                    match binary.operator.token.kind {
+                        TokenKind::EqualsEquals => Facts {
+                            when_true: HashSet::new(),
+                            when_false: names,
+                        },
+                        _ => Facts {
+                            when_true: names,
+                            when_false: HashSet::new(),
+                        },
+                    }
+                }
+                _ => {
+                    self.analyze_expression(expression);
+                    Facts::default()
+                }
+            },
+            Expression::Unary(unary) if unary.operator.token.kind == TokenKind::Exclamation => {
+                let facts = self.analyze_condition(&unary.expression);
+
+                Facts {
+                    when_true: facts.when_false,
+                    when_false: facts.when_true,
+                }
+            }
+            Expression::Parenthesized(parenthesized, None) => {
+                self.analyze_condition(&parenthesized.expression)
+            }
+            _ => {
+                self.analyze_expression(expression);
+                Facts::default()
+            }
+        }
+    }
+
+    /// Whether the value of the expression is known not to be none. Only `none`, the variables
+    /// that were not checked and the results of calls, indexes and fields may be none.
+    fn is_some_value(&self, expression: &Expression) -> bool {
+        match expression {
+            Expression::Literal(Literal::None(_)) => false,
+            Expression::Identifier(identifier, None) => {
+                self.state.checked.contains(&identifier.name)
+            }
+            Expression::Identifier(_, Some(_)) | Expression::Parenthesized(_, Some(_)) => false,
+            Expression::Parenthesized(parenthesized, None) => {
+                self.is_some_value(&parenthesized.expression)
+            }
+            Expression::Tuple(_, Some(_)) | Expression::Array(_, Some(_)) => false,
+            _ => true,
+        }
+    }
+
+    fn analyze_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier(identifier, meta) => {
+                if self.recording && self.state.checked.contains(&identifier.name) {
+                    self.checked.push(identifier.get_position());
+                }
+
+                self.analyze_meta(meta);
+            }
+            Expression::Literal(_) => {}
+            Expression::Unary(unary) => self.analyze_expression(&unary.expression),
+            Expression::Alloc(alloc) => self.analyze_expression(&alloc.count),
+            Expression::Binary(binary) => {
+                self.analyze_expression(&binary.left);
+                self.analyze_expression(&binary.right);
+            }
+            Expression::Parenthesized(parenthesized, meta) => {
+                self.analyze_expression(&parenthesized.expression);
+                self.analyze_meta(meta);
+            }
+            Expression::Range(range) => {
+                self.analyze_expression(&range.left);
+                self.analyze_expression(&range.right);
+
+                if let Some(step) = &range.step {
+                    self.analyze_expression(step);
+                }
+            }
+            Expression::Array(array, meta) => {
+                for expression in &array.expressions {
+                    self.analyze_expression(expression);
+                }
+
+                self.analyze_meta(meta);
+            }
+            Expression::Interpolation(interpolation) => {
+                for (expression, _) in interpolation.get_values() {
+                    self.analyze_expression(expression);
+                }
+            }
+            Expression::Tuple(tuple, meta) => {
+                for expression in &tuple.expressions {
+                    self.analyze_expression(expression);
+                }
+
+                self.analyze_meta(meta);
+            }
+            // A lambda captures the values as they are when it is created, and cannot assign
+            // them, so they stay checked in its body.
+            Expression::Lambda(lambda) => {
+                let state = self.state.clone();
+                let loops = std::mem::take(&mut self.loops);
+
+                self.analyze_block(&lambda.block);
+
+                self.state = state;
+                self.loops = loops;
+            }
+            Expression::Loop(r#loop) => {
+                self.analyze_loop(|analyzer| analyzer.analyze_loop_iteration(r#loop))
+            }
+            Expression::If(r#if) => self.analyze_if(r#if),
+            Expression::Block(block, _) => self.analyze_block(block),
+        }
+    }
+
+    fn analyze_meta(&mut self, meta: &Option<ExpressionMeta>) {
+        match meta {
+            Some(ExpressionMeta::Index(expression, meta, _)) => {
+                self.analyze_expression(expression);
+                self.analyze_meta(meta);
+            }
+            Some(ExpressionMeta::Field(_, meta, _)) | Some(ExpressionMeta::Try(meta, _)) => {
+                self.analyze_meta(meta)
+            }
+            Some(ExpressionMeta::Call(expressions, meta, _))
+            | Some(ExpressionMeta::Method(_, expressions, meta, _)) => {
+                for expression in expressions {
+                    self.analyze_expression(expression);
+                }
+
+                self.analyze_meta(meta);
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NoneCheckAnalyzer;
+    use crate::lang::syntax::{
+        parser::Parser, top_level_statements::top_level_statement::TopLevelStatement,
+    };
+
+    /// Analyzes the first function of the code, returning how many reads of optionals are known
+    /// not to be none.
+    fn analyze(code: &str) -> usize {
+        let ast = Parser::from_code(code).parse().unwrap();
+
+        match &ast.statements[0] {
+            TopLevelStatement::Function(function) => {
+                NoneCheckAnalyzer::analyze(function).checked.len()
+            }
+            _ => panic!("expected a function"),
+        }
+    }
+
+    #[test]
+    fn test_conditions() {
+        let code = "fun main() { let x: ?i32 = none; if x != none { println x; } }";
+        assert_eq!(analyze(code), 1);
+
+        let code = "fun main() { let x: ?i32 = none; if x == none { println x; } }";
+        assert_eq!(analyze(code), 0);
+
+        // The else branch runs when the condition is false.
+        let code = "fun main() { let x: ?i32 = none; if x == none { } else { println x; } }";
+        assert_eq!(analyze(code), 1);
+
+        // The right operand of `&&` is only evaluated when the left one is true.
+        let code = "fun main() { let x: ?i32 = none; if x != none && x > 0 { } }";
+        assert_eq!(analyze(code), 1);
+
+        let code = "fun main() { let x: ?i32 = none; if !(x == none) { println x; } }";
+        assert_eq!(analyze(code), 1);
+    }
+
+    #[test]
+    fn test_assignments() {
+        let code = "fun main() { let mut x: ?i32 = 1; println x; x = none; println x; }";
+        assert_eq!(analyze(code), 1);
+
+        // A branch that returns does not reach the read.
+        let code = "fun main() { let x: ?i32 = none; if x == none { return; } println x; }";
+        assert_eq!(analyze(code), 1);
+
+        // The body of a loop may assign none before the next iteration.
+        let code = "fun main() { let mut x: ?i32 = 1; while true { println x; x = none; } }";
+        assert_eq!(analyze(code), 0);
+    }
+}
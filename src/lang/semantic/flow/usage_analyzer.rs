@@ -0,0 +1,164 @@
+use crate::lang::position::Positioned;
+use crate::lang::semantic::analyzer::Scopes;
+use crate::lang::semantic::scope::Scope;
+use crate::lang::semantic::semantic_warning::SemanticWarning;
+use crate::lang::semantic::symbol::Symbol;
+
+use std::collections::{HashSet, VecDeque};
+use std::{cell::RefCell, rc::Rc};
+
+/// Analyzer responsible for reporting symbols that are declared but never used.
+///
+/// Symbols whose names start with `_` are never reported.
+pub struct UsageAnalyzer {
+    /// A collection of semantic warnings found during analysis.
+    pub(crate) warnings: Vec<SemanticWarning>,
+}
+
+impl UsageAnalyzer {
+    /// Analyzes the usage of the symbols declared in every scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `global_scope` - A reference-counted reference to the scope in which the functions are declared.
+    /// * `scopes` - A reference to the set of scopes created during the analysis.
+    ///
+    /// # Returns
+    ///
+    /// A `UsageAnalyzer` instance containing the analysis results.
+    pub fn analyze(global_scope: Rc<RefCell<Scope>>, scopes: &Scopes) -> Self {
+        let mut warnings: Vec<SemanticWarning> = vec![];
+
+        for scope in scopes.values() {
+            for (symbol, usage) in scope.borrow().get_usages() {
+                let name = symbol.get_name();
+
+                if name.starts_with('_') || usage.read {
+                    continue;
+                }
+
+                let position = usage.position;
+
+                match symbol {
+                    Symbol::Variable { .. } if usage.written => {
+                        warnings.push(SemanticWarning::VariableNeverRead { name, position })
+                    }
+                    Symbol::Variable { .. } => {
+                        warnings.push(SemanticWarning::UnusedVariable { name, position })
+                    }
+                    // The value a method is called on cannot be left out.
+                    Symbol::Parameter { .. } if name == "self" => {}
+                    Symbol::Parameter { .. } => {
+                        warnings.push(SemanticWarning::UnusedParameter { name, position })
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        warnings.extend(Self::analyze_functions(global_scope));
+
+        warnings.sort_by_key(|warning| {
+            let position = warning.get_position();
+            (position.line, position.column)
+        });
+
+        Self { warnings }
+    }
+
+    /// Reports the functions that cannot be reached from `main`, following the
+    /// functions in which each one of them is read. The functions known to C by their
+    /// names, declared with `extern` or `export`, are reached from C.
+    fn analyze_functions(global_scope: Rc<RefCell<Scope>>) -> Vec<SemanticWarning> {
+        let usages = global_scope.borrow().get_usages();
+
+        if !usages.iter().any(|(symbol, _)| symbol.get_name() == "main") {
+            return vec![];
+        }
+
+        let mut reachable: HashSet<String> = HashSet::from([String::from("main")]);
+        let mut queue: VecDeque<String> = VecDeque::from([String::from("main")]);
+
+        for (symbol, _) in &usages {
+            if let Symbol::Function {
+                name,
+                external: true,
+                ..
+            } = symbol
+            {
+                if reachable.insert(name.clone()) {
+                    queue.push_back(name.clone());
+                }
+            }
+        }
+
+        while let Some(caller) = queue.pop_front() {
+            for (symbol, usage) in &usages {
+                let name = symbol.get_name();
+
+                if usage.readers.contains(&caller) && reachable.insert(name.clone()) {
+                    queue.push_back(name);
+                }
+            }
+        }
+
+        usages
+            .into_iter()
+            .filter(|(symbol, _)| matches!(symbol, Symbol::Function { .. }))
+            .filter(|(symbol, _)| {
+                let name = symbol.get_name();
+                !name.starts_with('_') && !reachable.contains(&name)
+            })
+            .map(|(symbol, usage)| SemanticWarning::UnusedFunction {
+                name: symbol.get_name(),
+                position: usage.position,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{
+        semantic_warning::SemanticWarning, test_helpers::analyze_warnings,
+    };
+
+    #[test]
+    fn test_unused_symbols() {
+        let code = "fun main() { let x = 1; let mut y = 2; y = 3; let _z = 4; }";
+        let warnings = analyze_warnings(code);
+
+        assert!(matches!(
+            &warnings[..],
+            [
+                SemanticWarning::UnusedVariable { name: x, .. },
+                SemanticWarning::VariableNeverRead { name: y, .. },
+            ] if x == "x" && y == "y"
+        ));
+
+        let code = "fun add(a: i32, b: i32) -> i32 { return a; } fun main() { println add(1, 2); }";
+        let warnings = analyze_warnings(code);
+
+        assert!(matches!(
+            &warnings[..],
+            [SemanticWarning::UnusedParameter { name, .. }] if name == "b"
+        ));
+    }
+
+    #[test]
+    fn test_unreachable_functions() {
+        // A function only called by an unused function is not reached from `main`.
+        let code = "fun a() { b(); } fun b() { } fun c() { } fun main() { c(); }";
+        let warnings = analyze_warnings(code);
+
+        let names: Vec<&str> = warnings
+            .iter()
+            .map(|warning| match warning {
+                SemanticWarning::UnusedFunction { name, .. } => name.as_str(),
+                _ => "",
+            })
+            .collect();
+
+        assert_eq!(names, vec!["a", "b"]);
+    }
+}
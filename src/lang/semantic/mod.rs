@@ -1,9 +1,14 @@
 pub mod analyzer;
+pub mod builtin_function;
 pub mod expressions;
+pub mod flow;
 pub mod scope;
 pub mod semantic_error;
 pub mod semantic_type;
+pub mod semantic_warning;
 pub mod shared;
 pub mod statements;
 pub mod symbol;
+#[cfg(test)]
+pub mod test_helpers;
 pub mod top_level_statements;
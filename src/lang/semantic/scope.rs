@@ -1,4 +1,10 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use crate::lang::position::Position;
 
 use super::{semantic_type::SemanticType, symbol::Symbol};
 
@@ -6,14 +12,74 @@ use super::{semantic_type::SemanticType, symbol::Symbol};
 pub struct Func {
     pub name: String,
     pub return_type: SemanticType,
+
+    /// Whether the function is a lambda expression, which is named after the function where it
+    /// is written.
+    pub is_lambda: bool,
+}
+
+/// Describes how a symbol declared in the source code is used.
+#[derive(Clone, Debug)]
+pub struct Usage {
+    /// Where the symbol was declared.
+    pub position: Position,
+
+    /// Whether the value of the symbol is read anywhere.
+    pub read: bool,
+
+    /// Whether the symbol is assigned after its declaration.
+    pub written: bool,
+
+    /// The names of the functions in which the symbol is read.
+    pub readers: HashSet<String>,
+}
+
+impl Usage {
+    fn new(position: Position) -> Self {
+        Self {
+            position,
+            read: false,
+            written: false,
+            readers: HashSet::new(),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct Scope {
     is_loop: bool,
+
+    /// The label of the loop whose body is this scope, as in `'outer: for ...`.
+    label: Option<String>,
+
+    /// The types of the values given to the `break` statements that leave the loop, along with
+    /// their positions, which are only kept by the body of a `loop` expression.
+    break_types: Option<Vec<(SemanticType, Position)>>,
+
+    /// Whether this scope is the block of a `defer`, which runs when the enclosing block is
+    /// left, so that it cannot leave the loops or the function around it.
+    deferred: bool,
     parent: Option<Rc<RefCell<Scope>>>,
     function: Option<Func>,
     symbol_table: HashMap<String, Symbol>,
+    usages: HashMap<String, Usage>,
+    captures: Vec<String>,
+
+    /// The positions where optionals are read after being checked against none, which are
+    /// only kept by the global scope.
+    checked: HashSet<Position>,
+
+    /// The names of the methods that change the value they are called on, which are only kept
+    /// by the global scope.
+    mutating: HashSet<String>,
+
+    /// Whether each parameter of the functions is declared as `mut`, by the names of the
+    /// functions, which are only kept by the global scope.
+    mutable_params: HashMap<String, Vec<bool>>,
+
+    /// The types that implement each trait, by the names of the types and of the traits, which
+    /// are only kept by the global scope.
+    implementations: HashSet<(String, String)>,
 }
 
 impl Scope {
@@ -21,8 +87,17 @@ impl Scope {
         Self {
             parent: None,
             is_loop: false,
+            label: None,
+            break_types: None,
+            deferred: false,
             function: None,
             symbol_table: HashMap::new(),
+            usages: HashMap::new(),
+            captures: Vec::new(),
+            checked: HashSet::new(),
+            mutating: HashSet::new(),
+            mutable_params: HashMap::new(),
+            implementations: HashSet::new(),
         }
     }
 
@@ -30,19 +105,174 @@ impl Scope {
         Self {
             parent: Some(parent),
             is_loop,
+            label: None,
+            break_types: None,
+            deferred: false,
             function,
             symbol_table: HashMap::new(),
+            usages: HashMap::new(),
+            captures: Vec::new(),
+            checked: HashSet::new(),
+            mutating: HashSet::new(),
+            mutable_params: HashMap::new(),
+            implementations: HashSet::new(),
+        }
+    }
+
+    /// Creates the scope of the body of a loop, which may be labeled so that `break` and
+    /// `continue` can refer to it from inner loops.
+    pub fn new_loop(parent: Rc<RefCell<Scope>>, label: Option<String>) -> Self {
+        Self {
+            label,
+            ..Self::new(parent, true, None)
+        }
+    }
+
+    /// Creates the scope of the body of a `loop` expression, whose value is given by the `break`
+    /// statements that leave it.
+    pub fn new_loop_expression(parent: Rc<RefCell<Scope>>, label: Option<String>) -> Self {
+        Self {
+            break_types: Some(Vec::new()),
+            ..Self::new_loop(parent, label)
+        }
+    }
+
+    /// Creates the scope of the block of a `defer`.
+    pub fn new_deferred(parent: Rc<RefCell<Scope>>) -> Self {
+        Self {
+            deferred: true,
+            ..Self::new(parent, false, None)
         }
     }
 
+    /// Records the type of the value given to a `break` that leaves the loop with the given
+    /// label, or the innermost loop.
+    ///
+    /// # Returns
+    ///
+    /// Whether the loop left is a `loop` expression, as no other loop has a value.
+    pub fn add_break_type(
+        &mut self,
+        label: Option<&str>,
+        r#type: SemanticType,
+        position: Position,
+    ) -> bool {
+        let is_target = match label {
+            Some(label) => self.label.as_deref() == Some(label),
+            None => self.is_loop,
+        };
+
+        if is_target {
+            return match &mut self.break_types {
+                Some(break_types) => {
+                    break_types.push((r#type, position));
+                    true
+                }
+                None => false,
+            };
+        }
+
+        match &self.parent {
+            Some(parent) if self.function.is_none() && !self.deferred => {
+                parent.borrow_mut().add_break_type(label, r#type, position)
+            }
+            _ => false,
+        }
+    }
+
+    /// Gets the types of the values given to the `break` statements that leave the `loop`
+    /// expression whose body is this scope.
+    pub fn get_break_types(&self) -> Vec<(SemanticType, Position)> {
+        self.break_types.clone().unwrap_or_default()
+    }
+
+    /// Whether this scope is inside of a loop of the current function with the given label.
+    pub fn is_labeled_loop(&self, label: &str) -> bool {
+        self.label.as_deref() == Some(label)
+            || (self.function.is_none()
+                && !self.deferred
+                && matches!(&self.parent, Some(parent) if parent.borrow().is_labeled_loop(label)))
+    }
+
+    /// Whether this scope is inside of a loop of the current function.
     pub fn is_loop(&self) -> bool {
-        self.is_loop || matches!(&self.parent, Some(parent) if parent.borrow().is_loop())
+        self.is_loop
+            || (self.function.is_none()
+                && !self.deferred
+                && matches!(&self.parent, Some(parent) if parent.borrow().is_loop()))
+    }
+
+    /// Whether this scope is inside of the block of a `defer` of the current function.
+    pub fn is_deferred(&self) -> bool {
+        self.deferred
+            || (self.function.is_none()
+                && matches!(&self.parent, Some(parent) if parent.borrow().is_deferred()))
+    }
+
+    pub fn get_parent(&self) -> Option<Rc<RefCell<Scope>>> {
+        self.parent.clone()
+    }
+
+    /// Whether this scope is the body of a loop, without looking at its parents.
+    pub fn is_loop_scope(&self) -> bool {
+        self.is_loop
+    }
+
+    /// Whether this scope is the body of a function, without looking at its parents.
+    pub fn is_function_scope(&self) -> bool {
+        self.function.is_some()
     }
 
     pub fn insert(&mut self, symbol: Symbol) {
         self.symbol_table.insert(symbol.get_name().clone(), symbol);
     }
 
+    /// Inserts a symbol declared in the source code, so that its usage is tracked.
+    pub fn declare(&mut self, symbol: Symbol, position: Position) {
+        self.usages.insert(symbol.get_name(), Usage::new(position));
+        self.insert(symbol);
+    }
+
+    /// Marks the symbol with the given name as read in the current function.
+    pub fn mark_read(&mut self, name: &str) {
+        let function_name = self.get_function_name();
+
+        self.update_usage(name, |usage| {
+            usage.read = true;
+
+            if let Some(function_name) = function_name {
+                usage.readers.insert(function_name);
+            }
+        });
+    }
+
+    /// Marks the symbol with the given name as assigned.
+    pub fn mark_written(&mut self, name: &str) {
+        self.update_usage(name, |usage| usage.written = true);
+    }
+
+    fn update_usage(&mut self, name: &str, update: impl FnOnce(&mut Usage)) {
+        if self.symbol_table.contains_key(name) {
+            if let Some(usage) = self.usages.get_mut(name) {
+                update(usage);
+            }
+        } else if let Some(parent) = &self.parent {
+            parent.borrow_mut().update_usage(name, update);
+        }
+    }
+
+    /// Gets the symbols declared in this scope along with their usage.
+    pub fn get_usages(&self) -> Vec<(Symbol, Usage)> {
+        self.usages
+            .iter()
+            .filter_map(|(name, usage)| {
+                self.symbol_table
+                    .get(name)
+                    .map(|symbol| (symbol.clone(), usage.clone()))
+            })
+            .collect()
+    }
+
     pub fn get(&self, name: &str) -> Option<Symbol> {
         self.symbol_table.get(name).cloned().or_else(|| {
             self.parent
@@ -51,6 +281,161 @@ impl Scope {
         })
     }
 
+    /// Checks whether the name is already taken by a symbol. The builtin functions do not take
+    /// their names, so that a declaration can replace them.
+    pub fn is_declared(&self, name: &str) -> bool {
+        !matches!(
+            self.get(name),
+            None | Some(Symbol::Function { builtin: true, .. })
+        )
+    }
+
+    /// Gets the variable or parameter with the given name when it is declared outside of the
+    /// current function, which happens when a lambda expression reads a local of the function
+    /// where it is written.
+    pub fn get_captured(&self, name: &str) -> Option<Symbol> {
+        if self.symbol_table.contains_key(name) {
+            return None;
+        }
+
+        let parent = self.parent.as_ref()?;
+
+        if self.function.is_none() {
+            return parent.borrow().get_captured(name);
+        }
+
+        match parent.borrow().get(name) {
+            Some(symbol @ (Symbol::Variable { .. } | Symbol::Parameter { .. })) => Some(symbol),
+            _ => None,
+        }
+    }
+
+    /// Registers the symbol with the given name as captured by every lambda expression between
+    /// this scope and the one where the symbol is declared.
+    pub fn mark_captured(&mut self, name: &str) {
+        if self.symbol_table.contains_key(name) {
+            return;
+        }
+
+        if self.function.is_some() && !self.captures.iter().any(|capture| capture == name) {
+            self.captures.push(String::from(name));
+        }
+
+        if let Some(parent) = &self.parent {
+            parent.borrow_mut().mark_captured(name);
+        }
+    }
+
+    /// Gets the names of the symbols captured by the lambda expression of this scope, in the
+    /// order they are first read.
+    pub fn get_captures(&self) -> Vec<String> {
+        self.captures.clone()
+    }
+
+    /// Marks the optional read at the given position as known not to be none, so its value is
+    /// read instead.
+    pub fn mark_checked(&mut self, position: Position) {
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().mark_checked(position),
+            None => {
+                self.checked.insert(position);
+            }
+        }
+    }
+
+    /// Whether the optional read at the given position is known not to be none.
+    pub fn is_checked(&self, position: Position) -> bool {
+        match &self.parent {
+            Some(parent) => parent.borrow().is_checked(position),
+            None => self.checked.contains(&position),
+        }
+    }
+
+    /// Marks the method with the given name as changing the value it is called on, which is
+    /// declared as `mut self`.
+    pub fn mark_mutating(&mut self, name: &str) {
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().mark_mutating(name),
+            None => {
+                self.mutating.insert(String::from(name));
+            }
+        }
+    }
+
+    /// Whether the method with the given name changes the value it is called on.
+    pub fn is_mutating(&self, name: &str) -> bool {
+        match &self.parent {
+            Some(parent) => parent.borrow().is_mutating(name),
+            None => self.mutating.contains(name),
+        }
+    }
+
+    /// Marks the given type as implementing the trait with the given name.
+    pub fn mark_implemented(&mut self, r#type: &SemanticType, r#trait: &str) {
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().mark_implemented(r#type, r#trait),
+            None => {
+                self.implementations
+                    .insert((r#type.to_string(), String::from(r#trait)));
+            }
+        }
+    }
+
+    /// Whether the type can be used for a type parameter with the given bound, which is either
+    /// `numeric` or a trait implemented by the type.
+    pub fn satisfies(&self, r#type: &SemanticType, bound: &str) -> bool {
+        match self.get(bound) {
+            Some(Symbol::Trait { .. }) => match r#type {
+                SemanticType::Any => true,
+                SemanticType::TypeParameter(_, Some(own)) => own == bound,
+                _ => self.is_implemented(r#type, bound),
+            },
+            _ => r#type.satisfies(bound),
+        }
+    }
+
+    fn is_implemented(&self, r#type: &SemanticType, r#trait: &str) -> bool {
+        match &self.parent {
+            Some(parent) => parent.borrow().is_implemented(r#type, r#trait),
+            None => self
+                .implementations
+                .contains(&(r#type.to_string(), String::from(r#trait))),
+        }
+    }
+
+    /// Whether the current function is a lambda expression.
+    pub fn is_lambda(&self) -> bool {
+        match &self.function {
+            Some(function) => function.is_lambda,
+            None => self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.borrow().is_lambda()),
+        }
+    }
+
+    /// Marks which parameters of the function with the given name are declared as `mut`.
+    pub fn mark_mutable_params(&mut self, name: &str, mutable_params: Vec<bool>) {
+        match &self.parent {
+            Some(parent) => parent
+                .borrow_mut()
+                .mark_mutable_params(name, mutable_params),
+            None => {
+                self.mutable_params
+                    .insert(String::from(name), mutable_params);
+            }
+        }
+    }
+
+    /// Whether each parameter of the function with the given name is declared as `mut`, which
+    /// is empty for the functions that are not declared in the code, such as the builtins.
+    pub fn get_mutable_params(&self, name: &str) -> Vec<bool> {
+        match &self.parent {
+            Some(parent) => parent.borrow().get_mutable_params(name),
+            None => self.mutable_params.get(name).cloned().unwrap_or_default(),
+        }
+    }
+
     pub fn get_function_name(&self) -> Option<String> {
         self.function.clone().map(|v| v.name.clone()).or_else(|| {
             self.parent
@@ -70,3 +455,67 @@ impl Scope {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{Func, Scope, Usage};
+    use crate::lang::{
+        position::Position,
+        semantic::{semantic_type::SemanticType, symbol::Symbol},
+    };
+
+    fn declare(scope: &Rc<RefCell<Scope>>, name: &str, position: Position) {
+        let symbol = Symbol::Variable {
+            name: String::from(name),
+            symbol_type: SemanticType::I32,
+            mutable: true,
+        };
+
+        scope.borrow_mut().declare(symbol, position);
+    }
+
+    fn get_usage(scope: &Rc<RefCell<Scope>>, name: &str) -> Usage {
+        scope
+            .borrow()
+            .get_usages()
+            .into_iter()
+            .find(|(symbol, _)| symbol.get_name() == name)
+            .map(|(_, usage)| usage)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_usages() {
+        let global = Rc::new(RefCell::new(Scope::global()));
+        let function = Func {
+            name: String::from("main"),
+            return_type: SemanticType::Void,
+            is_lambda: false,
+        };
+        let outer = Rc::new(RefCell::new(Scope::new(global, false, Some(function))));
+        let inner = Rc::new(RefCell::new(Scope::new(Rc::clone(&outer), false, None)));
+
+        declare(&outer, "x", Position::new(1, 1));
+        declare(&outer, "y", Position::new(1, 2));
+
+        // The usages reach the scope where the symbol is declared.
+        inner.borrow_mut().mark_read("x");
+        inner.borrow_mut().mark_written("y");
+
+        let x = get_usage(&outer, "x");
+        let y = get_usage(&outer, "y");
+
+        assert!(x.read && !x.written);
+        assert!(x.readers.contains("main"));
+        assert!(!y.read && y.written);
+
+        // A symbol declared again in an inner scope shadows the outer one.
+        declare(&inner, "x", Position::new(1, 3));
+        inner.borrow_mut().mark_written("x");
+
+        assert!(get_usage(&inner, "x").written);
+        assert!(!get_usage(&outer, "x").written);
+    }
+}
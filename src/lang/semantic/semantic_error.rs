@@ -105,6 +105,22 @@ pub enum SemanticError {
         position: Position,
     },
 
+    // 'outer: for i in 0..10 { } break 'inner;
+    LabelNotFound {
+        name: String,
+        position: Position,
+    },
+
+    // while true { break 1; }
+    InvalidBreakValue {
+        position: Position,
+    },
+
+    // let x = loop { break; };
+    ExpressionWithoutValue {
+        position: Position,
+    },
+
     InvalidReturn {
         position: Position,
     },
@@ -124,6 +140,169 @@ pub enum SemanticError {
         found: SemanticType,
         position: Position,
     },
+
+    // let x: i32; println x;
+    VariableNotInitialized {
+        name: String,
+        position: Position,
+    },
+
+    // for x in true { ... }
+    ExpressionNotIterable {
+        found: SemanticType,
+        position: Position,
+    },
+
+    // 0..10 step 0.5
+    InvalidRangeStep {
+        found: SemanticType,
+        position: Position,
+    },
+
+    // 10..0 step 0
+    ZeroRangeStep {
+        position: Position,
+    },
+
+    // let f = len;
+    BuiltinNotCalled {
+        name: String,
+        position: Position,
+    },
+
+    // fun id<T>(x: T) -> T { ... } let f = id;
+    GenericNotCalled {
+        name: String,
+        position: Position,
+    },
+
+    // let a: u64 = 1; max(a, -1)
+    OverloadNotFound {
+        name: String,
+        found: Vec<SemanticType>,
+        position: Position,
+    },
+
+    // let v: vec<i32, i32>;
+    InvalidNumberOfTypeArguments {
+        expected: usize,
+        found: usize,
+        position: Position,
+    },
+
+    // fun max<T: numeric>(a: T, b: T) -> T { ... } max(true, false)
+    UnsatisfiedTypeBound {
+        found: SemanticType,
+        bound: String,
+        position: Position,
+    },
+
+    // fun zero<T>() -> T { ... }
+    TypeParameterNotInferable {
+        name: String,
+        position: Position,
+    },
+
+    // (1, true).2
+    FieldNotFound {
+        found: SemanticType,
+        field: String,
+        position: Position,
+    },
+
+    // 1.abs()
+    MethodNotFound {
+        found: SemanticType,
+        name: String,
+        position: Position,
+    },
+
+    // impl Comparable for i32 { } without fun compare(self, other: Self) -> i32
+    MissingTraitMethod {
+        name: String,
+        r#trait: String,
+        position: Position,
+    },
+
+    // impl Comparable for i32 { fun compare(self, other: bool) -> i32 { ... } }
+    InvalidTraitMethod {
+        name: String,
+        r#trait: String,
+        position: Position,
+    },
+
+    // impl Comparable for i32 { fun double(self) -> i32 { ... } }
+    MethodNotInTrait {
+        name: String,
+        r#trait: String,
+        position: Position,
+    },
+
+    // fun max(a: Comparable, b: Comparable) { ... }
+    TraitUsedAsType {
+        name: String,
+        position: Position,
+    },
+
+    // let (a, b) = (1, 2, 3);
+    InvalidDestructuring {
+        expected: usize,
+        found: SemanticType,
+        position: Position,
+    },
+
+    // let x = none;
+    TypeNotInferable {
+        found: SemanticType,
+        position: Position,
+    },
+
+    // if let x = 10 { ... }
+    ExpectedOptional {
+        found: SemanticType,
+        position: Position,
+    },
+
+    // fun f() -> i32 { return parse("1")?; }
+    InvalidTry {
+        found: SemanticType,
+        expected: SemanticType,
+        position: Position,
+    },
+
+    // while next()? { ... } or while loop { ... } { ... }
+    HoistedNotAllowed {
+        position: Position,
+    },
+
+    // "{1.5:x}" or println [1, 2];
+    InvalidFormat {
+        format: String,
+        found: SemanticType,
+        position: Position,
+    },
+
+    // extern fun f(values: vec<i32>); or export fun f() -> string { ... }
+    InvalidForeignType {
+        found: SemanticType,
+        position: Position,
+    },
+
+    // export fun f<T>(x: T) { ... }
+    GenericExport {
+        position: Position,
+    },
+
+    // alloc<vec<i32>>(10)
+    OwnedAllocation {
+        found: SemanticType,
+        position: Position,
+    },
+
+    // defer { return; } or defer close(file)?;
+    InvalidDeferredExit {
+        position: Position,
+    },
 }
 
 impl Display for SemanticError {
@@ -295,6 +474,27 @@ impl Display for SemanticError {
                     position.line, position.column
                 )
             }
+            Self::LabelNotFound { name, position } => {
+                write!(
+                    f,
+                    "No loop labeled '{}' encloses the statement at Line {} and Column {}",
+                    name, position.line, position.column
+                )
+            }
+            Self::InvalidBreakValue { position } => {
+                write!(
+                    f,
+                    "Only a 'break' that leaves a 'loop' expression can be given a value at Line {} and Column {}",
+                    position.line, position.column
+                )
+            }
+            Self::ExpressionWithoutValue { position } => {
+                write!(
+                    f,
+                    "Expression has no value to be used at Line {} and Column {}",
+                    position.line, position.column
+                )
+            }
             Self::InvalidReturn { position } => {
                 write!(
                     f,
@@ -336,6 +536,273 @@ impl Display for SemanticError {
                     position.line, position.column
                 )
             }
+            Self::VariableNotInitialized { name, position } => {
+                write!(
+                    f,
+                    "Variable '{}' used before being initialized at Line {} and Column {}",
+                    name, position.line, position.column
+                )
+            }
+            Self::ExpressionNotIterable { found, position } => {
+                write!(
+                    f,
+                    "Expression of type '{}' is not iterable at Line {} and Column {}",
+                    found.to_string(),
+                    position.line,
+                    position.column
+                )
+            }
+            Self::InvalidNumberOfTypeArguments {
+                expected,
+                found,
+                position,
+            } => {
+                write!(
+                    f,
+                    "Invalid number of type arguments. Expected {} but found {} at Line {} and Column {}",
+                    expected, found, position.line, position.column
+                )
+            }
+            Self::BuiltinNotCalled { name, position } => {
+                write!(
+                    f,
+                    "Builtin function '{}' must be called at Line {} and Column {}",
+                    name, position.line, position.column
+                )
+            }
+            Self::GenericNotCalled { name, position } => {
+                write!(
+                    f,
+                    "Generic function '{}' must be called at Line {} and Column {}",
+                    name, position.line, position.column
+                )
+            }
+            Self::OverloadNotFound {
+                name,
+                found,
+                position,
+            } => {
+                let found: Vec<String> = found.iter().map(|r#type| r#type.to_string()).collect();
+
+                write!(
+                    f,
+                    "No overload of '{}' takes '({})' at Line {} and Column {}",
+                    name,
+                    found.join(", "),
+                    position.line,
+                    position.column
+                )
+            }
+            Self::InvalidRangeStep { found, position } => {
+                write!(
+                    f,
+                    "Invalid range step of type '{}', expected an integer at Line {} and Column {}",
+                    found.to_string(),
+                    position.line,
+                    position.column
+                )
+            }
+            Self::ZeroRangeStep { position } => {
+                write!(
+                    f,
+                    "Range step cannot be zero at Line {} and Column {}",
+                    position.line, position.column
+                )
+            }
+            Self::UnsatisfiedTypeBound {
+                found,
+                bound,
+                position,
+            } => {
+                write!(
+                    f,
+                    "Type '{}' does not satisfy the bound '{}' at Line {} and Column {}",
+                    found.to_string(),
+                    bound,
+                    position.line,
+                    position.column
+                )
+            }
+            Self::TypeParameterNotInferable { name, position } => {
+                write!(
+                    f,
+                    "Type parameter '{}' must be used by a parameter so it can be inferred at Line {} and Column {}",
+                    name, position.line, position.column
+                )
+            }
+            Self::FieldNotFound {
+                found,
+                field,
+                position,
+            } => {
+                write!(
+                    f,
+                    "Type '{}' has no field '{}' at Line {} and Column {}",
+                    found.to_string(),
+                    field,
+                    position.line,
+                    position.column
+                )
+            }
+            Self::MethodNotFound {
+                found,
+                name,
+                position,
+            } => {
+                write!(
+                    f,
+                    "Type '{}' has no method '{}' at Line {} and Column {}",
+                    found.to_string(),
+                    name,
+                    position.line,
+                    position.column
+                )
+            }
+            Self::MissingTraitMethod {
+                name,
+                r#trait,
+                position,
+            } => {
+                write!(
+                    f,
+                    "Method '{}' of trait '{}' is not implemented at Line {} and Column {}",
+                    name, r#trait, position.line, position.column
+                )
+            }
+            Self::InvalidTraitMethod {
+                name,
+                r#trait,
+                position,
+            } => {
+                write!(
+                    f,
+                    "Method '{}' does not match its signature in trait '{}' at Line {} and Column {}",
+                    name, r#trait, position.line, position.column
+                )
+            }
+            Self::MethodNotInTrait {
+                name,
+                r#trait,
+                position,
+            } => {
+                write!(
+                    f,
+                    "Method '{}' is not a member of trait '{}' at Line {} and Column {}",
+                    name, r#trait, position.line, position.column
+                )
+            }
+            Self::TraitUsedAsType { name, position } => {
+                write!(
+                    f,
+                    "Trait '{}' cannot be used as a type, only as the bound of a type parameter at Line {} and Column {}",
+                    name, position.line, position.column
+                )
+            }
+            Self::InvalidDestructuring {
+                expected,
+                found,
+                position,
+            } => {
+                write!(
+                    f,
+                    "Cannot destructure type '{}' into {} variables at Line {} and Column {}",
+                    found.to_string(),
+                    expected,
+                    position.line,
+                    position.column
+                )
+            }
+            Self::TypeNotInferable { found, position } => {
+                write!(
+                    f,
+                    "The type '{}' cannot be inferred, the type of the variable must be given at Line {} and Column {}",
+                    found.to_string(),
+                    position.line,
+                    position.column
+                )
+            }
+            Self::ExpectedOptional { found, position } => {
+                write!(
+                    f,
+                    "Expected an optional or a result but found '{}' at Line {} and Column {}",
+                    found.to_string(),
+                    position.line,
+                    position.column
+                )
+            }
+            Self::InvalidTry {
+                found,
+                expected,
+                position,
+            } => {
+                write!(
+                    f,
+                    "Cannot propagate '{}' from a function that returns '{}' at Line {} and Column {}",
+                    found.to_string(),
+                    expected.to_string(),
+                    position.line,
+                    position.column
+                )
+            }
+            Self::HoistedNotAllowed { position } => {
+                write!(
+                    f,
+                    "The '?' operator, 'loop' expressions and 'if' or block expressions with statements cannot be used in a loop condition or after '&&' and '||' at Line {} and Column {}",
+                    position.line, position.column
+                )
+            }
+            Self::InvalidFormat {
+                format,
+                found,
+                position,
+            } => {
+                let format = if format.is_empty() {
+                    String::from("{}")
+                } else {
+                    format!("{{:{}}}", format)
+                };
+
+                write!(
+                    f,
+                    "Format '{}' cannot be used with '{}' at Line {} and Column {}",
+                    format,
+                    found.to_string(),
+                    position.line,
+                    position.column
+                )
+            }
+            Self::InvalidForeignType { found, position } => {
+                write!(
+                    f,
+                    "Type '{}' cannot be passed to or from C at Line {} and Column {}",
+                    found.to_string(),
+                    position.line,
+                    position.column
+                )
+            }
+            Self::GenericExport { position } => {
+                write!(
+                    f,
+                    "Generic functions cannot be exported at Line {} and Column {}",
+                    position.line, position.column
+                )
+            }
+            Self::OwnedAllocation { found, position } => {
+                write!(
+                    f,
+                    "Type '{}' owns memory and cannot be allocated at Line {} and Column {}",
+                    found.to_string(),
+                    position.line,
+                    position.column
+                )
+            }
+            Self::InvalidDeferredExit { position } => {
+                write!(
+                    f,
+                    "A deferred block cannot leave its function at Line {} and Column {}",
+                    position.line, position.column
+                )
+            }
         }
     }
 }
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use crate::lang::syntax::shared::syntax_type::SyntaxType;
 
 #[derive(Clone, Debug, PartialEq, Hash)]
@@ -17,15 +19,51 @@ pub enum SemanticType {
     Char,
     String,
     Range,
+    /// A handle to an open file, given by `openFile`.
+    File,
+    /// A pointer to a NUL-terminated string owned by C, as `const char*`.
+    CString,
     Any,
     Ref(Box<SemanticType>),
     Array(Box<SemanticType>, usize),
+    Slice(Box<SemanticType>),
+    Vec(Box<SemanticType>),
     Function(Vec<SemanticType>, Box<SemanticType>),
+    Tuple(Vec<SemanticType>),
+    /// A value that may be missing. The type of `none` is an optional of `Any`.
+    Optional(Box<SemanticType>),
+    /// Either a value or an error, with the types of both. `ok(..)` and `err(..)` only know one
+    /// of them, and leave the other as `Any`.
+    Result(Box<SemanticType>, Box<SemanticType>),
+    /// A type parameter of a generic function, with the name and the optional bound.
+    TypeParameter(String, Option<String>),
+    /// An array whose size is a size parameter of a generic function, with the element type and
+    /// the name of the parameter.
+    GenericArray(Box<SemanticType>, String),
+}
+
+/// The arguments of the type parameters of a generic function, by their names. Parameters used
+/// as the size of an array receive a size instead of a type.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TypeArguments {
+    pub types: BTreeMap<String, SemanticType>,
+    pub sizes: BTreeMap<String, usize>,
+}
+
+impl TypeArguments {
+    pub fn new() -> Self {
+        Self::default()
+    }
 }
 
 impl SemanticType {
     pub fn number_type_precedence(v: Vec<SemanticType>) -> SemanticType {
-        if v.contains(&SemanticType::Any) {
+        if let Some(parameter) = v
+            .iter()
+            .find(|r#type| matches!(r#type, Self::TypeParameter(..)))
+        {
+            parameter.clone()
+        } else if v.contains(&SemanticType::Any) {
             SemanticType::Any
         } else if v.contains(&SemanticType::F64) {
             SemanticType::F64
@@ -50,6 +88,70 @@ impl SemanticType {
         }
     }
 
+    /// The type of a value that may come from any of the given types, as the value of an `if`
+    /// or of a `loop` does. It is the first type that is not the type of `none`, `ok(..)` or
+    /// `err(..)`, and it becomes optional when `none` is among them. `Any`, given by branches
+    /// that are never left through their end, agrees with every type.
+    pub fn unify(types: &[SemanticType]) -> SemanticType {
+        let known: Vec<&SemanticType> = types
+            .iter()
+            .filter(|r#type| **r#type != Self::Any)
+            .collect();
+
+        let r#type = match known
+            .iter()
+            .find(|r#type| !r#type.is_partial())
+            .or(known.first())
+        {
+            Some(r#type) => (*r#type).clone(),
+            None if types.is_empty() => Self::Void,
+            None => Self::Any,
+        };
+
+        let none = Self::Optional(Box::new(Self::Any));
+
+        if !matches!(r#type, Self::Optional(_)) && known.contains(&&none) {
+            Self::Optional(Box::new(r#type))
+        } else {
+            r#type
+        }
+    }
+
+    /// Whether a value of the `found` type can be used where this type is expected. Numbers
+    /// are implicitly converted between each other, arrays coerce to slices and vectors of the
+    /// same element type and vectors coerce to slices. A value is wrapped when an optional of
+    /// its type is expected, and `none` is accepted by every optional. `ok(..)` and `err(..)`
+    /// are accepted by the results whose value or error accepts their argument. A string is
+    /// passed to C as a `cstring`.
+    pub fn accepts(&self, found: &SemanticType) -> bool {
+        match (self, found) {
+            (Self::CString, Self::String) => true,
+            (Self::Result(_, expected), Self::Result(value, found))
+                if value.as_ref() == &Self::Any =>
+            {
+                expected.accepts(found)
+            }
+            (Self::Result(expected, _), Self::Result(found, error))
+                if error.as_ref() == &Self::Any =>
+            {
+                expected.accepts(found)
+            }
+            (Self::Optional(expected), Self::Optional(found)) => {
+                expected == found || found.as_ref() == &Self::Any
+            }
+            (Self::Optional(expected), found) => expected.accepts(found),
+            (Self::Slice(expected), Self::Array(found, _))
+            | (Self::Slice(expected), Self::GenericArray(found, _))
+            | (Self::Slice(expected), Self::Vec(found))
+            | (Self::Vec(expected), Self::Array(found, _)) => {
+                expected == found || found.as_ref() == &Self::Any
+            }
+            // The sizes are only known in the instances of the generic function.
+            (Self::GenericArray(expected, _), Self::GenericArray(found, _)) => expected == found,
+            _ => self == found || (self.is_number() && found.is_number()),
+        }
+    }
+
     pub fn is_integer(&self) -> bool {
         matches!(
             &self,
@@ -70,6 +172,10 @@ impl SemanticType {
     }
 
     pub fn is_number(&self) -> bool {
+        if let Self::TypeParameter(_, Some(bound)) = self {
+            return bound == "numeric";
+        }
+
         matches!(
             &self,
             Self::Any
@@ -86,6 +192,221 @@ impl SemanticType {
         )
     }
 
+    /// Whether the type can be used for a type parameter with the given bound. Bounds that are
+    /// not known were already reported with the function, so they are always satisfied.
+    pub fn satisfies(&self, bound: &str) -> bool {
+        match bound {
+            "numeric" => self.is_number(),
+            _ => true,
+        }
+    }
+
+    /// Whether values of the type can be passed to and from C functions as they are, as the
+    /// parameters and the returns of `extern` and `export` functions are.
+    pub fn is_c_compatible(&self) -> bool {
+        match self {
+            Self::Void | Self::Bool | Self::Char | Self::String | Self::CString => true,
+            Self::Any | Self::TypeParameter(..) => false,
+            r#type => r#type.is_number(),
+        }
+    }
+
+    /// Whether the values of the type own memory that is released when they are dropped, as
+    /// vectors, function values, which hold the values captured by lambdas, files, and the
+    /// tuples, optionals and results that hold them do.
+    pub fn is_owned(&self) -> bool {
+        match self {
+            Self::Vec(_) | Self::Function(..) | Self::File => true,
+            Self::Optional(r#type) => r#type.is_owned(),
+            Self::Result(value, error) => value.is_owned() || error.is_owned(),
+            Self::Tuple(types) => types.iter().any(|r#type| r#type.is_owned()),
+            _ => false,
+        }
+    }
+
+    /// Whether the type only partially describes its values, as the types of `none`, `ok(..)`
+    /// and `err(..)` do, so that it cannot be given to a variable.
+    pub fn is_partial(&self) -> bool {
+        match self {
+            Self::Optional(r#type) => r#type.as_ref() == &Self::Any,
+            Self::Result(value, error) => {
+                value.as_ref() == &Self::Any || error.as_ref() == &Self::Any
+            }
+            _ => false,
+        }
+    }
+
+    /// The name under which the method with the given name is declared for this type, such as
+    /// `vec<i32>.push`, which cannot be written as an identifier.
+    pub fn get_method_name(&self, name: &str) -> String {
+        format!("{}.{}", self.to_string(), name)
+    }
+
+    /// Whether the type mentions a type parameter, as the types of generic functions do.
+    pub fn is_generic(&self) -> bool {
+        match self {
+            Self::TypeParameter(..) | Self::GenericArray(..) => true,
+            Self::Ref(r#type)
+            | Self::Array(r#type, _)
+            | Self::Slice(r#type)
+            | Self::Vec(r#type)
+            | Self::Optional(r#type) => r#type.is_generic(),
+            Self::Function(params, return_type) => {
+                params.iter().any(|param| param.is_generic()) || return_type.is_generic()
+            }
+            Self::Tuple(types) => types.iter().any(|r#type| r#type.is_generic()),
+            Self::Result(value, error) => value.is_generic() || error.is_generic(),
+            _ => false,
+        }
+    }
+
+    /// Collects the type parameters mentioned by the type, with their bounds, in the order
+    /// they first appear.
+    pub fn get_type_parameters(&self, parameters: &mut Vec<(String, Option<String>)>) {
+        match self {
+            Self::TypeParameter(name, bound)
+                if !parameters.iter().any(|(parameter, _)| parameter == name) =>
+            {
+                parameters.push((name.clone(), bound.clone()));
+            }
+            Self::GenericArray(r#type, name) => {
+                r#type.get_type_parameters(parameters);
+
+                if !parameters.iter().any(|(parameter, _)| parameter == name) {
+                    parameters.push((name.clone(), None));
+                }
+            }
+            Self::Ref(r#type)
+            | Self::Array(r#type, _)
+            | Self::Slice(r#type)
+            | Self::Vec(r#type)
+            | Self::Optional(r#type) => r#type.get_type_parameters(parameters),
+            Self::Function(params, return_type) => {
+                for param in params {
+                    param.get_type_parameters(parameters);
+                }
+
+                return_type.get_type_parameters(parameters);
+            }
+            Self::Tuple(types) => {
+                for r#type in types {
+                    r#type.get_type_parameters(parameters);
+                }
+            }
+            Self::Result(value, error) => {
+                value.get_type_parameters(parameters);
+                error.get_type_parameters(parameters);
+            }
+            _ => {}
+        }
+    }
+
+    /// Infers the type arguments of a generic type from the `found` type used in its place.
+    /// When a type parameter is used by several numbers, the one with the highest precedence
+    /// is kept, and a size parameter keeps the first size it is used with.
+    pub fn infer(&self, found: &SemanticType, type_arguments: &mut TypeArguments) {
+        match (self, found) {
+            (Self::TypeParameter(name, _), found) => match type_arguments.types.get(name) {
+                None => {
+                    type_arguments.types.insert(name.clone(), found.clone());
+                }
+                Some(inferred) if inferred.is_number() && found.is_number() => {
+                    let r#type =
+                        Self::number_type_precedence(vec![inferred.clone(), found.clone()]);
+                    type_arguments.types.insert(name.clone(), r#type);
+                }
+                _ => {}
+            },
+            (Self::GenericArray(expected, name), Self::Array(found, size)) => {
+                type_arguments.sizes.entry(name.clone()).or_insert(*size);
+                expected.infer(found, type_arguments);
+            }
+            (Self::Ref(expected), Self::Ref(found))
+            | (Self::Array(expected, _), Self::Array(found, _))
+            | (Self::Slice(expected), Self::Array(found, _))
+            | (Self::Slice(expected), Self::Slice(found))
+            | (Self::Slice(expected), Self::Vec(found))
+            | (Self::Vec(expected), Self::Array(found, _))
+            | (Self::Vec(expected), Self::Vec(found)) => expected.infer(found, type_arguments),
+            // Nothing is known about the type of a `none`.
+            (Self::Optional(_), Self::Optional(found)) if found.as_ref() == &Self::Any => {}
+            (Self::Optional(expected), Self::Optional(found)) => {
+                expected.infer(found, type_arguments)
+            }
+            (Self::Optional(expected), found) => expected.infer(found, type_arguments),
+            (
+                Self::Function(params, return_type),
+                Self::Function(found_params, found_return_type),
+            ) => {
+                for (param, found_param) in params.iter().zip(found_params) {
+                    param.infer(found_param, type_arguments);
+                }
+
+                return_type.infer(found_return_type, type_arguments);
+            }
+            (Self::Tuple(types), Self::Tuple(found_types)) => {
+                for (r#type, found_type) in types.iter().zip(found_types) {
+                    r#type.infer(found_type, type_arguments);
+                }
+            }
+            // `ok(..)` and `err(..)` say nothing about the other type.
+            (Self::Result(value, error), Self::Result(found_value, found_error)) => {
+                if found_value.as_ref() != &Self::Any {
+                    value.infer(found_value, type_arguments);
+                }
+
+                if found_error.as_ref() != &Self::Any {
+                    error.infer(found_error, type_arguments);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Replaces the type parameters mentioned by the type with the given type arguments.
+    pub fn substitute(&self, type_arguments: &TypeArguments) -> SemanticType {
+        match self {
+            Self::TypeParameter(name, _) => type_arguments
+                .types
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| self.clone()),
+            Self::GenericArray(r#type, name) => {
+                let r#type = Box::new(r#type.substitute(type_arguments));
+
+                match type_arguments.sizes.get(name) {
+                    Some(size) => Self::Array(r#type, *size),
+                    None => Self::GenericArray(r#type, name.clone()),
+                }
+            }
+            Self::Ref(r#type) => Self::Ref(Box::new(r#type.substitute(type_arguments))),
+            Self::Array(r#type, size) => {
+                Self::Array(Box::new(r#type.substitute(type_arguments)), *size)
+            }
+            Self::Slice(r#type) => Self::Slice(Box::new(r#type.substitute(type_arguments))),
+            Self::Vec(r#type) => Self::Vec(Box::new(r#type.substitute(type_arguments))),
+            Self::Optional(r#type) => Self::Optional(Box::new(r#type.substitute(type_arguments))),
+            Self::Result(value, error) => Self::Result(
+                Box::new(value.substitute(type_arguments)),
+                Box::new(error.substitute(type_arguments)),
+            ),
+            Self::Function(params, return_type) => Self::Function(
+                params
+                    .iter()
+                    .map(|param| param.substitute(type_arguments))
+                    .collect(),
+                Box::new(return_type.substitute(type_arguments)),
+            ),
+            Self::Tuple(types) => Self::Tuple(
+                types
+                    .iter()
+                    .map(|r#type| r#type.substitute(type_arguments))
+                    .collect(),
+            ),
+            _ => self.clone(),
+        }
+    }
+
     pub fn from_syntax(r#type: SyntaxType) -> Self {
         match r#type {
             SyntaxType::Simple { identifier } => Self::from(identifier.value),
@@ -93,9 +414,26 @@ impl SemanticType {
                 let size = size.value.parse::<usize>().unwrap();
                 Self::Array(Box::new(Self::from_syntax(r#type.as_ref().clone())), size)
             }
+            SyntaxType::Generic {
+                identifier,
+                arguments,
+            } => match (&identifier.value[..], &arguments[..]) {
+                ("vec", [r#type]) => Self::Vec(Box::new(Self::from_syntax(r#type.clone()))),
+                ("result", [value, error]) => Self::Result(
+                    Box::new(Self::from_syntax(value.clone())),
+                    Box::new(Self::from_syntax(error.clone())),
+                ),
+                _ => Self::Any,
+            },
+            SyntaxType::Slice { r#type, .. } => {
+                Self::Slice(Box::new(Self::from_syntax(r#type.as_ref().clone())))
+            }
             SyntaxType::Reference { inner_type, .. } => {
                 Self::Ref(Box::new(Self::from_syntax(inner_type.as_ref().clone())))
             }
+            SyntaxType::Optional { inner_type, .. } => {
+                Self::Optional(Box::new(Self::from_syntax(inner_type.as_ref().clone())))
+            }
             SyntaxType::Function { params, r#type, .. } => Self::Function(
                 params
                     .iter()
@@ -103,6 +441,12 @@ impl SemanticType {
                     .collect(),
                 Box::new(Self::from_syntax(r#type.as_ref().clone())),
             ),
+            SyntaxType::Tuple { types, .. } => Self::Tuple(
+                types
+                    .iter()
+                    .map(|r#type| Self::from_syntax(r#type.clone()))
+                    .collect(),
+            ),
         }
     }
 }
@@ -125,6 +469,8 @@ impl ToString for SemanticType {
             SemanticType::Char => "char".to_string(),
             SemanticType::String => "string".to_string(),
             SemanticType::Range => "range".to_string(),
+            SemanticType::File => "file".to_string(),
+            SemanticType::CString => "cstring".to_string(),
             SemanticType::Any => "any".to_string(),
             SemanticType::Ref(r#type) => {
                 let type_name = r#type.to_string();
@@ -133,6 +479,16 @@ impl ToString for SemanticType {
             SemanticType::Array(inner_type, size) => {
                 format!("[{}; {}]", inner_type.to_string(), size)
             }
+            SemanticType::Slice(inner_type) => format!("[{}]", inner_type.to_string()),
+            SemanticType::Vec(inner_type) => format!("vec<{}>", inner_type.to_string()),
+            SemanticType::Optional(inner_type) => format!("?{}", inner_type.to_string()),
+            SemanticType::Result(value, error) => {
+                format!("result<{}, {}>", value.to_string(), error.to_string())
+            }
+            SemanticType::TypeParameter(name, _) => name.clone(),
+            SemanticType::GenericArray(inner_type, name) => {
+                format!("[{}; {}]", inner_type.to_string(), name)
+            }
             SemanticType::Function(params, return_type) => {
                 format!(
                     "({}) -> {}",
@@ -144,6 +500,14 @@ impl ToString for SemanticType {
                     return_type.to_string()
                 )
             }
+            SemanticType::Tuple(types) => format!(
+                "({})",
+                types
+                    .iter()
+                    .map(|r#type| r#type.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
         }
     }
 }
@@ -166,6 +530,8 @@ impl From<String> for SemanticType {
             "char" => SemanticType::Char,
             "string" => SemanticType::String,
             "range" => SemanticType::Range,
+            "file" => SemanticType::File,
+            "cstring" => SemanticType::CString,
             "any" => SemanticType::Any,
             _ => SemanticType::Any,
         }
@@ -190,6 +556,8 @@ impl From<&str> for SemanticType {
             "char" => SemanticType::Char,
             "string" => SemanticType::String,
             "range" => SemanticType::Range,
+            "file" => SemanticType::File,
+            "cstring" => SemanticType::CString,
             "any" => SemanticType::Any,
             _ => SemanticType::Any,
         }
@@ -0,0 +1,68 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::lang::position::{Position, Positioned};
+
+#[derive(Debug)]
+pub enum SemanticWarning {
+    // let x = 2;
+    UnusedVariable {
+        name: String,
+        position: Position,
+    },
+
+    // let mut x = 2; x = 3;
+    VariableNeverRead {
+        name: String,
+        position: Position,
+    },
+
+    // fun f(x: i32) { }
+    UnusedParameter {
+        name: String,
+        position: Position,
+    },
+
+    // fun f() { }
+    UnusedFunction {
+        name: String,
+        position: Position,
+    },
+}
+
+impl Positioned for SemanticWarning {
+    fn get_position(&self) -> Position {
+        match self {
+            Self::UnusedVariable { position, .. }
+            | Self::VariableNeverRead { position, .. }
+            | Self::UnusedParameter { position, .. }
+            | Self::UnusedFunction { position, .. } => *position,
+        }
+    }
+}
+
+impl Display for SemanticWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnusedVariable { name, position } => write!(
+                f,
+                "Warning: Unused variable '{}' at Line {} and Column {}",
+                name, position.line, position.column
+            ),
+            Self::VariableNeverRead { name, position } => write!(
+                f,
+                "Warning: Variable '{}' is assigned but never read at Line {} and Column {}",
+                name, position.line, position.column
+            ),
+            Self::UnusedParameter { name, position } => write!(
+                f,
+                "Warning: Unused parameter '{}' at Line {} and Column {}",
+                name, position.line, position.column
+            ),
+            Self::UnusedFunction { name, position } => write!(
+                f,
+                "Warning: Function '{}' is never called from 'main' at Line {} and Column {}",
+                name, position.line, position.column
+            ),
+        }
+    }
+}
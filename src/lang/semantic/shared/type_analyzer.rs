@@ -1,7 +1,7 @@
 use crate::lang::lexer::token::Token;
 use crate::lang::lexer::token_kind::TokenKind;
 use crate::lang::semantic::{
-    scope::Scope, semantic_error::SemanticError, semantic_type::SemanticType,
+    scope::Scope, semantic_error::SemanticError, semantic_type::SemanticType, symbol::Symbol,
 };
 use crate::lang::syntax::shared::syntax_type::SyntaxType;
 
@@ -35,12 +35,40 @@ impl TypeAnalyzer {
             SyntaxType::Array { r#type, size, .. } => {
                 Self::analyze_array_type(r#type, size, Rc::clone(&scope))
             }
+            SyntaxType::Generic {
+                identifier,
+                arguments,
+            } => Self::analyze_generic_type(identifier, arguments, Rc::clone(&scope)),
+            SyntaxType::Slice { r#type, .. } => Self::analyze_slice_type(r#type, Rc::clone(&scope)),
             SyntaxType::Reference { inner_type, .. } => {
                 Self::analyze_reference_type(inner_type, Rc::clone(&scope))
             }
             SyntaxType::Function { params, r#type, .. } => {
                 Self::analyze_function_type(params, r#type, Rc::clone(&scope))
             }
+            SyntaxType::Tuple { types, .. } => {
+                let mut semantic_types: Vec<SemanticType> = vec![];
+                let mut diagnosis: Vec<SemanticError> = vec![];
+
+                for r#type in types {
+                    let analyzer = Self::analyze(r#type, Rc::clone(&scope));
+                    diagnosis.extend(analyzer.diagnosis);
+                    semantic_types.push(analyzer.result_type);
+                }
+
+                Self {
+                    diagnosis,
+                    result_type: SemanticType::Tuple(semantic_types),
+                }
+            }
+            SyntaxType::Optional { inner_type, .. } => {
+                let analyzer = Self::analyze(inner_type, Rc::clone(&scope));
+
+                Self {
+                    diagnosis: analyzer.diagnosis,
+                    result_type: SemanticType::Optional(Box::new(analyzer.result_type)),
+                }
+            }
         }
     }
 
@@ -84,12 +112,18 @@ impl TypeAnalyzer {
 
         let variable_type_name = token_identifier.value.clone();
 
-        if let Some(_) = scope.borrow().get(&variable_type_name) {
-            result_type = SemanticType::from(variable_type_name);
-        } else {
-            diagnosis.push(SemanticError::IdentifierNotFound {
+        let symbol = scope.borrow().get(&variable_type_name);
+
+        match symbol {
+            Some(Symbol::TypeParameter { symbol_type, .. }) => result_type = symbol_type,
+            Some(Symbol::Trait { name, .. }) => diagnosis.push(SemanticError::TraitUsedAsType {
+                name,
                 position: token_identifier.position,
-            });
+            }),
+            Some(_) => result_type = SemanticType::from(variable_type_name),
+            None => diagnosis.push(SemanticError::IdentifierNotFound {
+                position: token_identifier.position,
+            }),
         }
 
         Self {
@@ -130,6 +164,84 @@ impl TypeAnalyzer {
                 result_type =
                     SemanticType::Array(Box::new(SemanticType::from(analyzer.result_type)), number);
             }
+        } else {
+            // The size is a size parameter of a generic function.
+            let symbol = scope.borrow().get(&size.value);
+
+            match symbol {
+                Some(Symbol::SizeParameter {
+                    size: Some(number), ..
+                }) => result_type = SemanticType::Array(Box::new(analyzer.result_type), number),
+                Some(Symbol::SizeParameter { name, size: None }) => {
+                    result_type = SemanticType::GenericArray(Box::new(analyzer.result_type), name)
+                }
+                _ => diagnosis.push(SemanticError::IdentifierNotFound {
+                    position: size.position,
+                }),
+            }
+        }
+
+        Self {
+            result_type,
+            diagnosis,
+        }
+    }
+
+    /// Analyzes a type that receives type arguments, such as `vec<i32>`, within a given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - A reference to the token naming the type.
+    /// * `arguments` - The type arguments written between angle brackets.
+    /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
+    ///
+    /// # Returns
+    ///
+    /// A `TypeAnalyzer` instance containing the analysis results.
+    fn analyze_generic_type(
+        identifier: &Token,
+        arguments: &[SyntaxType],
+        scope: Rc<RefCell<Scope>>,
+    ) -> Self {
+        let mut result_type = SemanticType::Any;
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        let mut semantic_arguments: Vec<SemanticType> = vec![];
+
+        for argument in arguments {
+            let analyzer = Self::analyze(argument, Rc::clone(&scope));
+            diagnosis.extend(analyzer.diagnosis);
+            semantic_arguments.push(analyzer.result_type);
+        }
+
+        match &identifier.value[..] {
+            "vec" => {
+                if semantic_arguments.len() == 1 {
+                    result_type = SemanticType::Vec(Box::new(semantic_arguments.remove(0)));
+                } else {
+                    diagnosis.push(SemanticError::InvalidNumberOfTypeArguments {
+                        expected: 1,
+                        found: semantic_arguments.len(),
+                        position: identifier.position,
+                    });
+                }
+            }
+            "result" => {
+                if semantic_arguments.len() == 2 {
+                    let value = semantic_arguments.remove(0);
+                    let error = semantic_arguments.remove(0);
+                    result_type = SemanticType::Result(Box::new(value), Box::new(error));
+                } else {
+                    diagnosis.push(SemanticError::InvalidNumberOfTypeArguments {
+                        expected: 2,
+                        found: semantic_arguments.len(),
+                        position: identifier.position,
+                    });
+                }
+            }
+            _ => diagnosis.push(SemanticError::IdentifierNotFound {
+                position: identifier.position,
+            }),
         }
 
         Self {
@@ -138,6 +250,25 @@ impl TypeAnalyzer {
         }
     }
 
+    /// Analyzes a slice type based on the type of its elements within a given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `r#type` - A reference to the type contained within the slice.
+    /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
+    ///
+    /// # Returns
+    ///
+    /// A `TypeAnalyzer` instance containing the analysis results.
+    fn analyze_slice_type(r#type: &SyntaxType, scope: Rc<RefCell<Scope>>) -> Self {
+        let analyzer = Self::analyze(r#type, Rc::clone(&scope));
+
+        Self {
+            result_type: SemanticType::Slice(Box::new(analyzer.result_type)),
+            diagnosis: analyzer.diagnosis,
+        }
+    }
+
     /// Analyzes a reference type based on the inner type within a given scope.
     ///
     /// # Arguments
@@ -1,6 +1,9 @@
 use crate::lang::semantic::expressions::expression_analyzer::ExpressionAnalyzer;
+use crate::lang::semantic::expressions::identifier_analyzer::IdentifierAnalyzer;
 use crate::lang::semantic::scope::Scope;
 use crate::lang::semantic::semantic_error::SemanticError;
+use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::syntax::expressions::{expression::Expression, literal::Literal};
 use crate::lang::syntax::statements::assignment::Assignment;
 use crate::lang::{lexer::token_kind::TokenKind, position::Positioned};
 
@@ -14,9 +17,27 @@ impl AssignmentAnalyzer {
     pub fn analyze(assignment: &Assignment, scope: Rc<RefCell<Scope>>) -> Self {
         let mut diagnosis: Vec<SemanticError> = vec![];
 
-        let left_analyzer = ExpressionAnalyzer::analyze(&assignment.left, Rc::clone(&scope));
+        let left_analyzer = match &assignment.left {
+            // A plain assignment writes to the variable without reading its value.
+            Expression::Identifier(identifier, None)
+                if assignment.operator.token.kind == TokenKind::Equals =>
+            {
+                let analyzer = IdentifierAnalyzer::analyze_assignee(identifier, Rc::clone(&scope));
+
+                ExpressionAnalyzer {
+                    changeable: analyzer.changeable,
+                    return_type: analyzer.return_type,
+                    diagnosis: analyzer.diagnosis,
+                }
+            }
+            _ => ExpressionAnalyzer::analyze(&assignment.left, Rc::clone(&scope)),
+        };
         diagnosis.extend(left_analyzer.diagnosis);
 
+        if let Expression::Identifier(identifier, None) = &assignment.left {
+            scope.borrow_mut().mark_written(&identifier.name);
+        }
+
         if !left_analyzer.changeable {
             diagnosis.push(SemanticError::ValueCannotBeReassigned {
                 position: assignment.left.get_position(),
@@ -46,8 +67,17 @@ impl AssignmentAnalyzer {
             }
         }
 
-        if left_analyzer.return_type != right_analyzer.return_type
-            && (!left_analyzer.return_type.is_number() || !right_analyzer.return_type.is_number())
+        // An ASCII character fits in a byte, as the bytes of a string are written.
+        let is_byte = matches!(
+            (&left_analyzer.return_type, &assignment.right),
+            (SemanticType::U8, Expression::Literal(Literal::Char(token)))
+                if token.value.is_ascii()
+        );
+
+        if !is_byte
+            && !left_analyzer
+                .return_type
+                .accepts(&right_analyzer.return_type)
         {
             diagnosis.push(SemanticError::TypeMismatch {
                 left: left_analyzer.return_type,
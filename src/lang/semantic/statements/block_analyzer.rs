@@ -5,6 +5,7 @@ use super::do_while_analyzer::DoWhileAnalyzer;
 use super::for_analyzer::ForAnalyzer;
 use super::if_analyzer::IfAnalyzer;
 use super::let_analyzer::LetAnalyzer;
+use super::loop_analyzer::LoopAnalyzer;
 use super::print_analyzer::PrintAnalyzer;
 use super::return_analyzer::ReturnAnalyzer;
 use super::while_analyzer::WhileAnalyzer;
@@ -13,6 +14,7 @@ use crate::lang::syntax::shared::block::Block;
 use crate::lang::syntax::statements::statement::Statement;
 use crate::lang::semantic::scope::Scope;
 use crate::lang::semantic::semantic_error::SemanticError;
+use crate::lang::semantic::semantic_type::SemanticType;
 use crate::lang::semantic::{
     analyzer::Scopes, expressions::expression_analyzer::ExpressionAnalyzer,
 };
@@ -20,6 +22,9 @@ use crate::lang::semantic::{
 use std::{cell::RefCell, rc::Rc};
 
 pub struct BlockAnalyzer {
+    /// The type of the value of the block, which is `Any` when it is never left through its
+    /// end.
+    pub return_type: SemanticType,
     pub(crate) diagnosis: Vec<SemanticError>,
 }
 
@@ -29,6 +34,12 @@ impl BlockAnalyzer {
         Self::analyze_within_scope(block, Rc::new(RefCell::new(scope)), scopes)
     }
 
+    /// Analyzes a block written as an expression, which is analyzed apart from the scopes of
+    /// the function, as the blocks of lambdas are.
+    pub fn analyze_expression(block: &Block, parent_scope: Rc<RefCell<Scope>>) -> Self {
+        Self::analyze(block, parent_scope, &mut Scopes::new())
+    }
+
     pub fn analyze_within_scope(
         block: &Block,
         scope: Rc<RefCell<Scope>>,
@@ -64,6 +75,10 @@ impl BlockAnalyzer {
                     let analyzer = ForAnalyzer::analyze(r#for, scope, scopes);
                     diagnosis.extend(analyzer.diagnosis);
                 }
+                Statement::Loop(r#loop) => {
+                    let analyzer = LoopAnalyzer::analyze(r#loop, scope, scopes);
+                    diagnosis.extend(analyzer.diagnosis);
+                }
                 Statement::If(r#if) => {
                     let analyzer = IfAnalyzer::analyze(r#if, scope, scopes);
                     diagnosis.extend(analyzer.diagnosis);
@@ -84,6 +99,11 @@ impl BlockAnalyzer {
                     let analyzer = PrintAnalyzer::analyze(print, scope);
                     diagnosis.extend(analyzer.diagnosis);
                 }
+                Statement::Defer(defer) => {
+                    let scope = Rc::new(RefCell::new(Scope::new_deferred(scope)));
+                    let analyzer = Self::analyze_within_scope(&defer.block, scope, scopes);
+                    diagnosis.extend(analyzer.diagnosis);
+                }
                 Statement::Expression(expression) => {
                     let analyzer = ExpressionAnalyzer::analyze(expression, scope);
                     diagnosis.extend(analyzer.diagnosis);
@@ -91,8 +111,22 @@ impl BlockAnalyzer {
             }
         }
 
+        let return_type = match &block.expression {
+            Some(expression) => {
+                let analyzer = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope));
+                diagnosis.extend(analyzer.diagnosis);
+
+                analyzer.return_type
+            }
+            None if block.diverges() => SemanticType::Any,
+            None => SemanticType::Void,
+        };
+
         scopes.insert(block.id, Rc::clone(&scope));
 
-        Self { diagnosis }
+        Self {
+            return_type,
+            diagnosis,
+        }
     }
 }
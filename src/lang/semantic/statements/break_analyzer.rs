@@ -1,4 +1,6 @@
+use crate::lang::semantic::expressions::expression_analyzer::ExpressionAnalyzer;
 use crate::lang::semantic::semantic_error::SemanticError;
+use crate::lang::semantic::semantic_type::SemanticType;
 use crate::lang::syntax::statements::r#break::Break;
 use crate::lang::{position::Positioned, semantic::scope::Scope};
 
@@ -11,11 +13,12 @@ pub struct BreakAnalyzer {
 }
 
 impl BreakAnalyzer {
-    /// Analyzes the 'break' statement within a given scope.
+    /// Analyzes the 'break' statement within a given scope. The type of the value it gives, if
+    /// any, is recorded by the `loop` expression it leaves.
     ///
     /// # Arguments
     ///
-    /// * `_` - Represents the 'break' statement to be analyzed (unused in analysis).
+    /// * `r#break` - A reference to the 'break' statement to be analyzed.
     /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
     ///
     /// # Returns
@@ -24,12 +27,89 @@ impl BreakAnalyzer {
     pub fn analyze(r#break: &Break, scope: Rc<RefCell<Scope>>) -> Self {
         let mut diagnosis: Vec<SemanticError> = vec![];
 
-        if !scope.borrow().is_loop() {
-            diagnosis.push(SemanticError::InvalidBreak {
-                position: r#break.get_position(),
-            });
+        let is_valid = match &r#break.label {
+            // The label must be given to one of the loops around the statement.
+            Some(label) => {
+                let is_labeled_loop = scope.borrow().is_labeled_loop(&label.name);
+
+                if !is_labeled_loop {
+                    diagnosis.push(SemanticError::LabelNotFound {
+                        name: label.name.clone(),
+                        position: label.get_position(),
+                    });
+                }
+
+                is_labeled_loop
+            }
+            None => {
+                let is_loop = scope.borrow().is_loop();
+
+                if !is_loop {
+                    diagnosis.push(SemanticError::InvalidBreak {
+                        position: r#break.get_position(),
+                    });
+                }
+
+                is_loop
+            }
+        };
+
+        let r#type = match &r#break.expression {
+            Some(expression) => {
+                let analyzer = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope));
+                diagnosis.extend(analyzer.diagnosis);
+
+                analyzer.return_type
+            }
+            None => SemanticType::Void,
+        };
+
+        if is_valid {
+            let label = r#break.label.as_ref().map(|label| label.name.as_str());
+            let is_loop_expression =
+                scope
+                    .borrow_mut()
+                    .add_break_type(label, r#type, r#break.get_position());
+
+            if !is_loop_expression {
+                if let Some(expression) = &r#break.expression {
+                    diagnosis.push(SemanticError::InvalidBreakValue {
+                        position: expression.get_position(),
+                    });
+                }
+            }
         }
 
         Self { diagnosis }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    #[test]
+    fn test_labels() {
+        let code = "fun main() { 'outer: for i in 0..3 { while true { if i == 1 { continue 'outer; } break 'outer; } } }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { 'outer: for i in 0..3 { break 'missing; } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::LabelNotFound { ref name, .. }] if name == "missing"
+        ));
+
+        let code = "fun main() { 'outer: for i in 0..3 { continue 'missing; } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::LabelNotFound { ref name, .. }] if name == "missing"
+        ));
+
+        // The label of a loop is not seen from a function written inside of it.
+        let code = "fun main() { 'outer: for i in 0..3 { let f = fun() { while true { break 'outer; } }; f(); } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::LabelNotFound { .. }]
+        ));
+    }
+}
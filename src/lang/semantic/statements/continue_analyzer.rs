@@ -16,7 +16,7 @@ impl ContinueAnalyzer {
     ///
     /// # Arguments
     ///
-    /// * `_` - Represents the 'continue' statement to be analyzed (unused in analysis).
+    /// * `r#continue` - A reference to the 'continue' statement to be analyzed.
     /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
     ///
     /// # Returns
@@ -25,10 +25,23 @@ impl ContinueAnalyzer {
     pub fn analyze(r#continue: &Continue, scope: Rc<RefCell<Scope>>) -> Self {
         let mut diagnosis: Vec<SemanticError> = vec![];
 
-        if !scope.borrow().is_loop() {
-            diagnosis.push(SemanticError::InvalidContinue {
-                position: r#continue.get_position(),
-            });
+        match &r#continue.label {
+            // The label must be given to one of the loops around the statement.
+            Some(label) => {
+                if !scope.borrow().is_labeled_loop(&label.name) {
+                    diagnosis.push(SemanticError::LabelNotFound {
+                        name: label.name.clone(),
+                        position: label.get_position(),
+                    });
+                }
+            }
+            None => {
+                if !scope.borrow().is_loop() {
+                    diagnosis.push(SemanticError::InvalidContinue {
+                        position: r#continue.get_position(),
+                    });
+                }
+            }
         }
 
         Self { diagnosis }
@@ -31,7 +31,8 @@ impl DoWhileAnalyzer {
     pub fn analyze(do_while: &DoWhile, scope: Rc<RefCell<Scope>>, scopes: &mut Scopes) -> Self {
         let mut diagnosis: Vec<SemanticError> = vec![];
 
-        let scope = Rc::new(RefCell::new(Scope::new(Rc::clone(&scope), true, None)));
+        let label = do_while.label.as_ref().map(|label| label.name.clone());
+        let scope = Rc::new(RefCell::new(Scope::new_loop(Rc::clone(&scope), label)));
 
         let analyzer =
             BlockAnalyzer::analyze_within_scope(&do_while.block, Rc::clone(&scope), scopes);
@@ -42,6 +43,12 @@ impl DoWhileAnalyzer {
 
         diagnosis.extend(analyzer.diagnosis);
 
+        // A `?` or a `loop` runs before the statement, so only once instead of on every
+        // iteration.
+        if let Some(position) = do_while.expression.find_hoisted() {
+            diagnosis.push(SemanticError::HoistedNotAllowed { position });
+        }
+
         if analyzer.return_type != SemanticType::Bool {
             diagnosis.push(SemanticError::ExpectedType {
                 expected: SemanticType::Bool,
@@ -4,9 +4,11 @@ use crate::lang::semantic::semantic_error::SemanticError;
 use crate::lang::semantic::semantic_type::SemanticType;
 use crate::lang::semantic::symbol::Symbol;
 use crate::lang::semantic::{
-    analyzer::Scopes, expressions::expression_analyzer::ExpressionAnalyzer,
+    analyzer::Scopes,
+    expressions::{expression_analyzer::ExpressionAnalyzer, range_analyzer::RangeAnalyzer},
 };
 use crate::lang::syntax::expressions::expression::Expression;
+use crate::lang::syntax::shared::identifier::Identifier;
 use crate::lang::syntax::statements::r#for::For;
 
 use std::{cell::RefCell, rc::Rc};
@@ -34,19 +36,10 @@ impl ForAnalyzer {
     pub fn analyze(r#for: &For, scope: Rc<RefCell<Scope>>, scopes: &mut Scopes) -> Self {
         let mut diagnosis: Vec<SemanticError> = vec![];
 
-        let scope = Rc::new(RefCell::new(Scope::new(Rc::clone(&scope), true, None)));
+        let label = r#for.label.as_ref().map(|label| label.name.clone());
+        let scope = Rc::new(RefCell::new(Scope::new_loop(Rc::clone(&scope), label)));
 
-        let identifier_name = r#for.identifier.name.clone();
-
-        if let Some(_) = scope.borrow().get(&identifier_name) {
-            diagnosis.push(SemanticError::DuplicatedIdentifier {
-                position: r#for.identifier.get_position(),
-            });
-        }
-
-        let identifier_type = SemanticType::Any;
-
-        if let Expression::Range(range) = &r#for.expression {
+        let identifier_type = if let Expression::Range(range) = &r#for.expression {
             let left_analyzer = ExpressionAnalyzer::analyze(&range.left, Rc::clone(&scope));
             diagnosis.extend(left_analyzer.diagnosis);
 
@@ -62,36 +55,127 @@ impl ForAnalyzer {
                 });
             }
 
+            if let Some(step) = &range.step {
+                diagnosis.extend(RangeAnalyzer::analyze_step(step, Rc::clone(&scope)));
+            }
+
             // REVIEW: Should web check the type? In order to ensure that the types are both integers?
 
-            scope.borrow_mut().insert(Symbol::Variable {
-                name: identifier_name,
-                symbol_type: SemanticType::number_type_precedence(vec![
-                    left_analyzer.return_type,
-                    right_analyzer.return_type,
-                ]),
-            });
+            SemanticType::number_type_precedence(vec![
+                left_analyzer.return_type,
+                right_analyzer.return_type,
+            ])
         } else {
-            // If the expression is not a range expression we still verifies its types, but we keep the type Any;
-
             let analyzer = ExpressionAnalyzer::analyze(&r#for.expression, Rc::clone(&scope));
             diagnosis.extend(analyzer.diagnosis);
 
-            diagnosis.push(SemanticError::ExpectedType {
-                expected: SemanticType::Range,
-                found: analyzer.return_type,
-                position: r#for.expression.get_position(),
-            });
+            match analyzer.return_type {
+                SemanticType::Array(element_type, _)
+                | SemanticType::GenericArray(element_type, _)
+                | SemanticType::Slice(element_type)
+                | SemanticType::Vec(element_type) => element_type.as_ref().clone(),
+                SemanticType::String => SemanticType::Char,
+                SemanticType::Range => SemanticType::I64,
+                // The expression itself is invalid and was already reported.
+                SemanticType::Any => SemanticType::Any,
+                found => {
+                    diagnosis.push(SemanticError::ExpressionNotIterable {
+                        found,
+                        position: r#for.expression.get_position(),
+                    });
+
+                    SemanticType::Any
+                }
+            }
+        };
 
-            scope.borrow_mut().insert(Symbol::Variable {
-                name: identifier_name,
-                symbol_type: identifier_type,
-            });
+        if let Some(index) = &r#for.index {
+            Self::declare(index, SemanticType::I32, Rc::clone(&scope), &mut diagnosis);
         }
 
+        Self::declare(
+            &r#for.identifier,
+            identifier_type,
+            Rc::clone(&scope),
+            &mut diagnosis,
+        );
+
         let analyzer = BlockAnalyzer::analyze_within_scope(&r#for.block, Rc::clone(&scope), scopes);
         diagnosis.extend(analyzer.diagnosis);
 
         Self { diagnosis }
     }
+
+    /// Declares one of the loop variables in the loop scope.
+    fn declare(
+        identifier: &Identifier,
+        symbol_type: SemanticType,
+        scope: Rc<RefCell<Scope>>,
+        diagnosis: &mut Vec<SemanticError>,
+    ) {
+        let identifier_name = identifier.name.clone();
+
+        if scope.borrow().is_declared(&identifier_name) {
+            diagnosis.push(SemanticError::DuplicatedIdentifier {
+                position: identifier.get_position(),
+            });
+        }
+
+        scope.borrow_mut().declare(
+            Symbol::Variable {
+                name: identifier_name,
+                symbol_type,
+                mutable: false,
+            },
+            identifier.get_position(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{
+        semantic_error::SemanticError, semantic_type::SemanticType, test_helpers::analyze,
+    };
+
+    #[test]
+    fn test_iterables() {
+        let code = "fun main() { for x in [1, 2, 3] { let y: i32 = x; println y; } }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { for c in \"abc\" { let d: char = c; println d; } }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let a = [[1, 2], [3, 4]]; for row in a { println row[0]; } }";
+        assert!(analyze(code).is_empty());
+
+        let code =
+            "fun main() { for i, x in [1.5, 2.5] { let j: i32 = i; println j; println x; } }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let n = 10; for x in n { println x; } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ExpressionNotIterable {
+                found: SemanticType::I32,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_loop_variables() {
+        // The loop variables are immutable.
+        let code = "fun main() { for x in [1, 2] { x = 3; } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ValueCannotBeReassigned { .. }]
+        ));
+
+        let code = "fun main() { for x, x in [1, 2] { println x; } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::DuplicatedIdentifier { .. }]
+        ));
+    }
 }
@@ -1,17 +1,23 @@
 use std::{cell::RefCell, rc::Rc};
 
-use crate::lang::position::Positioned;
+use crate::lang::position::{Position, Positioned};
 use crate::lang::semantic::analyzer::Scopes;
 use crate::lang::semantic::expressions::expression_analyzer::ExpressionAnalyzer;
 use crate::lang::semantic::scope::Scope;
 use crate::lang::semantic::semantic_error::SemanticError;
 use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::symbol::Symbol;
+use crate::lang::syntax::shared::block::Block;
 use crate::lang::syntax::statements::r#if::If;
 
 use super::block_analyzer::BlockAnalyzer;
 
-/// Analyzer responsible for semantic analysis of 'if' statements.
+/// Analyzer responsible for semantic analysis of 'if' statements, which may also be written as
+/// expressions.
 pub struct IfAnalyzer {
+    /// The type of the value of the 'if', unified from the values of its branches.
+    pub return_type: SemanticType,
+
     /// A collection of semantic errors found during analysis.
     pub(crate) diagnosis: Vec<SemanticError>,
 }
@@ -35,7 +41,46 @@ impl IfAnalyzer {
 
         diagnosis.extend(analyzer.diagnosis);
 
-        if !analyzer.return_type.is_bool() {
+        let block_scope = Rc::new(RefCell::new(Scope::new(Rc::clone(&scope), false, None)));
+
+        if let Some(binding) = &r#if.binding {
+            // The value of the optional or the result is bound in the block, which only runs when
+            // there is one.
+            let value_type = match analyzer.return_type {
+                SemanticType::Optional(value_type) if *value_type != SemanticType::Any => {
+                    *value_type
+                }
+                SemanticType::Result(value_type, _)
+                    if !matches!(*value_type, SemanticType::Any | SemanticType::Void) =>
+                {
+                    *value_type
+                }
+                SemanticType::Any => SemanticType::Any,
+                found => {
+                    diagnosis.push(SemanticError::ExpectedOptional {
+                        found,
+                        position: r#if.expression.get_position(),
+                    });
+
+                    SemanticType::Any
+                }
+            };
+
+            if scope.borrow().is_declared(&binding.name) {
+                diagnosis.push(SemanticError::DuplicatedIdentifier {
+                    position: binding.get_position(),
+                });
+            }
+
+            block_scope.borrow_mut().declare(
+                Symbol::Variable {
+                    name: binding.name.clone(),
+                    symbol_type: value_type,
+                    mutable: false,
+                },
+                binding.get_position(),
+            );
+        } else if !analyzer.return_type.is_bool() {
             diagnosis.push(SemanticError::ExpectedType {
                 expected: SemanticType::Bool,
                 found: analyzer.return_type,
@@ -43,16 +88,112 @@ impl IfAnalyzer {
             })
         }
 
-        let analyzer = BlockAnalyzer::analyze(&r#if.block, Rc::clone(&scope), scopes);
+        let analyzer = BlockAnalyzer::analyze_within_scope(&r#if.block, block_scope, scopes);
 
         diagnosis.extend(analyzer.diagnosis);
 
-        if let Some(r#else) = &r#if.r#else {
-            let analyzer = BlockAnalyzer::analyze(&r#else.block, Rc::clone(&scope), scopes);
+        let then_type = analyzer.return_type;
+
+        // Without an `else`, the 'if' has no value, so neither may its block.
+        let return_type = match &r#if.r#else {
+            Some(r#else) => {
+                let analyzer = BlockAnalyzer::analyze(&r#else.block, Rc::clone(&scope), scopes);
+
+                diagnosis.extend(analyzer.diagnosis);
 
-            diagnosis.extend(analyzer.diagnosis);
+                let else_type = analyzer.return_type;
+                let return_type = SemanticType::unify(&[then_type.clone(), else_type.clone()]);
+
+                let agree = [&then_type, &else_type]
+                    .iter()
+                    .all(|r#type| **r#type == SemanticType::Any || return_type.accepts(r#type));
+
+                if !agree {
+                    diagnosis.push(SemanticError::TypeMismatch {
+                        left: then_type,
+                        right: else_type,
+                        position: Self::get_value_position(&r#else.block, r#if),
+                    });
+                }
+
+                return_type
+            }
+            None => {
+                if !matches!(then_type, SemanticType::Void | SemanticType::Any) {
+                    diagnosis.push(SemanticError::TypeMismatch {
+                        left: then_type,
+                        right: SemanticType::Void,
+                        position: r#if.get_position(),
+                    });
+                }
+
+                SemanticType::Void
+            }
+        };
+
+        Self {
+            return_type,
+            diagnosis,
         }
+    }
+
+    /// Analyzes an 'if' written as an expression, whose blocks are analyzed apart from the
+    /// scopes of the function, as the blocks of lambdas are.
+    pub fn analyze_expression(r#if: &If, scope: Rc<RefCell<Scope>>) -> Self {
+        Self::analyze(r#if, scope, &mut Scopes::new())
+    }
+
+    /// The position of the value of a branch, or of the 'if' when the branch has none.
+    fn get_value_position(block: &Block, r#if: &If) -> Position {
+        match &block.expression {
+            Some(expression) => expression.get_position(),
+            None => r#if.get_position(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    #[test]
+    fn test_if_values() {
+        let code = "fun main() { let c = true; let x = if c { 1 } else { 2 }; let y = { let a = 2; a * a }; println x + y; }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun f() { } fun g() { } fun main() { let c = true; let x = if c { f(); } else { g(); }; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ExpressionWithoutValue { .. }]
+        ));
+
+        let code = "fun f() { } fun main() { let x = { f(); }; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ExpressionWithoutValue { .. }]
+        ));
+
+        // A branch that returns has no value to agree with.
+        let code = "fun f(c: bool) -> i32 { let x = if c { 1 } else { return 0; }; return x; } fun main() { }";
+        assert!(analyze(code).is_empty());
+    }
+
+    #[test]
+    fn test_if_let() {
+        let code = "fun find() -> ?i32 { return 1; } fun parse() -> result<i32, string> { return ok(1); } fun main() { if let x = find() { println x; } if let y = parse() { println y; } }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { if let x = 10 { println x; } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ExpectedOptional { .. }]
+        ));
 
-        Self { diagnosis }
+        // A result without a value has nothing to be bound.
+        let code = "fun run() -> result<void, string> { return ok(); } fun main() { if let x = run() { } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ExpectedOptional { .. }]
+        ));
     }
 }
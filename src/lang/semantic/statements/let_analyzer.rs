@@ -33,7 +33,7 @@ impl LetAnalyzer {
         let variable_name = r#let.identifier.name.clone();
 
         // Verify if the function was already declared or if some builtin identifier has the same name.
-        if let Some(_) = scope.borrow().get(&variable_name) {
+        if scope.borrow().is_declared(&variable_name) {
             diagnosis.push(SemanticError::DuplicatedIdentifier {
                 position: r#let.identifier.get_position(),
             });
@@ -62,15 +62,12 @@ impl LetAnalyzer {
 
         // The code will always use the explicit type in case mismatched types. That's way the variable_type receives the result of the TypeAnalyzer analyses.
 
-        if r#let.r#type.is_some() && r#let.expression.is_some() {
-            let both_numbers = variable_type.is_number() && expression_type.is_number();
-
-            // Verify if the numbers are different and if they both are not numbers.
-            if !both_numbers && variable_type != expression_type {
+        if let (Some(_), Some(expression)) = (&r#let.r#type, &r#let.expression) {
+            if !variable_type.accepts(&expression_type) {
                 diagnosis.push(SemanticError::ExpectedType {
                     expected: variable_type.clone(),
                     found: expression_type.clone(),
-                    position: r#let.expression.as_ref().unwrap().get_position(),
+                    position: expression.get_position(),
                 });
             }
         }
@@ -81,12 +78,80 @@ impl LetAnalyzer {
             });
         }
 
+        // Nothing is known about the type of a `none`, nor about the other type of `ok(..)`
+        // and `err(..)`.
+        if r#let.r#type.is_none() && expression_type.is_partial() {
+            diagnosis.push(SemanticError::TypeNotInferable {
+                found: expression_type.clone(),
+                position: r#let.identifier.get_position(),
+            });
+        }
+
+        if !r#let.elements.is_empty() {
+            diagnosis.extend(Self::declare_elements(r#let, &variable_type, scope));
+            return Self { diagnosis };
+        }
+
         // Adds the new variable in the symbol table.
+        scope.borrow_mut().declare(
+            Symbol::Variable {
+                name: variable_name.clone(),
+                symbol_type: variable_type.clone(),
+                mutable: r#let.mutable,
+            },
+            r#let.identifier.get_position(),
+        );
+
+        Self { diagnosis }
+    }
+
+    /// Declares the variables that receive the elements of a tuple. The tuple is kept in a
+    /// hidden variable, whose usage is not tracked.
+    fn declare_elements(
+        r#let: &Let,
+        variable_type: &SemanticType,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Vec<SemanticError> {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        let types = match variable_type {
+            SemanticType::Tuple(types) if types.len() == r#let.elements.len() => types.clone(),
+            _ => {
+                if variable_type != &SemanticType::Any {
+                    diagnosis.push(SemanticError::InvalidDestructuring {
+                        expected: r#let.elements.len(),
+                        found: variable_type.clone(),
+                        position: r#let.identifier.get_position(),
+                    });
+                }
+
+                vec![SemanticType::Any; r#let.elements.len()]
+            }
+        };
+
         scope.borrow_mut().insert(Symbol::Variable {
-            name: variable_name.clone(),
+            name: r#let.identifier.name.clone(),
             symbol_type: variable_type.clone(),
+            mutable: false,
         });
 
-        Self { diagnosis }
+        for (element, element_type) in r#let.elements.iter().zip(types) {
+            if scope.borrow().is_declared(&element.name) {
+                diagnosis.push(SemanticError::DuplicatedIdentifier {
+                    position: element.get_position(),
+                });
+            }
+
+            scope.borrow_mut().declare(
+                Symbol::Variable {
+                    name: element.name.clone(),
+                    symbol_type: element_type,
+                    mutable: r#let.mutable,
+                },
+                element.get_position(),
+            );
+        }
+
+        diagnosis
     }
 }
@@ -0,0 +1,140 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::lang::position::Positioned;
+use crate::lang::semantic::analyzer::Scopes;
+use crate::lang::semantic::scope::Scope;
+use crate::lang::semantic::semantic_error::SemanticError;
+use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::syntax::statements::r#loop::Loop;
+
+use super::block_analyzer::BlockAnalyzer;
+
+/// Analyzer responsible for semantic analysis of 'loop' loops, which may be written as
+/// statements or as expressions.
+pub struct LoopAnalyzer {
+    pub return_type: SemanticType,
+
+    /// A collection of semantic errors found during analysis.
+    pub(crate) diagnosis: Vec<SemanticError>,
+}
+
+impl LoopAnalyzer {
+    /// Analyzes the provided 'loop' statement within a given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `r#loop` - A reference to the 'loop' statement to be analyzed.
+    /// * `scope` - A reference-counted reference to the scope in which the analysis occurs.
+    /// * `scopes` - A mutable reference to the set of scopes available for analysis.
+    ///
+    /// # Returns
+    ///
+    /// A `LoopAnalyzer` instance containing the analysis results.
+    pub fn analyze(r#loop: &Loop, scope: Rc<RefCell<Scope>>, scopes: &mut Scopes) -> Self {
+        let label = r#loop.label.as_ref().map(|label| label.name.clone());
+        let scope = Rc::new(RefCell::new(Scope::new_loop(Rc::clone(&scope), label)));
+
+        let analyzer = BlockAnalyzer::analyze_within_scope(&r#loop.block, scope, scopes);
+
+        Self {
+            return_type: SemanticType::Void,
+            diagnosis: analyzer.diagnosis,
+        }
+    }
+
+    pub fn analyze_expression(r#loop: &Loop, scope: Rc<RefCell<Scope>>) -> Self {
+        Self::analyze_body(r#loop, scope, &mut Scopes::new())
+    }
+
+    /// Analyzes a 'loop' expression along with its body. Its type is unified from the types of
+    /// the values given to the `break` statements that leave it, which must all agree.
+    ///
+    /// # Arguments
+    ///
+    /// * `r#loop` - A reference to the 'loop' expression to be analyzed.
+    /// * `scope` - A reference-counted reference to the scope in which the loop is written.
+    /// * `scopes` - Where the scopes of the blocks of the loop are stored.
+    ///
+    /// # Returns
+    ///
+    /// A `LoopAnalyzer` instance containing the analysis results.
+    pub fn analyze_body(r#loop: &Loop, scope: Rc<RefCell<Scope>>, scopes: &mut Scopes) -> Self {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        let label = r#loop.label.as_ref().map(|label| label.name.clone());
+        let scope = Rc::new(RefCell::new(Scope::new_loop_expression(
+            Rc::clone(&scope),
+            label,
+        )));
+
+        let analyzer =
+            BlockAnalyzer::analyze_within_scope(&r#loop.block, Rc::clone(&scope), scopes);
+        diagnosis.extend(analyzer.diagnosis);
+
+        let break_types = scope.borrow().get_break_types();
+
+        let types: Vec<SemanticType> = break_types
+            .iter()
+            .map(|(r#type, _)| r#type.clone())
+            .collect();
+        let return_type = SemanticType::unify(&types);
+
+        // A loop that is never left, or only left without a value, has no value.
+        if return_type == SemanticType::Void {
+            diagnosis.push(SemanticError::ExpressionWithoutValue {
+                position: r#loop.get_position(),
+            });
+        }
+
+        for (r#type, position) in &break_types {
+            if *r#type != SemanticType::Any && !return_type.accepts(r#type) {
+                diagnosis.push(SemanticError::TypeMismatch {
+                    left: return_type.clone(),
+                    right: r#type.clone(),
+                    position: *position,
+                });
+            }
+        }
+
+        Self {
+            return_type,
+            diagnosis,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    #[test]
+    fn test_loop_values() {
+        let code = "fun main() { let mut i = 0; let x = loop { i += 1; if i == 3 { break i * 2; } }; println x; }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun main() { let w = loop { break; }; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ExpressionWithoutValue { .. }]
+        ));
+
+        let code = "fun main() { let s = loop {}; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::ExpressionWithoutValue { .. }]
+        ));
+
+        // Only a `loop` expression has a value to be given.
+        let code = "fun main() { while true { break 1; } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidBreakValue { .. }]
+        ));
+
+        let code = "fun main() { for i in 0..3 { let x = loop { break i; }; break x; } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidBreakValue { .. }]
+        ));
+    }
+}
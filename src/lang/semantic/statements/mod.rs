@@ -6,6 +6,7 @@ pub mod do_while_analyzer;
 pub mod for_analyzer;
 pub mod if_analyzer;
 pub mod let_analyzer;
+pub mod loop_analyzer;
 pub mod print_analyzer;
 pub mod return_analyzer;
 pub mod while_analyzer;
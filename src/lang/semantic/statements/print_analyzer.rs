@@ -1,6 +1,9 @@
-use crate::lang::syntax::statements::print::Print;
+use crate::lang::position::Positioned;
+use crate::lang::semantic::expressions::interpolation_analyzer::InterpolationAnalyzer;
 use crate::lang::semantic::semantic_error::SemanticError;
 use crate::lang::semantic::{expressions::expression_analyzer::ExpressionAnalyzer, scope::Scope};
+use crate::lang::syntax::expressions::interpolation::Format;
+use crate::lang::syntax::statements::print::Print;
 
 use std::{cell::RefCell, rc::Rc};
 
@@ -27,6 +30,15 @@ impl PrintAnalyzer {
         for expression in &print.expressions {
             let analyzer = ExpressionAnalyzer::analyze(expression, Rc::clone(&scope));
             diagnosis.extend(analyzer.diagnosis);
+
+            // Each value is written as in an interpolated string without a format.
+            if let Some(error) = InterpolationAnalyzer::analyze_format(
+                &analyzer.return_type,
+                &Format::default(),
+                expression.get_position(),
+            ) {
+                diagnosis.push(error);
+            }
         }
 
         Self { diagnosis }
@@ -26,7 +26,16 @@ impl ReturnAnalyzer {
     pub fn analyze(r#return: &Return, scope: Rc<RefCell<Scope>>) -> Self {
         let mut diagnosis: Vec<SemanticError> = vec![];
 
-        if let Some(function_type) = scope.borrow().get_function_type() {
+        let function_type = scope.borrow().get_function_type();
+
+        // The block of a `defer` runs while its function is already being left.
+        if scope.borrow().is_deferred() {
+            diagnosis.push(SemanticError::InvalidDeferredExit {
+                position: r#return.get_position(),
+            });
+        }
+
+        if let Some(function_type) = function_type {
             let return_type = match &r#return.expression {
                 None => SemanticType::Void,
                 Some(expression) => {
@@ -39,9 +48,7 @@ impl ReturnAnalyzer {
             };
 
             if let SemanticType::Function(_, function_return_type) = function_type {
-                if function_return_type.as_ref().clone() != return_type
-                    && (!function_return_type.is_number() || !return_type.is_number())
-                {
+                if !function_return_type.accepts(&return_type) {
                     diagnosis.push(SemanticError::ExpectedType {
                         expected: function_return_type.as_ref().clone(),
                         found: return_type,
@@ -58,3 +65,27 @@ impl ReturnAnalyzer {
         Self { diagnosis }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    #[test]
+    fn test_deferred_exits() {
+        let code = "fun main() { defer { return; } }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidDeferredExit { .. }]
+        ));
+
+        let code = "fun f() -> ?i32 { let x: ?i32 = none; defer { let y = x?; } return 1; } fun main() { }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidDeferredExit { .. }]
+        ));
+
+        // A function written in a deferred block returns from itself.
+        let code = "fun main() { defer { let f = fun() -> i32 { return 1; }; println f(); } }";
+        assert!(analyze(code).is_empty());
+    }
+}
@@ -31,12 +31,19 @@ impl WhileAnalyzer {
     pub fn analyze(r#while: &While, scope: Rc<RefCell<Scope>>, scopes: &mut Scopes) -> Self {
         let mut diagnosis: Vec<SemanticError> = vec![];
 
-        let scope = Rc::new(RefCell::new(Scope::new(Rc::clone(&scope), true, None)));
+        let label = r#while.label.as_ref().map(|label| label.name.clone());
+        let scope = Rc::new(RefCell::new(Scope::new_loop(Rc::clone(&scope), label)));
 
         let analyzer = ExpressionAnalyzer::analyze(&r#while.expression, Rc::clone(&scope));
 
         diagnosis.extend(analyzer.diagnosis);
 
+        // A `?` or a `loop` runs before the statement, so only once instead of on every
+        // iteration.
+        if let Some(position) = r#while.expression.find_hoisted() {
+            diagnosis.push(SemanticError::HoistedNotAllowed { position });
+        }
+
         if !analyzer.return_type.is_bool() {
             diagnosis.push(SemanticError::ExpectedType {
                 expected: SemanticType::Bool,
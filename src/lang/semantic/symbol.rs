@@ -1,22 +1,55 @@
 use super::semantic_type::SemanticType;
 
+/// A method that the types implementing a trait must provide, whose type has `Self` standing
+/// for each of the types.
+#[derive(Clone, Debug)]
+pub struct TraitMethod {
+    pub name: String,
+    pub symbol_type: SemanticType,
+
+    /// Whether the receiver is declared as `mut self`.
+    pub mutating: bool,
+}
+
 #[derive(Clone, Debug)]
 pub enum Symbol {
     Variable {
         name: String,
         symbol_type: SemanticType,
+        mutable: bool,
     },
     Parameter {
         name: String,
         symbol_type: SemanticType,
+        mutable: bool,
     },
     Function {
         name: String,
         symbol_type: SemanticType,
+        /// Whether it is a builtin function, which a declaration with the same name replaces.
+        builtin: bool,
+        /// Whether it keeps its name in C, as the functions declared with `extern` or `export`.
+        external: bool,
     },
     Type {
         name: String,
     },
+    /// A type parameter of a generic function, standing for the given type.
+    TypeParameter {
+        name: String,
+        symbol_type: SemanticType,
+    },
+    /// The methods shared by the types that implement the trait.
+    Trait {
+        name: String,
+        methods: Vec<TraitMethod>,
+    },
+    /// A type parameter used as the size of arrays, which is only known in the instances of the
+    /// generic function.
+    SizeParameter {
+        name: String,
+        size: Option<usize>,
+    },
 }
 
 impl Symbol {
@@ -26,6 +59,17 @@ impl Symbol {
             Self::Function { name, .. } => name.clone(),
             Self::Parameter { name, .. } => name.clone(),
             Self::Type { name } => name.clone(),
+            Self::TypeParameter { name, .. } => name.clone(),
+            Self::SizeParameter { name, .. } => name.clone(),
+            Self::Trait { name, .. } => name.clone(),
+        }
+    }
+
+    /// Whether the symbol can be written to, either directly or through an index.
+    pub fn is_mutable(&self) -> bool {
+        match &self {
+            Self::Variable { mutable, .. } | Self::Parameter { mutable, .. } => *mutable,
+            _ => false,
         }
     }
 }
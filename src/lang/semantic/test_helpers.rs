@@ -0,0 +1,17 @@
+//! Helpers shared by the tests of the semantic analyses.
+
+use crate::lang::syntax::parser::Parser;
+
+use super::{analyzer::Analyzer, semantic_error::SemanticError, semantic_warning::SemanticWarning};
+
+/// Analyzes a program, returning the errors found in it.
+pub fn analyze(code: &str) -> Vec<SemanticError> {
+    let ast = Parser::from_code(code).parse().unwrap();
+    Analyzer::analyze(&ast).diagnosis
+}
+
+/// Analyzes a program, returning the warnings found in it.
+pub fn analyze_warnings(code: &str) -> Vec<SemanticWarning> {
+    let ast = Parser::from_code(code).parse().unwrap();
+    Analyzer::analyze(&ast).warnings
+}
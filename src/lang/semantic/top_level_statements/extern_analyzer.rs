@@ -0,0 +1,119 @@
+use crate::lang::position::Positioned;
+use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::shared::type_analyzer::TypeAnalyzer;
+use crate::lang::semantic::symbol::Symbol;
+use crate::lang::semantic::{scope::Scope, semantic_error::SemanticError};
+use crate::lang::syntax::shared::syntax_type::SyntaxType;
+use crate::lang::syntax::top_level_statements::function::ParamsDeclaration;
+use crate::lang::syntax::top_level_statements::r#extern::Extern;
+
+use std::{cell::RefCell, rc::Rc};
+
+/// Analyzer responsible for the declaration of a function implemented in C, which has no body
+/// to be analyzed.
+pub struct ExternAnalyzer {
+    pub(crate) diagnosis: Vec<SemanticError>,
+}
+
+impl ExternAnalyzer {
+    pub fn analyze_declaration(r#extern: &Extern, global_scope: Rc<RefCell<Scope>>) -> Self {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        let signature = &r#extern.signature;
+        let name = signature.identifier.name.clone();
+
+        // Verify if the function was already declared or if some builtin identifier has the same name.
+        if global_scope.borrow().is_declared(&name) {
+            diagnosis.push(SemanticError::DuplicatedIdentifier {
+                position: signature.identifier.get_position(),
+            });
+        }
+
+        let mut params_types: Vec<SemanticType> = vec![];
+
+        for param_declaration in &signature.params_declaration.params {
+            let analyzer =
+                TypeAnalyzer::analyze(&param_declaration.r#type, Rc::clone(&global_scope));
+            diagnosis.extend(analyzer.diagnosis);
+            params_types.push(analyzer.result_type);
+        }
+
+        let return_type = match &signature.r#type {
+            Some(r#type) => {
+                let analyzer = TypeAnalyzer::analyze(r#type, Rc::clone(&global_scope));
+                diagnosis.extend(analyzer.diagnosis);
+                analyzer.result_type
+            }
+            None => SemanticType::Void,
+        };
+
+        let function_type = SemanticType::Function(params_types, Box::new(return_type));
+
+        diagnosis.extend(Self::analyze_foreign_types(
+            &signature.params_declaration,
+            &signature.r#type,
+            &function_type,
+        ));
+
+        global_scope.borrow_mut().declare(
+            Symbol::Function {
+                name,
+                symbol_type: function_type,
+                builtin: false,
+                external: true,
+            },
+            signature.identifier.get_position(),
+        );
+
+        Self { diagnosis }
+    }
+
+    /// Verifies that the types of a function called from or by C can be passed as they are. A
+    /// string cannot be returned, as it is an array in C, so a `cstring` is returned instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `params_declaration` - The parameters of the function.
+    /// * `return_type` - The return type as written, if any.
+    /// * `function_type` - The type of the function.
+    ///
+    /// # Returns
+    ///
+    /// An `InvalidForeignType` error for each type that cannot be passed.
+    pub fn analyze_foreign_types(
+        params_declaration: &ParamsDeclaration,
+        return_type: &Option<SyntaxType>,
+        function_type: &SemanticType,
+    ) -> Vec<SemanticError> {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        let (params_types, semantic_return_type) = match function_type {
+            SemanticType::Function(params_types, return_type) => (params_types, return_type),
+            _ => return diagnosis,
+        };
+
+        for (param_declaration, param_type) in params_declaration.params.iter().zip(params_types) {
+            if !param_type.is_c_compatible() {
+                diagnosis.push(SemanticError::InvalidForeignType {
+                    found: param_type.clone(),
+                    position: param_declaration.r#type.get_position(),
+                });
+            }
+        }
+
+        if let Some(r#type) = return_type {
+            let semantic_return_type = semantic_return_type.as_ref();
+
+            if !semantic_return_type.is_c_compatible()
+                || semantic_return_type == &SemanticType::String
+            {
+                diagnosis.push(SemanticError::InvalidForeignType {
+                    found: semantic_return_type.clone(),
+                    position: r#type.get_position(),
+                });
+            }
+        }
+
+        diagnosis
+    }
+}
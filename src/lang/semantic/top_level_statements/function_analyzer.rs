@@ -1,10 +1,13 @@
 use crate::lang::position::Positioned;
 use crate::lang::semantic::analyzer::Scopes;
+use crate::lang::semantic::flow::initialization_analyzer::InitializationAnalyzer;
+use crate::lang::semantic::flow::none_check_analyzer::NoneCheckAnalyzer;
 use crate::lang::semantic::scope::Func;
-use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::semantic_type::{SemanticType, TypeArguments};
 use crate::lang::semantic::shared::type_analyzer::TypeAnalyzer;
 use crate::lang::semantic::statements::block_analyzer::BlockAnalyzer;
 use crate::lang::semantic::symbol::Symbol;
+use crate::lang::semantic::top_level_statements::extern_analyzer::ExternAnalyzer;
 use crate::lang::semantic::{scope::Scope, semantic_error::SemanticError};
 use crate::lang::syntax::top_level_statements::function::Function;
 
@@ -22,35 +25,67 @@ impl FunctionAnalyzer {
         let function_name = function.identifier.name.clone();
 
         // Verify if the function was already declared or if some builtin identifier has the same name.
-        if let Some(_) = global_scope.borrow().get(&function_name) {
+        if global_scope.borrow().is_declared(&function_name) {
             diagnosis.push(SemanticError::DuplicatedIdentifier {
                 position: function.identifier.get_position(),
             });
         }
 
+        // The type parameters are only visible to the types of the parameters and of the return.
+        let type_scope = Rc::new(RefCell::new(Scope::new(
+            Rc::clone(&global_scope),
+            false,
+            None,
+        )));
+        diagnosis.extend(Self::declare_type_parameters(
+            function,
+            &TypeArguments::new(),
+            Rc::clone(&type_scope),
+        ));
+
         let mut params_types: Vec<SemanticType> = vec![];
 
         for param_declaration in &function.params_declaration.params {
             let param_name = param_declaration.identifier.name.clone();
 
             // Verify if the parameter was already declared or if some builtin identifier has the same name.
-            if let Some(_) = global_scope.borrow().get(&param_name) {
+            if global_scope.borrow().is_declared(&param_name) {
                 diagnosis.push(SemanticError::DuplicatedIdentifier {
                     position: param_declaration.identifier.get_position(),
                 });
             }
 
-            let analyzer =
-                TypeAnalyzer::analyze(&param_declaration.r#type, Rc::clone(&global_scope));
+            let analyzer = TypeAnalyzer::analyze(&param_declaration.r#type, Rc::clone(&type_scope));
             diagnosis.extend(analyzer.diagnosis);
             let param_type = analyzer.result_type;
 
             params_types.push(param_type);
         }
 
+        // Verify that every type parameter can be inferred from the arguments of a call.
+        let mut inferable_parameters = vec![];
+
+        for param_type in &params_types {
+            param_type.get_type_parameters(&mut inferable_parameters);
+        }
+
+        for type_parameter in &function.type_parameters {
+            let name = &type_parameter.identifier.name;
+
+            if !inferable_parameters
+                .iter()
+                .any(|(parameter, _)| parameter == name)
+            {
+                diagnosis.push(SemanticError::TypeParameterNotInferable {
+                    name: name.clone(),
+                    position: type_parameter.identifier.get_position(),
+                });
+            }
+        }
+
         // Verify the function return type.
         if let Some(r#type) = &function.r#type {
-            let analyzer = TypeAnalyzer::analyze(r#type, Rc::clone(&global_scope));
+            let analyzer = TypeAnalyzer::analyze(r#type, Rc::clone(&type_scope));
             diagnosis.extend(analyzer.diagnosis);
             function_type = SemanticType::Function(params_types, Box::new(analyzer.result_type));
         } else {
@@ -73,8 +108,24 @@ impl FunctionAnalyzer {
         //     }
         // }
 
+        // The functions called by C are generated once, with the types they are declared with.
+        if let (true, Some(type_parameter)) = (function.export, function.type_parameters.first()) {
+            diagnosis.push(SemanticError::GenericExport {
+                position: type_parameter.identifier.get_position(),
+            });
+        } else if function.export {
+            diagnosis.extend(ExternAnalyzer::analyze_foreign_types(
+                &function.params_declaration,
+                &function.r#type,
+                &function_type,
+            ));
+        }
+
         // Verify is the main function and if it has parameters.
-        if function_name == "main" && function.params_declaration.params.len() != 0 {
+        if function_name == "main"
+            && (!function.params_declaration.params.is_empty()
+                || !function.type_parameters.is_empty())
+        {
             diagnosis.push(SemanticError::MainFunctionWithParameters {
                 position: function.identifier.get_position(),
             });
@@ -91,10 +142,25 @@ impl FunctionAnalyzer {
         }
 
         // Save the function in the global scope.
-        global_scope.borrow_mut().insert(Symbol::Function {
-            name: function_name.clone(),
-            symbol_type: function_type,
-        });
+        let mutable_params = function
+            .params_declaration
+            .params
+            .iter()
+            .map(|param| param.mutable)
+            .collect();
+        global_scope
+            .borrow_mut()
+            .mark_mutable_params(&function_name, mutable_params);
+
+        global_scope.borrow_mut().declare(
+            Symbol::Function {
+                name: function_name.clone(),
+                symbol_type: function_type,
+                builtin: false,
+                external: function.export,
+            },
+            function.identifier.get_position(),
+        );
 
         Self { diagnosis }
     }
@@ -103,6 +169,29 @@ impl FunctionAnalyzer {
         function: &Function,
         global_scope: Rc<RefCell<Scope>>,
         scopes: &mut Scopes,
+    ) -> Self {
+        Self::analyze_instance(function, &TypeArguments::new(), global_scope, scopes)
+    }
+
+    /// Analyzes the body of a function with its type parameters standing for the given type
+    /// arguments, so a generic function can be generated once for each of its instances.
+    ///
+    /// # Arguments
+    ///
+    /// * `function` - The function to be analyzed.
+    /// * `type_arguments` - The types given to the type parameters, empty to analyze the
+    ///   function as declared.
+    /// * `global_scope` - The scope where the function is declared.
+    /// * `scopes` - Where the scopes of the blocks of the function are stored.
+    ///
+    /// # Returns
+    ///
+    /// A `FunctionAnalyzer` instance containing the analysis results.
+    pub fn analyze_instance(
+        function: &Function,
+        type_arguments: &TypeArguments,
+        global_scope: Rc<RefCell<Scope>>,
+        scopes: &mut Scopes,
     ) -> Self {
         let mut diagnosis: Vec<SemanticError> = vec![];
 
@@ -111,45 +200,219 @@ impl FunctionAnalyzer {
 
         if let Some(symbol) = global_scope.borrow().get(&function_name) {
             if let Symbol::Function { symbol_type, .. } = &symbol {
-                function_return_type = symbol_type.clone();
+                function_return_type = symbol_type.substitute(type_arguments);
             }
         }
 
         // Creates the local function scope.
-        let mut function_scope = Scope::new(
+        let scope = Rc::new(RefCell::new(Scope::new(
             Rc::clone(&global_scope),
             false,
             Some(Func {
                 name: function_name,
                 return_type: function_return_type,
+                is_lambda: false,
             }),
-        );
+        )));
+
+        // The errors of the type parameters were already reported with the declaration.
+        Self::declare_type_parameters(function, type_arguments, Rc::clone(&scope));
 
         for param_declaration in &function.params_declaration.params {
             let param_name = param_declaration.identifier.name.clone();
 
             // Verify if the parameter was already declared or if some builtin identifier has the same name.
-            if let Some(_) = function_scope.get(&param_name) {
+            if scope.borrow().is_declared(&param_name) {
                 diagnosis.push(SemanticError::DuplicatedIdentifier {
                     position: param_declaration.identifier.get_position(),
                 });
             }
 
-            let analyzer =
-                TypeAnalyzer::analyze(&param_declaration.r#type, Rc::clone(&global_scope));
-            diagnosis.extend(analyzer.diagnosis);
-            let param_type = analyzer.result_type;
+            // The errors of the types were already reported with the declaration.
+            let param_type =
+                TypeAnalyzer::analyze(&param_declaration.r#type, Rc::clone(&scope)).result_type;
+
+            scope.borrow_mut().declare(
+                Symbol::Parameter {
+                    name: param_name,
+                    symbol_type: param_type,
+                    mutable: param_declaration.mutable,
+                },
+                param_declaration.identifier.get_position(),
+            )
+        }
 
-            function_scope.insert(Symbol::Parameter {
-                name: param_name,
-                symbol_type: param_type,
-            })
+        // The optionals checked against none are read as their values.
+        for position in NoneCheckAnalyzer::analyze(function).checked {
+            scope.borrow_mut().mark_checked(position);
         }
 
-        let scope = Rc::new(RefCell::new(function_scope));
         let analyzer = BlockAnalyzer::analyze_within_scope(&function.block, scope, scopes);
         diagnosis.extend(analyzer.diagnosis);
 
+        // Verify that every variable is assigned before being read.
+        let analyzer = InitializationAnalyzer::analyze(function);
+        diagnosis.extend(analyzer.diagnosis);
+
         Self { diagnosis }
     }
+
+    /// Declares the type parameters of a function in the given scope. Each one stands for its
+    /// type argument or, when there is none, for itself. The ones used as the size of an array
+    /// are declared as size parameters.
+    fn declare_type_parameters(
+        function: &Function,
+        type_arguments: &TypeArguments,
+        scope: Rc<RefCell<Scope>>,
+    ) -> Vec<SemanticError> {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        for type_parameter in &function.type_parameters {
+            let name = type_parameter.identifier.name.clone();
+
+            // Verify if the type parameter was already declared or if it has the name of a type.
+            let symbol = scope.borrow().get(&name);
+
+            if symbol.is_some() {
+                diagnosis.push(SemanticError::DuplicatedIdentifier {
+                    position: type_parameter.identifier.get_position(),
+                });
+            }
+
+            let bound = type_parameter
+                .bound
+                .as_ref()
+                .map(|bound| bound.name.clone());
+
+            // A bound is either `numeric` or a trait.
+            if let Some(bound) = &type_parameter.bound {
+                let symbol = scope.borrow().get(&bound.name);
+
+                if bound.name != "numeric" && !matches!(symbol, Some(Symbol::Trait { .. })) {
+                    diagnosis.push(SemanticError::IdentifierNotFound {
+                        position: bound.get_position(),
+                    });
+                }
+            }
+
+            let is_size = function
+                .params_declaration
+                .params
+                .iter()
+                .map(|param| &param.r#type)
+                .chain(function.r#type.as_ref())
+                .any(|r#type| r#type.uses_size(&name));
+
+            if is_size {
+                let size = type_arguments.sizes.get(&name).copied();
+
+                scope
+                    .borrow_mut()
+                    .insert(Symbol::SizeParameter { name, size });
+            } else {
+                let symbol_type = type_arguments
+                    .types
+                    .get(&name)
+                    .cloned()
+                    .unwrap_or(SemanticType::TypeParameter(name.clone(), bound));
+
+                scope
+                    .borrow_mut()
+                    .insert(Symbol::TypeParameter { name, symbol_type });
+            }
+        }
+
+        diagnosis
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    const MAX: &str = "fun max<T: numeric>(a: T, b: T) -> T { if a > b { return a; } return b; }";
+
+    #[test]
+    fn test_generic_inference() {
+        let code = format!(
+            "{} fun main() {{ let a: i32 = max(1, 2); let b: f32 = max(1.5, 2.5); println a; println b; }}",
+            MAX
+        );
+        assert!(analyze(&code).is_empty());
+
+        let code = format!("{} fun main() {{ println max(\"a\", \"b\"); }}", MAX);
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::UnsatisfiedTypeBound { .. }]
+        ));
+
+        // Both arguments give the type of the same parameter.
+        let code =
+            "fun first<T>(a: T, b: T) -> T { return a; } fun main() { println first(1, true); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::InvalidParameterType { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_generic_declarations() {
+        let code = "fun make<T>() -> vec<T> { let v: vec<T> = []; return v; } fun main() { }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::TypeParameterNotInferable { .. }]
+        ));
+
+        let code = "fun main<T>(x: T) { }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::MainFunctionWithParameters { .. }]
+        ));
+
+        // The instance of a generic function is only known where it is called.
+        let code = "fun id<T>(x: T) -> T { return x; } fun main() { let f = id; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::GenericNotCalled { .. }]
+        ));
+
+        let code = "export fun id<T>(x: T) -> T { return x; } fun main() { }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::GenericExport { .. }]
+        ));
+
+        let code = "fun main() { let v: vec<i32, i32>; let r: result<i32>; }";
+        assert!(matches!(
+            analyze(code)[..],
+            [
+                SemanticError::InvalidNumberOfTypeArguments {
+                    expected: 1,
+                    found: 2,
+                    ..
+                },
+                SemanticError::InvalidNumberOfTypeArguments {
+                    expected: 2,
+                    found: 1,
+                    ..
+                }
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_size_parameters() {
+        let code = "fun sum<N>(a: [i32; N]) -> i32 { let mut s = N; for x in a { s = s + x; } return s; } fun main() { println sum([1, 2, 3]); println sum([1]); }";
+        assert!(analyze(code).is_empty());
+
+        // The size of the repeat form may be a size parameter.
+        let code = "fun zeros<N>(a: [i32; N]) -> [i32; N] { let b = [0; N]; return b; } fun main() { let z: [i32; 2] = zeros([1, 2]); println z[0]; }";
+        assert!(analyze(code).is_empty());
+
+        let code = "fun zeros<N>(a: [i32; N]) -> i32 { let b = [0; M]; return len(b); } fun main() { println zeros([1]); }";
+        assert!(matches!(
+            analyze(code)[..],
+            [SemanticError::IdentifierNotFound { .. }, ..]
+        ));
+    }
 }
@@ -0,0 +1,266 @@
+use crate::lang::position::Positioned;
+use crate::lang::semantic::analyzer::Scopes;
+use crate::lang::semantic::semantic_type::{SemanticType, TypeArguments};
+use crate::lang::semantic::shared::type_analyzer::TypeAnalyzer;
+use crate::lang::semantic::symbol::Symbol;
+use crate::lang::semantic::{scope::Scope, semantic_error::SemanticError};
+use crate::lang::syntax::shared::identifier::Identifier;
+use crate::lang::syntax::top_level_statements::function::{
+    Function, ParamDeclaration, ParamsDeclaration,
+};
+use crate::lang::syntax::top_level_statements::r#impl::Impl;
+
+use super::function_analyzer::FunctionAnalyzer;
+
+use std::{cell::RefCell, rc::Rc};
+
+/// Analyzer responsible for the methods of a type, which are analyzed as functions named after
+/// the type, such as `vec<i32>.push`, whose first parameter is the value they are called on.
+/// The methods of a trait implementation must match the signatures declared by the trait.
+pub struct ImplAnalyzer {
+    pub(crate) diagnosis: Vec<SemanticError>,
+}
+
+impl ImplAnalyzer {
+    pub fn analyze_declaration(r#impl: &Impl, global_scope: Rc<RefCell<Scope>>) -> Self {
+        let analyzer = TypeAnalyzer::analyze(&r#impl.r#type, Rc::clone(&global_scope));
+        let mut diagnosis = analyzer.diagnosis;
+        let r#type = analyzer.result_type;
+
+        for function in Self::get_functions(r#impl, Rc::clone(&global_scope)) {
+            let analyzer =
+                FunctionAnalyzer::analyze_declaration(&function, Rc::clone(&global_scope));
+            diagnosis.extend(analyzer.diagnosis);
+
+            // The receiver is always the first parameter.
+            if function.params_declaration.params[0].mutable {
+                global_scope
+                    .borrow_mut()
+                    .mark_mutating(&function.identifier.name);
+            }
+        }
+
+        if let Some(r#trait) = &r#impl.r#trait {
+            diagnosis.extend(Self::analyze_trait(
+                r#impl,
+                r#trait,
+                &r#type,
+                Rc::clone(&global_scope),
+            ));
+        }
+
+        Self { diagnosis }
+    }
+
+    /// Verifies that the implementation provides every method of the trait, with the signature
+    /// declared by the trait where `Self` is the type of the implementation, and nothing else.
+    fn analyze_trait(
+        r#impl: &Impl,
+        r#trait: &Identifier,
+        r#type: &SemanticType,
+        global_scope: Rc<RefCell<Scope>>,
+    ) -> Vec<SemanticError> {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        let symbol = global_scope.borrow().get(&r#trait.name);
+
+        let methods = match symbol {
+            Some(Symbol::Trait { methods, .. }) => methods,
+            _ => {
+                diagnosis.push(SemanticError::IdentifierNotFound {
+                    position: r#trait.get_position(),
+                });
+
+                return diagnosis;
+            }
+        };
+
+        let mut type_arguments = TypeArguments::new();
+        type_arguments
+            .types
+            .insert(String::from("Self"), r#type.clone());
+
+        for method in &methods {
+            let function = r#impl
+                .functions
+                .iter()
+                .find(|function| function.identifier.name == method.name);
+
+            let function = match function {
+                Some(function) => function,
+                None => {
+                    diagnosis.push(SemanticError::MissingTraitMethod {
+                        name: method.name.clone(),
+                        r#trait: r#trait.name.clone(),
+                        position: r#trait.get_position(),
+                    });
+
+                    continue;
+                }
+            };
+
+            let symbol = global_scope
+                .borrow()
+                .get(&r#type.get_method_name(&method.name));
+
+            let is_same_type = match symbol {
+                Some(Symbol::Function { symbol_type, .. }) => {
+                    symbol_type == method.symbol_type.substitute(&type_arguments)
+                }
+                _ => false,
+            };
+
+            if !is_same_type || function.params_declaration.params[0].mutable != method.mutating {
+                diagnosis.push(SemanticError::InvalidTraitMethod {
+                    name: method.name.clone(),
+                    r#trait: r#trait.name.clone(),
+                    position: function.identifier.get_position(),
+                });
+            }
+        }
+
+        for function in &r#impl.functions {
+            if !methods
+                .iter()
+                .any(|method| method.name == function.identifier.name)
+            {
+                diagnosis.push(SemanticError::MethodNotInTrait {
+                    name: function.identifier.name.clone(),
+                    r#trait: r#trait.name.clone(),
+                    position: function.identifier.get_position(),
+                });
+            }
+        }
+
+        global_scope
+            .borrow_mut()
+            .mark_implemented(r#type, &r#trait.name);
+
+        diagnosis
+    }
+
+    pub fn analyze(r#impl: &Impl, global_scope: Rc<RefCell<Scope>>, scopes: &mut Scopes) -> Self {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        for function in Self::get_functions(r#impl, Rc::clone(&global_scope)) {
+            let analyzer = FunctionAnalyzer::analyze(&function, Rc::clone(&global_scope), scopes);
+            diagnosis.extend(analyzer.diagnosis);
+        }
+
+        Self { diagnosis }
+    }
+
+    /// Gets the methods of an implementation as functions named after the type, with `Self` in
+    /// their signatures replaced by the type. They keep their blocks, so their scopes are found
+    /// as the ones of the methods.
+    ///
+    /// # Arguments
+    ///
+    /// * `r#impl` - The implementation whose methods are taken.
+    /// * `global_scope` - The scope where the type of the implementation is resolved.
+    ///
+    /// # Returns
+    ///
+    /// The methods, named after the type.
+    pub fn get_functions(r#impl: &Impl, global_scope: Rc<RefCell<Scope>>) -> Vec<Function> {
+        let r#type = TypeAnalyzer::analyze(&r#impl.r#type, global_scope).result_type;
+
+        r#impl
+            .functions
+            .iter()
+            .map(|function| {
+                let mut identifier = Identifier::new(function.identifier.token.clone());
+                identifier.name = r#type.get_method_name(&function.identifier.name);
+
+                let params = function
+                    .params_declaration
+                    .params
+                    .iter()
+                    .map(|param| ParamDeclaration {
+                        r#type: param.r#type.replace("Self", &r#impl.r#type),
+                        ..param.clone()
+                    })
+                    .collect();
+
+                Function {
+                    identifier,
+                    params_declaration: ParamsDeclaration::new(params),
+                    r#type: function
+                        .r#type
+                        .as_ref()
+                        .map(|r#type| r#type.replace("Self", &r#impl.r#type)),
+                    ..function.clone()
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::semantic::{semantic_error::SemanticError, test_helpers::analyze};
+
+    const TRAIT: &str = "trait Comparable { fun compare(self, other: Self) -> i32; }";
+
+    #[test]
+    fn test_trait_implementations() {
+        let code = format!(
+            "{} impl Comparable for i32 {{ fun compare(self, other: i32) -> i32 {{ return self - other; }} }} \
+            fun max<T: Comparable>(a: T, b: T) -> T {{ if a.compare(b) > 0 {{ return a; }} return b; }} \
+            fun main() {{ println max(1, 2); }}",
+            TRAIT
+        );
+        assert!(analyze(&code).is_empty());
+
+        let code = format!("{} impl Comparable for i32 {{ }} fun main() {{ }}", TRAIT);
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::MissingTraitMethod { .. }]
+        ));
+
+        let code = format!(
+            "{} impl Comparable for i32 {{ fun compare(self, other: bool) -> i32 {{ return 0; }} }} fun main() {{ }}",
+            TRAIT
+        );
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::InvalidTraitMethod { .. }]
+        ));
+
+        // A method that mutates its value does not match one that does not.
+        let code = format!(
+            "{} impl Comparable for i32 {{ fun compare(mut self, other: i32) -> i32 {{ return 0; }} }} fun main() {{ }}",
+            TRAIT
+        );
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::InvalidTraitMethod { .. }]
+        ));
+
+        let code = format!(
+            "{} impl Comparable for i32 {{ fun compare(self, other: i32) -> i32 {{ return 0; }} \
+            fun double(self) -> i32 {{ return self * 2; }} }} fun main() {{ }}",
+            TRAIT
+        );
+        assert!(matches!(
+            analyze(&code)[..],
+            [SemanticError::MethodNotInTrait { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_traits_as_types() {
+        // A trait only bounds a type parameter, as its types have no common representation.
+        let code = format!(
+            "{} fun max(a: Comparable, b: Comparable) {{ }} fun main() {{ }}",
+            TRAIT
+        );
+        assert!(matches!(
+            analyze(&code)[..],
+            [
+                SemanticError::TraitUsedAsType { .. },
+                SemanticError::TraitUsedAsType { .. }
+            ]
+        ));
+    }
+}
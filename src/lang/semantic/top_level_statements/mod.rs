@@ -1 +1,4 @@
+pub mod extern_analyzer;
 pub mod function_analyzer;
+pub mod impl_analyzer;
+pub mod trait_analyzer;
@@ -0,0 +1,83 @@
+use crate::lang::position::Positioned;
+use crate::lang::semantic::semantic_type::SemanticType;
+use crate::lang::semantic::shared::type_analyzer::TypeAnalyzer;
+use crate::lang::semantic::symbol::{Symbol, TraitMethod};
+use crate::lang::semantic::{scope::Scope, semantic_error::SemanticError};
+use crate::lang::syntax::top_level_statements::r#trait::Trait;
+
+use std::{cell::RefCell, rc::Rc};
+
+/// Analyzer responsible for the declaration of a trait, whose methods are checked against the
+/// ones of each implementation. It has no body to be analyzed.
+pub struct TraitAnalyzer {
+    pub(crate) diagnosis: Vec<SemanticError>,
+}
+
+impl TraitAnalyzer {
+    pub fn analyze_declaration(r#trait: &Trait, global_scope: Rc<RefCell<Scope>>) -> Self {
+        let mut diagnosis: Vec<SemanticError> = vec![];
+
+        let name = r#trait.identifier.name.clone();
+
+        // Verify if the trait was already declared or if some builtin identifier has the same name.
+        if global_scope.borrow().is_declared(&name) {
+            diagnosis.push(SemanticError::DuplicatedIdentifier {
+                position: r#trait.identifier.get_position(),
+            });
+        }
+
+        // `Self` stands for the type of each implementation.
+        let type_scope = Rc::new(RefCell::new(Scope::new(
+            Rc::clone(&global_scope),
+            false,
+            None,
+        )));
+        type_scope.borrow_mut().insert(Symbol::TypeParameter {
+            name: String::from("Self"),
+            symbol_type: SemanticType::TypeParameter(String::from("Self"), Some(name.clone())),
+        });
+
+        let mut methods: Vec<TraitMethod> = vec![];
+
+        for signature in &r#trait.signatures {
+            let method_name = signature.identifier.name.clone();
+
+            if methods.iter().any(|method| method.name == method_name) {
+                diagnosis.push(SemanticError::DuplicatedIdentifier {
+                    position: signature.identifier.get_position(),
+                });
+            }
+
+            let mut params_types: Vec<SemanticType> = vec![];
+
+            for param_declaration in &signature.params_declaration.params {
+                let analyzer =
+                    TypeAnalyzer::analyze(&param_declaration.r#type, Rc::clone(&type_scope));
+                diagnosis.extend(analyzer.diagnosis);
+                params_types.push(analyzer.result_type);
+            }
+
+            let return_type = match &signature.r#type {
+                Some(r#type) => {
+                    let analyzer = TypeAnalyzer::analyze(r#type, Rc::clone(&type_scope));
+                    diagnosis.extend(analyzer.diagnosis);
+                    analyzer.result_type
+                }
+                None => SemanticType::Void,
+            };
+
+            // The receiver is always the first parameter.
+            methods.push(TraitMethod {
+                name: method_name,
+                symbol_type: SemanticType::Function(params_types, Box::new(return_type)),
+                mutating: signature.params_declaration.params[0].mutable,
+            });
+        }
+
+        global_scope
+            .borrow_mut()
+            .insert(Symbol::Trait { name, methods });
+
+        Self { diagnosis }
+    }
+}
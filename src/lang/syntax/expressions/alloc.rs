@@ -0,0 +1,41 @@
+use super::expression::Expression;
+use crate::lang::{
+    position::{Position, Positioned},
+    syntax::{shared::syntax_type::SyntaxType, tree_display::TreeDisplay},
+};
+
+/// The allocation of a number of values on the heap, as in `alloc<i32>(n)`, which gives a
+/// reference to the first of them.
+#[derive(Clone, Debug)]
+pub struct Alloc {
+    pub r#type: SyntaxType,
+    pub count: Box<Expression>,
+    position: Position,
+}
+
+impl Alloc {
+    pub fn new(r#type: SyntaxType, count: Expression, position: Position) -> Self {
+        Self {
+            r#type,
+            count: Box::new(count),
+            position,
+        }
+    }
+}
+
+impl Positioned for Alloc {
+    fn get_position(&self) -> Position {
+        self.position
+    }
+}
+
+impl TreeDisplay for Alloc {
+    fn display(&self, layer: usize) {
+        println!(
+            "{}AllocExpression ({})",
+            "  ".repeat(layer),
+            self.r#type.to_string()
+        );
+        self.count.display(layer + 1);
+    }
+}
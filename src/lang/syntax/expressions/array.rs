@@ -1,3 +1,4 @@
+use crate::lang::lexer::token::Token;
 use crate::lang::position::{Position, Positioned};
 use crate::lang::syntax::tree_display::TreeDisplay;
 
@@ -6,6 +7,7 @@ use super::expression::Expression;
 #[derive(Clone, Debug)]
 pub struct Array {
     pub expressions: Vec<Expression>,
+    pub size: Option<Token>,
     pub position: Position,
 }
 
@@ -13,6 +15,17 @@ impl Array {
     pub fn new(expressions: Vec<Expression>, position: Position) -> Self {
         Self {
             expressions,
+            size: None,
+            position,
+        }
+    }
+
+    /// Creates an array in the format `[expression; size]`, which repeats the
+    /// given expression `size` times.
+    pub fn new_repeat(expression: Expression, size: Token, position: Position) -> Self {
+        Self {
+            expressions: vec![expression],
+            size: Some(size),
             position,
         }
     }
@@ -26,7 +39,10 @@ impl Positioned for Array {
 
 impl TreeDisplay for Array {
     fn display(&self, layer: usize) {
-        println!("{}ArrayExpression", "  ".repeat(layer));
+        match &self.size {
+            Some(size) => println!("{}ArrayExpression ({})", "  ".repeat(layer), size.value),
+            None => println!("{}ArrayExpression", "  ".repeat(layer)),
+        }
 
         for expression in &self.expressions {
             expression.display(layer + 1);
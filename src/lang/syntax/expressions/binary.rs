@@ -46,7 +46,7 @@ impl Binary {
 
 impl Positioned for Binary {
     fn get_position(&self) -> crate::lang::position::Position {
-        todo!()
+        self.left.get_position()
     }
 }
 
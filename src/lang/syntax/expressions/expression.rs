@@ -1,19 +1,53 @@
 use std::fmt::Display;
 
+use super::alloc::Alloc;
 use super::array::Array;
 use super::binary::Binary;
+use super::interpolation::Interpolation;
+use super::lambda::Lambda;
 use super::literal::Literal;
 use super::parenthesized::Parenthesized;
 use super::range::Range;
+use super::tuple::Tuple;
 use super::unary::Unary;
+use crate::lang::lexer::token::Token;
 use crate::lang::position::{Position, Positioned};
-use crate::lang::syntax::shared::identifier::Identifier;
+use crate::lang::syntax::shared::{block::Block, identifier::Identifier};
+use crate::lang::syntax::statements::{r#if::If, r#loop::Loop};
 use crate::lang::syntax::tree_display::TreeDisplay;
 
 #[derive(Clone, Debug)]
 pub enum ExpressionMeta {
     Index(Box<Expression>, Box<Option<ExpressionMeta>>, Position),
     Call(Vec<Expression>, Box<Option<ExpressionMeta>>, Position),
+    // a.0
+    Field(Token, Box<Option<ExpressionMeta>>, Position),
+    // a.b()
+    Method(
+        Identifier,
+        Vec<Expression>,
+        Box<Option<ExpressionMeta>>,
+        Position,
+    ),
+    // a?
+    Try(Box<Option<ExpressionMeta>>, Position),
+}
+
+impl ExpressionMeta {
+    /// The position of the first `?` or `loop` within the meta, if any.
+    pub fn find_hoisted(&self) -> Option<Position> {
+        match self {
+            Self::Index(expression, meta, _) => expression
+                .find_hoisted()
+                .or_else(|| meta.as_ref().as_ref()?.find_hoisted()),
+            Self::Call(expressions, meta, _) | Self::Method(_, expressions, meta, _) => expressions
+                .iter()
+                .find_map(|expression| expression.find_hoisted())
+                .or_else(|| meta.as_ref().as_ref()?.find_hoisted()),
+            Self::Field(_, meta, _) => meta.as_ref().as_ref()?.find_hoisted(),
+            Self::Try(_, position) => Some(*position),
+        }
+    }
 }
 
 impl Positioned for ExpressionMeta {
@@ -21,6 +55,9 @@ impl Positioned for ExpressionMeta {
         match &self {
             Self::Index(_, _, position) => *position,
             Self::Call(_, _, position) => *position,
+            Self::Field(_, _, position) => *position,
+            Self::Method(_, _, _, position) => *position,
+            Self::Try(_, position) => *position,
         }
     }
 }
@@ -43,6 +80,31 @@ impl TreeDisplay for ExpressionMeta {
                     expression.display(layer + 1);
                 }
 
+                if let Some(meta) = meta.as_ref() {
+                    meta.display(layer + 1);
+                }
+            }
+            Self::Field(field, meta, _) => {
+                println!("{}Field ({})", "  ".repeat(layer), field.value);
+
+                if let Some(meta) = meta.as_ref() {
+                    meta.display(layer + 1);
+                }
+            }
+            Self::Method(identifier, expressions, meta, _) => {
+                println!("{}Method ({})", "  ".repeat(layer), identifier.name);
+
+                for expression in expressions {
+                    expression.display(layer + 1);
+                }
+
+                if let Some(meta) = meta.as_ref() {
+                    meta.display(layer + 1);
+                }
+            }
+            Self::Try(meta, _) => {
+                println!("{}Try", "  ".repeat(layer));
+
                 if let Some(meta) = meta.as_ref() {
                     meta.display(layer + 1);
                 }
@@ -60,6 +122,65 @@ pub enum Expression {
     Parenthesized(Parenthesized, Option<ExpressionMeta>),
     Range(Range),
     Array(Array, Option<ExpressionMeta>),
+    Lambda(Lambda),
+    Tuple(Tuple, Option<ExpressionMeta>),
+    Loop(Loop),
+    If(Box<If>),
+    Block(Block, Position),
+    Interpolation(Interpolation),
+    Alloc(Alloc),
+}
+
+impl Expression {
+    /// The position of the first `?` or `loop` within the expression, if any, which are
+    /// generated before the statement where they are written, as are the `if` and block
+    /// expressions that hold statements. The bodies of lambdas are not searched, as they return
+    /// from their own function.
+    pub fn find_hoisted(&self) -> Option<Position> {
+        let find_in_meta = |meta: &Option<ExpressionMeta>| meta.as_ref()?.find_hoisted();
+
+        match self {
+            Self::Identifier(_, meta) => find_in_meta(meta),
+            Self::Literal(_) | Self::Lambda(_) => None,
+            Self::Loop(r#loop) => Some(r#loop.get_position()),
+            Self::If(r#if) => match r#if.get_values() {
+                Some((then_value, else_value))
+                    if then_value.find_hoisted().is_none()
+                        && else_value.find_hoisted().is_none() =>
+                {
+                    r#if.expression.find_hoisted()
+                }
+                _ => Some(r#if.get_position()),
+            },
+            Self::Block(block, position) => match block.get_value() {
+                Some(value) => value.find_hoisted(),
+                None => Some(*position),
+            },
+            Self::Interpolation(interpolation) => interpolation
+                .get_values()
+                .find_map(|(expression, _)| expression.find_hoisted()),
+            Self::Unary(unary) => unary.expression.find_hoisted(),
+            Self::Alloc(alloc) => alloc.count.find_hoisted(),
+            Self::Binary(binary) => binary
+                .left
+                .find_hoisted()
+                .or_else(|| binary.right.find_hoisted()),
+            Self::Parenthesized(parenthesized, meta) => parenthesized
+                .expression
+                .find_hoisted()
+                .or_else(|| find_in_meta(meta)),
+            Self::Range(range) => range
+                .left
+                .find_hoisted()
+                .or_else(|| range.right.find_hoisted())
+                .or_else(|| range.step.as_ref()?.find_hoisted()),
+            Self::Array(Array { expressions, .. }, meta)
+            | Self::Tuple(Tuple { expressions, .. }, meta) => expressions
+                .iter()
+                .find_map(|expression| expression.find_hoisted())
+                .or_else(|| find_in_meta(meta)),
+        }
+    }
 }
 
 impl Positioned for Expression {
@@ -72,6 +193,13 @@ impl Positioned for Expression {
             Self::Parenthesized(parenthesized, _) => parenthesized.get_position(),
             Self::Range(range) => range.get_position(),
             Self::Array(array, _) => array.get_position(),
+            Self::Lambda(lambda) => lambda.get_position(),
+            Self::Tuple(tuple, _) => tuple.get_position(),
+            Self::Loop(r#loop) => r#loop.get_position(),
+            Self::If(r#if) => r#if.get_position(),
+            Self::Block(_, position) => *position,
+            Self::Interpolation(interpolation) => interpolation.get_position(),
+            Self::Alloc(alloc) => alloc.get_position(),
         }
     }
 }
@@ -86,6 +214,13 @@ impl Display for Expression {
             Self::Parenthesized(_, _) => write!(f, "parenthesized expression"),
             Self::Range(_) => write!(f, "range expression"),
             Self::Array(_, _) => write!(f, "array expression"),
+            Self::Lambda(_) => write!(f, "lambda expression"),
+            Self::Tuple(_, _) => write!(f, "tuple expression"),
+            Self::Loop(_) => write!(f, "loop expression"),
+            Self::If(_) => write!(f, "if expression"),
+            Self::Block(_, _) => write!(f, "block expression"),
+            Self::Interpolation(_) => write!(f, "interpolation expression"),
+            Self::Alloc(_) => write!(f, "alloc expression"),
         }
     }
 }
@@ -114,6 +249,19 @@ impl TreeDisplay for Expression {
             Self::Array(array, meta) => {
                 array.display(layer);
 
+                if let Some(meta) = meta {
+                    meta.display(layer + 1);
+                }
+            }
+            Self::Lambda(lambda) => lambda.display(layer),
+            Self::Loop(r#loop) => r#loop.display(layer),
+            Self::If(r#if) => r#if.display(layer),
+            Self::Block(block, _) => block.display(layer),
+            Self::Interpolation(interpolation) => interpolation.display(layer),
+            Self::Alloc(alloc) => alloc.display(layer),
+            Self::Tuple(tuple, meta) => {
+                tuple.display(layer);
+
                 if let Some(meta) = meta {
                     meta.display(layer + 1);
                 }
@@ -0,0 +1,149 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    iter::Peekable,
+    str::Chars,
+};
+
+use crate::lang::{
+    position::{Position, Positioned},
+    syntax::tree_display::TreeDisplay,
+};
+
+use super::expression::Expression;
+
+/// How a value written in an interpolated string is formatted, in the format:
+/// `[<|0][width][.precision][x|X]`.
+#[derive(Clone, Debug, Default)]
+pub struct Format {
+    /// Pads the value on the right instead of on the left.
+    pub left: bool,
+    /// Pads numbers with zeros instead of spaces.
+    pub zero: bool,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+    /// Writes integers in hexadecimal, with uppercase digits when set.
+    pub hex: Option<bool>,
+}
+
+impl Format {
+    /// Parses the text written after the `:` of a value, returning `None` if it is not a format.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut format = Self::default();
+        let mut chars = text.chars().peekable();
+
+        match chars.peek() {
+            Some('<') => format.left = true,
+            Some('0') => format.zero = true,
+            _ => {}
+        }
+
+        if format.left || format.zero {
+            chars.next();
+        }
+
+        fn read_number(chars: &mut Peekable<Chars>) -> Option<usize> {
+            let mut digits = String::new();
+
+            while let Some(digit) = chars.next_if(|char| char.is_ascii_digit()) {
+                digits.push(digit);
+            }
+
+            digits.parse::<usize>().ok()
+        }
+
+        format.width = read_number(&mut chars);
+
+        if chars.next_if_eq(&'.').is_some() {
+            format.precision = Some(read_number(&mut chars)?);
+        }
+
+        format.hex = match chars.next() {
+            Some('x') => Some(false),
+            Some('X') => Some(true),
+            Some(_) => return None,
+            None => None,
+        };
+
+        match chars.next() {
+            Some(_) => None,
+            None => Some(format),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.left {
+            write!(f, "<")?;
+        }
+
+        if self.zero {
+            write!(f, "0")?;
+        }
+
+        if let Some(width) = self.width {
+            write!(f, "{}", width)?;
+        }
+
+        if let Some(precision) = self.precision {
+            write!(f, ".{}", precision)?;
+        }
+
+        match self.hex {
+            Some(false) => write!(f, "x"),
+            Some(true) => write!(f, "X"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum InterpolationPart {
+    Text(String),
+    Value(Box<Expression>, Format),
+}
+
+/// A string literal with values written between braces, as in `"{name} has {count:03} items"`.
+/// Braces are written in the text as `{{` and `}}`.
+#[derive(Clone, Debug)]
+pub struct Interpolation {
+    pub parts: Vec<InterpolationPart>,
+    pub position: Position,
+}
+
+impl Interpolation {
+    pub fn new(parts: Vec<InterpolationPart>, position: Position) -> Self {
+        Self { parts, position }
+    }
+
+    pub fn get_values(&self) -> impl Iterator<Item = (&Expression, &Format)> {
+        self.parts.iter().filter_map(|part| match part {
+            InterpolationPart::Text(_) => None,
+            InterpolationPart::Value(expression, format) => Some((expression.as_ref(), format)),
+        })
+    }
+}
+
+impl Positioned for Interpolation {
+    fn get_position(&self) -> Position {
+        self.position
+    }
+}
+
+impl TreeDisplay for Interpolation {
+    fn display(&self, layer: usize) {
+        println!("{}InterpolationExpression", "  ".repeat(layer));
+
+        for part in &self.parts {
+            match part {
+                InterpolationPart::Text(text) => {
+                    println!("{}Text (\"{}\")", "  ".repeat(layer + 1), text)
+                }
+                InterpolationPart::Value(expression, format) => {
+                    println!("{}Value ({})", "  ".repeat(layer + 1), format);
+                    expression.display(layer + 2);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::lang::{
+    position::{Position, Positioned},
+    syntax::{
+        shared::{block::Block, syntax_type::SyntaxType},
+        top_level_statements::function::ParamsDeclaration,
+        tree_display::TreeDisplay,
+    },
+};
+
+/// An anonymous function written as an expression, which may use the locals of the
+/// function where it is written: `fun(a: i32) -> i32 { return a + b; }`.
+#[derive(Clone, Debug)]
+pub struct Lambda {
+    pub params_declaration: ParamsDeclaration,
+    pub r#type: Option<SyntaxType>,
+    pub block: Block,
+    pub position: Position,
+}
+
+impl Lambda {
+    pub fn new(
+        params_declaration: ParamsDeclaration,
+        r#type: Option<SyntaxType>,
+        block: Block,
+        position: Position,
+    ) -> Self {
+        Self {
+            params_declaration,
+            r#type,
+            block,
+            position,
+        }
+    }
+}
+
+impl Positioned for Lambda {
+    fn get_position(&self) -> Position {
+        self.position
+    }
+}
+
+impl TreeDisplay for Lambda {
+    fn display(&self, layer: usize) {
+        match self.r#type.as_ref() {
+            Some(r#type) => println!(
+                "{}LambdaExpression ({})",
+                "  ".repeat(layer),
+                r#type.to_string()
+            ),
+            None => println!("{}LambdaExpression", "  ".repeat(layer)),
+        }
+
+        self.params_declaration.display(layer + 1);
+        self.block.display(layer + 1);
+    }
+}
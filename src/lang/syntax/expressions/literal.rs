@@ -8,6 +8,7 @@ pub enum Literal {
     Char(Token),
     Number(Token),
     String(Token),
+    None(Token),
 }
 
 impl Positioned for Literal {
@@ -16,7 +17,8 @@ impl Positioned for Literal {
             Literal::Boolean(token)
             | Literal::Char(token)
             | Literal::String(token)
-            | Literal::Number(token) => token.position,
+            | Literal::Number(token)
+            | Literal::None(token) => token.position,
         }
     }
 }
@@ -25,7 +27,7 @@ impl TreeDisplay for Literal {
     fn display(&self, layer: usize) {
         // REVIEW: Should we show the type here?
         match self {
-            Self::Boolean(token) | Self::Number(token) => {
+            Self::Boolean(token) | Self::Number(token) | Self::None(token) => {
                 println!("{}Literal ({})", "  ".repeat(layer), token.value)
             }
             Self::Char(token) => println!("{}Literal ('{}')", "  ".repeat(layer), token.value),
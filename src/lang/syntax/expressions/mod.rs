@@ -1,7 +1,11 @@
+pub mod alloc;
 pub mod array;
 pub mod binary;
 pub mod expression;
+pub mod interpolation;
+pub mod lambda;
 pub mod literal;
 pub mod parenthesized;
 pub mod range;
+pub mod tuple;
 pub mod unary;
@@ -35,21 +35,28 @@ pub struct Range {
     pub left: Box<Expression>,
     pub operator: RangeOperator,
     pub right: Box<Expression>,
+    pub step: Option<Box<Expression>>,
 }
 
 impl Range {
-    pub fn new(left: Expression, operator: RangeOperator, right: Expression) -> Self {
+    pub fn new(
+        left: Expression,
+        operator: RangeOperator,
+        right: Expression,
+        step: Option<Expression>,
+    ) -> Self {
         Self {
             left: Box::new(left),
             operator,
             right: Box::new(right),
+            step: step.map(Box::new),
         }
     }
 }
 
 impl Positioned for Range {
     fn get_position(&self) -> Position {
-        todo!()
+        self.left.get_position()
     }
 }
 
@@ -59,5 +66,9 @@ impl TreeDisplay for Range {
         self.left.display(layer + 1);
         self.operator.display(layer + 1);
         self.right.display(layer + 1);
+
+        if let Some(step) = &self.step {
+            step.display(layer + 1);
+        }
     }
 }
@@ -0,0 +1,36 @@
+use crate::lang::position::{Position, Positioned};
+use crate::lang::syntax::tree_display::TreeDisplay;
+
+use super::expression::Expression;
+
+/// A tuple written as a list of expressions between parentheses: `(1, true)`.
+#[derive(Clone, Debug)]
+pub struct Tuple {
+    pub expressions: Vec<Expression>,
+    pub position: Position,
+}
+
+impl Tuple {
+    pub fn new(expressions: Vec<Expression>, position: Position) -> Self {
+        Self {
+            expressions,
+            position,
+        }
+    }
+}
+
+impl Positioned for Tuple {
+    fn get_position(&self) -> Position {
+        self.position
+    }
+}
+
+impl TreeDisplay for Tuple {
+    fn display(&self, layer: usize) {
+        println!("{}TupleExpression", "  ".repeat(layer));
+
+        for expression in &self.expressions {
+            expression.display(layer + 1);
+        }
+    }
+}
@@ -1,15 +1,21 @@
+use crate::lang::lexer::lexer::Lexer;
 use crate::lang::lexer::token::Token;
 use crate::lang::lexer::token_kind::TokenKind;
+use crate::lang::position::Position;
 
 use super::compilation_unit::CompilationUnit;
+use super::expressions::alloc::Alloc;
 use super::expressions::array::Array;
 use super::expressions::expression::ExpressionMeta;
 use super::expressions::{
     binary::{Binary, BinaryOperator},
     expression::Expression,
+    interpolation::{Format, Interpolation, InterpolationPart},
+    lambda::Lambda,
     literal::Literal,
     parenthesized::Parenthesized,
     range::{Range, RangeOperator},
+    tuple::Tuple,
     unary::{Unary, UnaryOperator},
 };
 
@@ -22,17 +28,22 @@ use super::statements::r#break::Break;
 use super::statements::r#continue::Continue;
 use super::statements::{
     assignment::Assignment,
+    defer::Defer,
     do_while::DoWhile,
     r#for::For,
     r#if::{Else, If},
     r#let::Let,
+    r#loop::Loop,
     r#return::Return,
     r#while::While,
     statement::Statement,
 };
 use super::syntax_error::SyntaxError;
 use super::top_level_statements::{
-    function::{Function, ParamDeclaration, ParamsDeclaration},
+    function::{Function, ParamDeclaration, ParamsDeclaration, TypeParameter},
+    r#extern::Extern,
+    r#impl::Impl,
+    r#trait::{Signature, Trait},
     top_level_statement::TopLevelStatement,
 };
 
@@ -51,8 +62,6 @@ impl Parser {
 
     #[cfg(test)]
     pub fn from_code(code: &str) -> Self {
-        use crate::lang::lexer::lexer::Lexer;
-
         let mut lexer = Lexer::new(code);
         let tokens = lexer.lex().unwrap();
 
@@ -84,6 +93,22 @@ impl Parser {
         self.tokens.pop_front().unwrap()
     }
 
+    /// Uses the `>` that closes a list of type arguments. A `>>` closes two nested lists at
+    /// once, so it is split and only its first half is used.
+    fn use_closing_angle_bracket(&mut self) -> Result<Token, SyntaxError> {
+        if self.get_current_token().kind == TokenKind::GreaterThanGreaterThan {
+            let token = self.next_token();
+            let position = Position::new(token.position.column + 1, token.position.line);
+
+            self.tokens
+                .push_front(Token::new(TokenKind::GreaterThan, position, ">"));
+
+            return Ok(Token::new(TokenKind::GreaterThan, token.position, ">"));
+        }
+
+        self.use_token(&[TokenKind::GreaterThan])
+    }
+
     pub fn parse(&mut self) -> Result<CompilationUnit, SyntaxError> {
         let mut statements: Vec<TopLevelStatement> = vec![];
 
@@ -107,7 +132,12 @@ impl Parser {
         let current_token = self.get_current_token();
 
         match current_token.kind {
-            TokenKind::FunKeyword => self.parse_function_declaration(),
+            TokenKind::FunKeyword | TokenKind::ExportKeyword => {
+                self.parse_function_declaration()
+            }
+            TokenKind::ImplKeyword => self.parse_impl_declaration(),
+            TokenKind::TraitKeyword => self.parse_trait_declaration(),
+            TokenKind::ExternKeyword => self.parse_extern_declaration(),
             _ => Err(SyntaxError::TopLevelStatementExpected {
                 position: current_token.position,
             }),
@@ -142,15 +172,34 @@ impl Parser {
             TokenKind::LeftBracket,
             TokenKind::Ref,
             TokenKind::LeftParenthesis,
+            TokenKind::Question,
         ])?;
 
         match &token.kind {
             TokenKind::Identifier => {
                 let type_identifier_token = token;
-                Ok(SyntaxType::new_simple(type_identifier_token))
+
+                if self.get_current_token().kind != TokenKind::LessThan {
+                    return Ok(SyntaxType::new_simple(type_identifier_token));
+                }
+
+                // vec<i32>
+
+                self.use_token(&[TokenKind::LessThan])?;
+
+                let mut arguments: Vec<SyntaxType> = vec![self.parse_type()?];
+
+                while self.get_current_token().kind == TokenKind::Comma {
+                    self.use_token(&[TokenKind::Comma])?;
+                    arguments.push(self.parse_type()?);
+                }
+
+                self.use_closing_angle_bracket()?;
+
+                Ok(SyntaxType::new_generic(type_identifier_token, arguments))
             }
             TokenKind::LeftParenthesis => {
-                // (i32, i32) -> i32
+                // (i32, i32) -> i32 or (i32, bool)
 
                 let mut params: Vec<SyntaxType> = vec![];
 
@@ -169,6 +218,11 @@ impl Parser {
                 }
 
                 self.use_token(&[TokenKind::RightParenthesis])?;
+
+                if params.len() > 1 && self.get_current_token().kind != TokenKind::ArrowRight {
+                    return Ok(SyntaxType::new_tuple(params, token.position));
+                }
+
                 self.use_token(&[TokenKind::ArrowRight])?;
 
                 let return_type = self.parse_type()?;
@@ -182,8 +236,17 @@ impl Parser {
             TokenKind::LeftBracket => {
                 let r#type = self.parse_type()?;
 
+                if self.get_current_token().kind == TokenKind::RightBracket {
+                    // [i32]
+
+                    self.use_token(&[TokenKind::RightBracket])?;
+
+                    return Ok(SyntaxType::new_slice(r#type, token.position));
+                }
+
                 self.use_token(&[TokenKind::Semicolon])?;
-                let array_size_token = self.use_token(&[TokenKind::NumberLiteral])?;
+                let array_size_token =
+                    self.use_token(&[TokenKind::NumberLiteral, TokenKind::Identifier])?;
 
                 self.use_token(&[TokenKind::RightBracket])?;
 
@@ -197,19 +260,33 @@ impl Parser {
                 let r#type = self.parse_type()?;
                 Ok(SyntaxType::new_reference(r#type, token.position))
             }
+            TokenKind::Question => {
+                // ?i32
+
+                let r#type = self.parse_type()?;
+                Ok(SyntaxType::new_optional(r#type, token.position))
+            }
             _ => unreachable!(),
         }
     }
 
-    /// Parses a function declaration in the format: `fun id(params) { ... }`.
+    /// Parses a function declaration in the format: `fun id(params) { ... }`, optionally
+    /// preceded by `export`.
     ///
     /// # Returns
     /// - `Ok(TopLevelStatement)`: Parsed function declaration as a top-level statement.
     /// - `Err(String)`: Error message if parsing fails.
     fn parse_function_declaration(&mut self) -> Result<TopLevelStatement, SyntaxError> {
+        let export = self.get_current_token().kind == TokenKind::ExportKeyword;
+
+        if export {
+            self.use_token(&[TokenKind::ExportKeyword])?;
+        }
+
         self.use_token(&[TokenKind::FunKeyword])?;
 
         let identifier_token = self.use_token(&[TokenKind::Identifier])?;
+        let type_parameters = self.parse_type_parameters_optional()?;
 
         self.use_token(&[TokenKind::LeftParenthesis])?;
 
@@ -221,13 +298,227 @@ impl Parser {
         let block = self.parse_block()?;
 
         Ok(TopLevelStatement::Function(Function::new(
+            export,
             Identifier::new(identifier_token),
+            type_parameters,
             ParamsDeclaration::new(params),
             identifier_type,
             block,
         )))
     }
 
+    /// Parses the methods of a type in the format: `impl type { fun id(self, ...) { ... } ... }`
+    /// or, implementing a trait, `impl Trait for type { ... }`.
+    ///
+    /// # Returns
+    /// - `Ok(TopLevelStatement)`: Parsed implementation.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_impl_declaration(&mut self) -> Result<TopLevelStatement, SyntaxError> {
+        self.use_token(&[TokenKind::ImplKeyword])?;
+
+        let mut r#trait: Option<Identifier> = None;
+        let mut r#type = self.parse_type()?;
+
+        if self.get_current_token().kind == TokenKind::ForKeyword {
+            let token = self.use_token(&[TokenKind::ForKeyword])?;
+
+            // The trait is named by an identifier, which is parsed as a type before the `for`.
+            match r#type {
+                SyntaxType::Simple { identifier } => r#trait = Some(Identifier::new(identifier)),
+                _ => {
+                    return Err(SyntaxError::UnexpectedToken {
+                        found: token.kind,
+                        position: token.position,
+                    })
+                }
+            }
+
+            r#type = self.parse_type()?;
+        }
+
+        let mut functions: Vec<Function> = vec![];
+
+        self.use_token(&[TokenKind::LeftBrace])?;
+
+        while self.get_current_token().kind != TokenKind::RightBrace {
+            functions.push(self.parse_method_declaration(&r#type)?);
+        }
+
+        self.use_token(&[TokenKind::RightBrace])?;
+
+        Ok(TopLevelStatement::Impl(Impl::new(r#trait, r#type, functions)))
+    }
+
+    /// Parses a trait in the format: `trait id { fun id(self, ...) -> type; ... }`, where the
+    /// receiver is of the type `Self`.
+    ///
+    /// # Returns
+    /// - `Ok(TopLevelStatement)`: Parsed trait.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_trait_declaration(&mut self) -> Result<TopLevelStatement, SyntaxError> {
+        self.use_token(&[TokenKind::TraitKeyword])?;
+
+        let identifier_token = self.use_token(&[TokenKind::Identifier])?;
+        let mut signatures: Vec<Signature> = vec![];
+
+        self.use_token(&[TokenKind::LeftBrace])?;
+
+        while self.get_current_token().kind != TokenKind::RightBrace {
+            let position = self.get_current_token().position;
+            let r#type = SyntaxType::new_simple(Token::new(TokenKind::Identifier, position, "Self"));
+
+            let (identifier, params, identifier_type) = self.parse_method_signature(&r#type)?;
+            self.use_token(&[TokenKind::Semicolon])?;
+
+            signatures.push(Signature::new(
+                identifier,
+                ParamsDeclaration::new(params),
+                identifier_type,
+            ));
+        }
+
+        self.use_token(&[TokenKind::RightBrace])?;
+
+        Ok(TopLevelStatement::Trait(Trait::new(
+            Identifier::new(identifier_token),
+            signatures,
+        )))
+    }
+
+    /// Parses a function implemented in C in the format: `extern fun id(params) -> type;` or,
+    /// with the header that declares it, `extern "header.h" fun id(params) -> type;`.
+    ///
+    /// # Returns
+    /// - `Ok(TopLevelStatement)`: Parsed extern function.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_extern_declaration(&mut self) -> Result<TopLevelStatement, SyntaxError> {
+        self.use_token(&[TokenKind::ExternKeyword])?;
+
+        let header = match self.get_current_token().kind {
+            TokenKind::StringLiteral => Some(self.use_token(&[TokenKind::StringLiteral])?),
+            _ => None,
+        };
+
+        self.use_token(&[TokenKind::FunKeyword])?;
+
+        let identifier_token = self.use_token(&[TokenKind::Identifier])?;
+
+        self.use_token(&[TokenKind::LeftParenthesis])?;
+
+        let params: Vec<ParamDeclaration> = self.parse_params_declaration()?;
+
+        self.use_token(&[TokenKind::RightParenthesis])?;
+
+        let identifier_type = self.parse_return_type_optional()?;
+
+        self.use_token(&[TokenKind::Semicolon])?;
+
+        Ok(TopLevelStatement::Extern(Extern::new(
+            header,
+            Signature::new(
+                Identifier::new(identifier_token),
+                ParamsDeclaration::new(params),
+                identifier_type,
+            ),
+        )))
+    }
+
+    /// Parses a method in the format: `fun id(self, ...) { ... }` or `fun id(mut self, ...) { ... }`.
+    /// The receiver is declared as a parameter named `self` of the type of the implementation.
+    ///
+    /// # Returns
+    /// - `Ok(Function)`: Parsed method.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_method_declaration(&mut self, r#type: &SyntaxType) -> Result<Function, SyntaxError> {
+        let (identifier, params, identifier_type) = self.parse_method_signature(r#type)?;
+        let block = self.parse_block()?;
+
+        Ok(Function::new(
+            false,
+            identifier,
+            vec![],
+            ParamsDeclaration::new(params),
+            identifier_type,
+            block,
+        ))
+    }
+
+    /// Parses the signature of a method in the format: `fun id(self, ...) -> type`, with the
+    /// receiver declared as a parameter named `self` of the given type.
+    ///
+    /// # Returns
+    /// - `Ok((Identifier, Vec<ParamDeclaration>, Option<SyntaxType>))`: Parsed name, parameters and return type.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_method_signature(
+        &mut self,
+        r#type: &SyntaxType,
+    ) -> Result<(Identifier, Vec<ParamDeclaration>, Option<SyntaxType>), SyntaxError> {
+        self.use_token(&[TokenKind::FunKeyword])?;
+
+        let identifier_token = self.use_token(&[TokenKind::Identifier])?;
+
+        self.use_token(&[TokenKind::LeftParenthesis])?;
+
+        let mutable = self.parse_mut_optional();
+        let receiver_token = self.use_token(&[TokenKind::SelfKeyword])?;
+
+        let mut params = vec![ParamDeclaration::new(
+            mutable,
+            Identifier::new(receiver_token),
+            r#type.clone(),
+        )];
+
+        if self.get_current_token().kind == TokenKind::Comma {
+            self.use_token(&[TokenKind::Comma])?;
+            params.extend(self.parse_params_declaration()?);
+        }
+
+        self.use_token(&[TokenKind::RightParenthesis])?;
+
+        let identifier_type = self.parse_return_type_optional()?;
+
+        Ok((Identifier::new(identifier_token), params, identifier_type))
+    }
+
+    /// Parses the type parameters of a generic function, if any, in the format: `<T, U: bound, ...>`.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<TypeParameter>)`: Parsed type parameters, empty if the function is not generic.
+    /// - `Err(String)`: Parsing error message.
+    fn parse_type_parameters_optional(&mut self) -> Result<Vec<TypeParameter>, SyntaxError> {
+        let mut type_parameters: Vec<TypeParameter> = vec![];
+
+        if self.get_current_token().kind != TokenKind::LessThan {
+            return Ok(type_parameters);
+        }
+
+        self.use_token(&[TokenKind::LessThan])?;
+
+        loop {
+            let identifier_token = self.use_token(&[TokenKind::Identifier])?;
+            let mut bound: Option<Identifier> = None;
+
+            if self.get_current_token().kind == TokenKind::Colon {
+                self.use_token(&[TokenKind::Colon])?;
+
+                let bound_token = self.use_token(&[TokenKind::Identifier])?;
+                bound = Some(Identifier::new(bound_token));
+            }
+
+            type_parameters.push(TypeParameter::new(Identifier::new(identifier_token), bound));
+
+            if self.get_current_token().kind != TokenKind::Comma {
+                break;
+            }
+
+            self.use_token(&[TokenKind::Comma])?;
+        }
+
+        self.use_token(&[TokenKind::GreaterThan])?;
+
+        Ok(type_parameters)
+    }
+
     /// Parses a list of parameter declarations in the format: `id : type_id, id2 : type_id2, ...`.
     ///
     /// # Returns
@@ -237,7 +528,7 @@ impl Parser {
         let current_token = self.get_current_token();
 
         match current_token.kind {
-            TokenKind::Identifier => {
+            TokenKind::Identifier | TokenKind::MutKeyword => {
                 let mut params: Vec<ParamDeclaration> = vec![];
 
                 let param = self.parse_param_declaration()?;
@@ -256,12 +547,13 @@ impl Parser {
         }
     }
 
-    /// Parses a parameter declaration in the form: `id : type_id`.
+    /// Parses a parameter declaration in the form: `id : type_id` or `mut id : type_id`.
     ///
     /// # Returns
     /// - `Ok(ParamDeclaration)`: Parsed parameter declaration.
     /// - `Err(String)`: Parsing error message.
     fn parse_param_declaration(&mut self) -> Result<ParamDeclaration, SyntaxError> {
+        let mutable = self.parse_mut_optional();
         let param_name_token = self.use_token(&[TokenKind::Identifier])?;
 
         self.use_token(&[TokenKind::Colon])?;
@@ -269,6 +561,7 @@ impl Parser {
         let r#type = self.parse_type()?;
 
         Ok(ParamDeclaration::new(
+            mutable,
             Identifier::new(param_name_token),
             r#type,
         ))
@@ -286,65 +579,96 @@ impl Parser {
             TokenKind::ForKeyword => self.parse_for_statement(),
             TokenKind::DoKeyword => self.parse_do_while_statement(),
             TokenKind::WhileKeyword => self.parse_while_statement(),
+            TokenKind::LoopKeyword => self.parse_loop_statement(),
+            TokenKind::Label => self.parse_labeled_statement(),
             TokenKind::IfKeyword => self.parse_if_statement(),
             TokenKind::LeftBrace => self.parse_block().map(|block| Statement::Block(block)),
             TokenKind::LetKeyword => Ok(self.parse_variable_declaration_statement()?),
             TokenKind::ReturnKeyword => self.parse_return_statement(),
             TokenKind::BreakKeyword => self.parse_break_statement(),
             TokenKind::ContinueKeyword => self.parse_continue_statement(),
-            TokenKind::PrintKeyword | TokenKind::PrintlnKeyword => self.parse_print_statement(),
+            TokenKind::DeferKeyword => self.parse_defer_statement(),
+            TokenKind::PrintKeyword
+            | TokenKind::PrintlnKeyword
+            | TokenKind::EprintKeyword
+            | TokenKind::EprintlnKeyword => self.parse_print_statement(),
             _ => {
                 let left_expression = self.parse_expression(0)?;
+                self.parse_expression_statement(left_expression)
+            }
+        }
+    }
 
-                let current_token = self.get_current_token();
+    /// Parses the rest of a statement that starts with the given expression, which is either
+    /// assigned or ends with a semicolon.
+    ///
+    /// # Returns
+    /// - `Ok(Statement)`: Parsed assignment or expression statement.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_expression_statement(
+        &mut self,
+        left_expression: Expression,
+    ) -> Result<Statement, SyntaxError> {
+        let current_token = self.get_current_token();
 
-                match current_token.kind {
-                    TokenKind::Equals
-                    | TokenKind::AmpersandEquals
-                    | TokenKind::PipeEquals
-                    | TokenKind::CircumflexEquals
-                    | TokenKind::TildeEquals
-                    | TokenKind::PlusEquals
-                    | TokenKind::MinusEquals
-                    | TokenKind::StarEquals
-                    | TokenKind::SlashEquals
-                    | TokenKind::ModEquals => {
-                        // Assignment
-
-                        let current_token = self.next_token();
-
-                        let right_expression = self.parse_expression(0)?;
-
-                        self.use_token(&[TokenKind::Semicolon])?;
-
-                        Ok(Statement::Assignment(Assignment::new(
-                            left_expression,
-                            AssignmentOperator::new(current_token),
-                            right_expression,
-                        )))
-                    }
-                    _ => {
-                        // Semicolon
+        match current_token.kind {
+            TokenKind::Equals
+            | TokenKind::AmpersandEquals
+            | TokenKind::PipeEquals
+            | TokenKind::CircumflexEquals
+            | TokenKind::TildeEquals
+            | TokenKind::PlusEquals
+            | TokenKind::MinusEquals
+            | TokenKind::StarEquals
+            | TokenKind::SlashEquals
+            | TokenKind::ModEquals => {
+                // Assignment
+
+                let current_token = self.next_token();
+
+                let right_expression = self.parse_expression(0)?;
 
-                        self.use_token(&[TokenKind::Semicolon])?;
+                self.use_token(&[TokenKind::Semicolon])?;
 
-                        Ok(Statement::Expression(left_expression))
-                    }
-                }
+                Ok(Statement::Assignment(Assignment::new(
+                    left_expression,
+                    AssignmentOperator::new(current_token),
+                    right_expression,
+                )))
+            }
+            _ => {
+                // Semicolon
+
+                self.use_token(&[TokenKind::Semicolon])?;
+
+                Ok(Statement::Expression(left_expression))
             }
         }
     }
 
     fn parse_continue_statement(&mut self) -> Result<Statement, SyntaxError> {
         let continue_token = self.use_token(&[TokenKind::ContinueKeyword])?;
+        let label = self.parse_label_optional();
         self.use_token(&[TokenKind::Semicolon])?;
-        Ok(Statement::Continue(Continue::new(continue_token.position)))
+        Ok(Statement::Continue(Continue::new(label, continue_token.position)))
     }
 
     fn parse_print_statement(&mut self) -> Result<Statement, SyntaxError> {
-        let token = self.use_token(&[TokenKind::PrintKeyword, TokenKind::PrintlnKeyword])?;
+        let token = self.use_token(&[
+            TokenKind::PrintKeyword,
+            TokenKind::PrintlnKeyword,
+            TokenKind::EprintKeyword,
+            TokenKind::EprintlnKeyword,
+        ])?;
 
-        let new_line = token.kind == TokenKind::PrintlnKeyword;
+        let new_line = matches!(
+            token.kind,
+            TokenKind::PrintlnKeyword | TokenKind::EprintlnKeyword
+        );
+        let stderr = matches!(
+            token.kind,
+            TokenKind::EprintKeyword | TokenKind::EprintlnKeyword
+        );
         let mut expressions: Vec<Expression> = vec![];
 
         while self.get_current_token().kind != TokenKind::Semicolon {
@@ -361,13 +685,80 @@ impl Parser {
 
         self.use_token(&[TokenKind::Semicolon])?;
 
-        Ok(Statement::Print(Print::new(new_line, expressions)))
+        Ok(Statement::Print(Print::new(new_line, stderr, expressions)))
     }
 
+    /// Parses a 'break' statement in the format: `break ['label] [expression];`.
+    ///
+    /// # Returns
+    /// - `Ok(Statement)`: Parsed 'break' statement.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
     fn parse_break_statement(&mut self) -> Result<Statement, SyntaxError> {
         let break_token = self.use_token(&[TokenKind::BreakKeyword])?;
+        let label = self.parse_label_optional();
+
+        let expression = if self.get_current_token().kind == TokenKind::Semicolon {
+            None
+        } else {
+            Some(self.parse_expression(0)?)
+        };
+
         self.use_token(&[TokenKind::Semicolon])?;
-        Ok(Statement::Break(Break::new(break_token.position)))
+
+        Ok(Statement::Break(Break::new(
+            label,
+            expression,
+            break_token.position,
+        )))
+    }
+
+    /// Parses the label that may follow `break` or `continue`, as in `break 'outer;`.
+    ///
+    /// # Returns
+    /// - `Option<Identifier>`: Parsed label, if any.
+    fn parse_label_optional(&mut self) -> Option<Identifier> {
+        if self.get_current_token().kind != TokenKind::Label {
+            return None;
+        }
+
+        Some(Identifier::new(self.next_token()))
+    }
+
+    /// Parses a loop with a label in the format: `'label: for ...`, `'label: while ...`,
+    /// `'label: do ...` or `'label: loop ...`.
+    ///
+    /// # Returns
+    /// - `Ok(Statement)`: Parsed loop statement.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_labeled_statement(&mut self) -> Result<Statement, SyntaxError> {
+        let label = Some(Identifier::new(self.use_token(&[TokenKind::Label])?));
+
+        self.use_token(&[TokenKind::Colon])?;
+
+        let current_token = self.get_current_token();
+
+        let mut statement = match current_token.kind {
+            TokenKind::ForKeyword => self.parse_for_statement()?,
+            TokenKind::DoKeyword => self.parse_do_while_statement()?,
+            TokenKind::WhileKeyword => self.parse_while_statement()?,
+            TokenKind::LoopKeyword => self.parse_loop_statement()?,
+            _ => {
+                return Err(SyntaxError::UnexpectedToken {
+                    found: current_token.kind,
+                    position: current_token.position,
+                })
+            }
+        };
+
+        match &mut statement {
+            Statement::For(r#for) => r#for.label = label,
+            Statement::DoWhile(do_while) => do_while.label = label,
+            Statement::While(r#while) => r#while.label = label,
+            Statement::Loop(r#loop) => r#loop.label = label,
+            _ => unreachable!(),
+        }
+
+        Ok(statement)
     }
 
     /// Parses a 'return' statement.
@@ -422,6 +813,58 @@ impl Parser {
         Ok(Block::new(statements))
     }
 
+    /// Parses a block used as an expression, whose value is written after its statements
+    /// without a semicolon: `{ statement value }`. The `if` and blocks among its statements
+    /// may also have values, so that the last of them can give the value of the block.
+    ///
+    /// # Returns
+    /// - `Ok(Block)`: Parsed block, along with its value.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_block_expression(&mut self) -> Result<Block, SyntaxError> {
+        self.use_token(&[TokenKind::LeftBrace])?;
+
+        let mut statements: Vec<Statement> = vec![];
+        let mut expression: Option<Expression> = None;
+
+        while self.get_current_token().kind != TokenKind::RightBrace {
+            let statement = match self.get_current_token().kind {
+                TokenKind::ForKeyword
+                | TokenKind::DoKeyword
+                | TokenKind::WhileKeyword
+                | TokenKind::LoopKeyword
+                | TokenKind::Label
+                | TokenKind::LetKeyword
+                | TokenKind::ReturnKeyword
+                | TokenKind::BreakKeyword
+                | TokenKind::ContinueKeyword
+                | TokenKind::PrintKeyword
+                | TokenKind::PrintlnKeyword
+                | TokenKind::EprintKeyword
+                | TokenKind::EprintlnKeyword => self.parse_statement()?,
+                _ => {
+                    let left_expression = self.parse_expression(0)?;
+
+                    if self.get_current_token().kind == TokenKind::RightBrace {
+                        expression = Some(left_expression);
+                        break;
+                    }
+
+                    match left_expression {
+                        Expression::If(r#if) => Statement::If(*r#if),
+                        Expression::Block(block, _) => Statement::Block(block),
+                        left_expression => self.parse_expression_statement(left_expression)?,
+                    }
+                }
+            };
+
+            statements.push(statement);
+        }
+
+        self.use_token(&[TokenKind::RightBrace])?;
+
+        Ok(Block::with_expression(statements, expression))
+    }
+
     /// Parses a 'while' loop statement in the format: `while condition { statement }`.
     ///
     /// # Returns
@@ -436,6 +879,35 @@ impl Parser {
         Ok(Statement::While(While::new(expression, block)))
     }
 
+    /// Parses a 'defer' statement in the format: `defer statement`, where a single statement
+    /// is kept as the only statement of the deferred block.
+    ///
+    /// # Returns
+    /// - `Ok(Statement)`: Parsed 'defer' statement.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_defer_statement(&mut self) -> Result<Statement, SyntaxError> {
+        let defer_token = self.use_token(&[TokenKind::DeferKeyword])?;
+
+        let block = match self.parse_statement()? {
+            Statement::Block(block) => block,
+            statement => Block::new(vec![statement]),
+        };
+
+        Ok(Statement::Defer(Defer::new(block, defer_token.position)))
+    }
+
+    /// Parses a 'loop' statement in the format: `loop { statement }`.
+    ///
+    /// # Returns
+    /// - `Ok(Statement)`: Parsed 'loop' statement.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_loop_statement(&mut self) -> Result<Statement, SyntaxError> {
+        let loop_token = self.use_token(&[TokenKind::LoopKeyword])?;
+        let block = self.parse_block()?;
+
+        Ok(Statement::Loop(Loop::new(block, loop_token.position)))
+    }
+
     /// Parses a 'while' loop statement in the format: `while condition { statement }`.
     ///
     /// # Returns
@@ -455,7 +927,7 @@ impl Parser {
         Ok(Statement::DoWhile(DoWhile::new(block, expression)))
     }
 
-    /// Parses a 'for' loop statement in the format: `for condition in expression { statement }`.
+    /// Parses a 'for' loop statement in the format: `for [index,] identifier in expression { statement }`.
     ///
     /// # Returns
     /// - `Ok(Statement)`: Parsed 'for' loop statement.
@@ -463,7 +935,15 @@ impl Parser {
     fn parse_for_statement(&mut self) -> Result<Statement, SyntaxError> {
         self.use_token(&[TokenKind::ForKeyword])?;
 
-        let identifier_token = self.use_token(&[TokenKind::Identifier])?;
+        let mut index: Option<Identifier> = None;
+        let mut identifier_token = self.use_token(&[TokenKind::Identifier])?;
+
+        if self.get_current_token().kind == TokenKind::Comma {
+            self.use_token(&[TokenKind::Comma])?;
+
+            index = Some(Identifier::new(identifier_token));
+            identifier_token = self.use_token(&[TokenKind::Identifier])?;
+        }
 
         self.use_token(&[TokenKind::InKeyword])?;
 
@@ -471,6 +951,7 @@ impl Parser {
         let statement = self.parse_block()?;
 
         Ok(Statement::For(For::new(
+            index,
             Identifier::new(identifier_token),
             expression,
             statement,
@@ -485,37 +966,105 @@ impl Parser {
     /// - `Ok(Statement)`: Parsed 'if' statement.
     /// - `Err(SyntaxError)`: Syntax error if parsing fails.
     fn parse_if_statement(&mut self) -> Result<Statement, SyntaxError> {
-        self.use_token(&[TokenKind::IfKeyword])?;
+        let if_token = self.use_token(&[TokenKind::IfKeyword])?;
+
+        Ok(Statement::If(self.parse_if(if_token, false)?))
+    }
+
+    /// Parses the rest of an 'if' after its keyword. Used as an expression, its blocks have
+    /// values. An `else if` is parsed as an else block holding only the inner 'if'.
+    ///
+    /// # Returns
+    /// - `Ok(If)`: Parsed 'if'.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_if(&mut self, if_token: Token, is_expression: bool) -> Result<If, SyntaxError> {
+        // if let value = expression { ... }
+        let binding = if self.get_current_token().kind == TokenKind::LetKeyword {
+            self.use_token(&[TokenKind::LetKeyword])?;
+
+            let identifier_token = self.use_token(&[TokenKind::Identifier])?;
+            self.use_token(&[TokenKind::Equals])?;
+
+            Some(Identifier::new(identifier_token))
+        } else {
+            None
+        };
 
         let expression = self.parse_expression(0)?;
-        let block = self.parse_block()?;
+
+        let block = if is_expression {
+            self.parse_block_expression()?
+        } else {
+            self.parse_block()?
+        };
 
         let current_token = self.get_current_token();
 
-        match current_token.kind {
+        let r#else = match current_token.kind {
             TokenKind::ElseKeyword => {
                 self.use_token(&[TokenKind::ElseKeyword])?;
 
-                let else_statement = self.parse_block()?;
+                let current_token = self.get_current_token();
 
-                Ok(Statement::If(If::new(
-                    expression,
-                    block,
-                    Some(Else::new(else_statement)),
-                )))
-            }
-            _ => Ok(Statement::If(If::new(expression, block, None))),
-        }
+                let else_block = match current_token.kind {
+                    TokenKind::IfKeyword => {
+                        let if_token = self.next_token();
+                        let r#if = self.parse_if(if_token, is_expression)?;
+
+                        // As an expression, the value of the block is the inner 'if'.
+                        if is_expression {
+                            Block::with_expression(vec![], Some(Expression::If(Box::new(r#if))))
+                        } else {
+                            Block::new(vec![Statement::If(r#if)])
+                        }
+                    }
+                    _ if is_expression => self.parse_block_expression()?,
+                    _ => self.parse_block()?,
+                };
+
+                Some(Else::new(else_block))
+            }
+            _ => None,
+        };
+
+        Ok(If::new(
+            binding,
+            expression,
+            block,
+            r#else,
+            if_token.position,
+        ))
+    }
+
+    /// Parses the optional `mut` keyword of a binding.
+    ///
+    /// # Returns
+    /// Whether the binding is mutable.
+    fn parse_mut_optional(&mut self) -> bool {
+        if self.get_current_token().kind == TokenKind::MutKeyword {
+            self.next_token();
+            true
+        } else {
+            false
+        }
     }
 
     /// Parses a variable declaration statement in the format: "let id = expression;" or "let id: type = expression;" or "let id: type;".
     ///
+    /// Mutable variables are declared with "let mut id ...".
+    ///
     /// # Returns
     /// - `Ok(Statement)`: Parsed variable declaration statement.
     /// - `Err(SyntaxError)`: Syntax error if parsing fails.
     fn parse_variable_declaration_statement(&mut self) -> Result<Statement, SyntaxError> {
         self.use_token(&[TokenKind::LetKeyword])?;
 
+        let mutable = self.parse_mut_optional();
+
+        if self.get_current_token().kind == TokenKind::LeftParenthesis {
+            return self.parse_destructuring_declaration(mutable);
+        }
+
         let identifier_token = self.use_token(&[TokenKind::Identifier])?;
 
         let type_identifier = self.parse_type_optional()?;
@@ -526,6 +1075,7 @@ impl Parser {
             TokenKind::Semicolon => {
                 self.use_token(&[TokenKind::Semicolon])?;
                 Ok(Statement::Let(Let::new(
+                    mutable,
                     Identifier::new(identifier_token),
                     type_identifier,
                     None,
@@ -539,6 +1089,7 @@ impl Parser {
                 self.use_token(&[TokenKind::Semicolon])?;
 
                 Ok(Statement::Let(Let::new(
+                    mutable,
                     Identifier::new(identifier_token),
                     type_identifier,
                     Some(expression),
@@ -547,6 +1098,43 @@ impl Parser {
         }
     }
 
+    /// Parses the declaration of the variables that receive the elements of a tuple, as in
+    /// `let (q, r) = divmod(a, b);`.
+    fn parse_destructuring_declaration(&mut self, mutable: bool) -> Result<Statement, SyntaxError> {
+        let token = self.use_token(&[TokenKind::LeftParenthesis])?;
+
+        let mut elements: Vec<Identifier> = vec![];
+
+        loop {
+            let identifier_token = self.use_token(&[TokenKind::Identifier])?;
+            elements.push(Identifier::new(identifier_token));
+
+            if self.get_current_token().kind == TokenKind::RightParenthesis {
+                break;
+            }
+
+            self.use_token(&[TokenKind::Comma])?;
+        }
+
+        self.use_token(&[TokenKind::RightParenthesis])?;
+
+        let type_identifier = self.parse_type_optional()?;
+
+        self.use_token(&[TokenKind::Equals])?;
+
+        let expression = self.parse_expression(0)?;
+
+        self.use_token(&[TokenKind::Semicolon])?;
+
+        Ok(Statement::Let(Let::new_destructuring(
+            mutable,
+            elements,
+            token.position,
+            type_identifier,
+            expression,
+        )))
+    }
+
     /// Parses an expression.
     ///
     /// # Arguments
@@ -593,10 +1181,18 @@ impl Parser {
                     let range_operator = RangeOperator::new(operator_token);
                     let right_expression = self.parse_expression(precedence)?;
 
+                    let mut step_expression: Option<Expression> = None;
+
+                    if self.get_current_token().kind == TokenKind::StepKeyword {
+                        self.use_token(&[TokenKind::StepKeyword])?;
+                        step_expression = Some(self.parse_expression(precedence)?);
+                    }
+
                     left_expression = Expression::Range(Range::new(
                         left_expression,
                         range_operator,
                         right_expression,
+                        step_expression,
                     ));
 
                     let current_token = self.get_current_token();
@@ -636,12 +1232,16 @@ impl Parser {
 
         if matches!(
             self.get_current_token().kind,
-            TokenKind::LeftBracket | TokenKind::LeftParenthesis | TokenKind::Dot
+            TokenKind::LeftBracket
+                | TokenKind::LeftParenthesis
+                | TokenKind::Dot
+                | TokenKind::Question
         ) {
             let token = self.use_token(&[
                 TokenKind::LeftBracket,
                 TokenKind::LeftParenthesis,
                 TokenKind::Dot,
+                TokenKind::Question,
             ])?;
 
             match &token.kind {
@@ -660,25 +1260,68 @@ impl Parser {
                 TokenKind::LeftParenthesis => {
                     // a()
 
-                    let mut expressions: Vec<Expression> = vec![];
+                    let expressions = self.parse_arguments()?;
+
+                    meta = Some(ExpressionMeta::Call(
+                        expressions,
+                        Box::new(self.parse_expression_meta()?),
+                        token.position,
+                    ));
+                }
+                TokenKind::Dot if self.get_current_token().kind == TokenKind::Identifier => {
+                    // a.b()
 
-                    while self.get_current_token().kind != TokenKind::RightParenthesis {
-                        let expression = self.parse_expression(0)?;
-                        expressions.push(expression);
+                    let identifier = Identifier::new(self.use_token(&[TokenKind::Identifier])?);
 
-                        if self.get_current_token().kind != TokenKind::RightParenthesis {
-                            self.use_token(&[TokenKind::Comma])?;
-                        }
-                    }
+                    self.use_token(&[TokenKind::LeftParenthesis])?;
 
-                    self.use_token(&[TokenKind::RightParenthesis])?;
+                    let expressions = self.parse_arguments()?;
 
-                    meta = Some(ExpressionMeta::Call(
+                    meta = Some(ExpressionMeta::Method(
+                        identifier,
                         expressions,
                         Box::new(self.parse_expression_meta()?),
                         token.position,
                     ));
                 }
+                TokenKind::Dot => {
+                    // a.0
+
+                    let field = self.use_token(&[TokenKind::NumberLiteral])?;
+
+                    // The fields of a nested tuple, as in a.0.1, are read as a decimal number.
+                    meta = match field.value.split_once('.') {
+                        Some((first, second)) => {
+                            let mut position = field.position;
+                            position.column += first.len() + 1;
+
+                            let second = Token::new(TokenKind::NumberLiteral, position, second);
+
+                            Some(ExpressionMeta::Field(
+                                Token::new(TokenKind::NumberLiteral, field.position, first),
+                                Box::new(Some(ExpressionMeta::Field(
+                                    second,
+                                    Box::new(self.parse_expression_meta()?),
+                                    position,
+                                ))),
+                                token.position,
+                            ))
+                        }
+                        None => Some(ExpressionMeta::Field(
+                            field,
+                            Box::new(self.parse_expression_meta()?),
+                            token.position,
+                        )),
+                    };
+                }
+                TokenKind::Question => {
+                    // a?
+
+                    meta = Some(ExpressionMeta::Try(
+                        Box::new(self.parse_expression_meta()?),
+                        token.position,
+                    ));
+                }
                 _ => {}
             }
         }
@@ -686,15 +1329,187 @@ impl Parser {
         Ok(meta)
     }
 
+    /// Parses the arguments of a call up to the closing parenthesis, in the format: `a, b, ...)`.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<Expression>)`: Parsed arguments.
+    /// - `Err(SyntaxError)`: Syntax error if parsing fails.
+    fn parse_arguments(&mut self) -> Result<Vec<Expression>, SyntaxError> {
+        let mut expressions: Vec<Expression> = vec![];
+
+        while self.get_current_token().kind != TokenKind::RightParenthesis {
+            let expression = self.parse_expression(0)?;
+            expressions.push(expression);
+
+            if self.get_current_token().kind != TokenKind::RightParenthesis {
+                self.use_token(&[TokenKind::Comma])?;
+            }
+        }
+
+        self.use_token(&[TokenKind::RightParenthesis])?;
+
+        Ok(expressions)
+    }
+
+    /// Parses an interpolated string in the format: `"text {expression[:format]} text"`. The
+    /// values are lexed and parsed apart, keeping their positions in the code, and the escape
+    /// sequences of the texts are decoded.
+    ///
+    /// # Returns
+    /// - `Ok(Interpolation)`: Parsed texts and values.
+    /// - `Err(SyntaxError)`: If a brace is not closed or a value is not a valid expression.
+    fn parse_interpolation(token: &Token) -> Result<Interpolation, SyntaxError> {
+        let advance = |position: &mut Position, char: char| {
+            if char == '\n' {
+                position.line += 1;
+                position.column = 1;
+            } else {
+                position.column += 1;
+            }
+        };
+
+        let mut parts: Vec<InterpolationPart> = vec![];
+        let mut text = String::new();
+
+        // The text starts after the opening quote.
+        let mut position = Position::new(token.position.column + 1, token.position.line);
+        let mut chars = token.value.chars().peekable();
+
+        while let Some(char) = chars.next() {
+            let char_position = position;
+            advance(&mut position, char);
+
+            match char {
+                // The escape sequences are kept until the text is decoded, along with the braces
+                // of a `\u{...}`.
+                '\\' => {
+                    text.push(char);
+
+                    if let Some(escaped) = chars.next() {
+                        advance(&mut position, escaped);
+                        text.push(escaped);
+
+                        if escaped == 'u' {
+                            for char in chars.by_ref() {
+                                advance(&mut position, char);
+                                text.push(char);
+
+                                if char == '}' {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+                // "{{" and "}}" are written as braces.
+                '{' | '}' if chars.peek() == Some(&char) => {
+                    advance(&mut position, char);
+                    chars.next();
+                    text.push(char);
+                }
+                '}' => {
+                    return Err(SyntaxError::InvalidInterpolation {
+                        position: char_position,
+                    })
+                }
+                '{' => {
+                    let value_position = position;
+                    let mut value = String::new();
+                    let mut depth = 0;
+
+                    loop {
+                        match chars.next() {
+                            Some('}') if depth == 0 => break,
+                            // The quotes of the strings in a value are escaped.
+                            Some('\\') if chars.peek() == Some(&'"') => {
+                                advance(&mut position, '\\');
+                            }
+                            Some(char) => {
+                                match char {
+                                    '{' => depth += 1,
+                                    '}' => depth -= 1,
+                                    _ => {}
+                                }
+
+                                advance(&mut position, char);
+                                value.push(char);
+                            }
+                            None => {
+                                return Err(SyntaxError::InvalidInterpolation {
+                                    position: char_position,
+                                })
+                            }
+                        }
+                    }
+
+                    advance(&mut position, '}');
+
+                    if !text.is_empty() {
+                        let value = Lexer::unescape(&std::mem::take(&mut text));
+                        parts.push(InterpolationPart::Text(value));
+                    }
+
+                    parts.push(Self::parse_interpolated_value(&value, value_position)?);
+                }
+                _ => text.push(char),
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(InterpolationPart::Text(Lexer::unescape(&text)));
+        }
+
+        Ok(Interpolation::new(parts, token.position))
+    }
+
+    /// Parses a value of an interpolated string in the format: `expression[:format]`, where the
+    /// format is `[<|0][width][.precision][x|X]`.
+    ///
+    /// # Returns
+    /// - `Ok(InterpolationPart)`: Parsed value and format.
+    /// - `Err(SyntaxError)`: If the value is not a valid expression.
+    fn parse_interpolated_value(
+        value: &str,
+        position: Position,
+    ) -> Result<InterpolationPart, SyntaxError> {
+        // The format follows the last `:`, unless it belongs to the expression, as in a lambda.
+        let (expression, format) = match value.rsplit_once(':') {
+            Some((expression, format)) => match Format::parse(format) {
+                Some(format) => (expression, format),
+                None => (value, Format::default()),
+            },
+            None => (value, Format::default()),
+        };
+
+        let mut lexer = Lexer::with_position(expression, position);
+        let tokens = lexer
+            .lex()
+            .map_err(|_| SyntaxError::InvalidInterpolation { position })?;
+
+        let mut parser = Parser::from_tokens(tokens);
+        let expression = parser.parse_expression(0)?;
+        parser.use_token(&[TokenKind::EndOfFile])?;
+
+        Ok(InterpolationPart::Value(Box::new(expression), format))
+    }
+
     fn parse_factor(&mut self) -> Result<Expression, SyntaxError> {
         let token = self.next_token();
 
         match token.kind {
             TokenKind::BooleanLiteral => Ok(Expression::Literal(Literal::Boolean(token))),
             TokenKind::CharLiteral => Ok(Expression::Literal(Literal::Char(token))),
-            TokenKind::StringLiteral => Ok(Expression::Literal(Literal::String(token))),
+            TokenKind::StringLiteral if token.value.contains(['{', '}']) => {
+                Ok(Expression::Interpolation(Self::parse_interpolation(&token)?))
+            }
+            TokenKind::StringLiteral => Ok(Expression::Literal(Literal::String(Token::new(
+                TokenKind::StringLiteral,
+                token.position,
+                &Lexer::unescape(&token.value),
+            )))),
             TokenKind::NumberLiteral => Ok(Expression::Literal(Literal::Number(token))),
-            TokenKind::Identifier => {
+            TokenKind::NoneLiteral => Ok(Expression::Literal(Literal::None(token))),
+            TokenKind::Identifier | TokenKind::SelfKeyword => {
                 let meta = self.parse_expression_meta()?;
                 Ok(Expression::Identifier(Identifier::new(token), meta))
             }
@@ -714,6 +1529,33 @@ impl Parser {
 
                 loop {
                     let expression = self.parse_expression(0)?;
+
+                    // [a; 10] or [a; N]
+                    if expressions.is_empty()
+                        && self.get_current_token().kind == TokenKind::Semicolon
+                    {
+                        self.next_token();
+                        let size =
+                            self.use_token(&[TokenKind::NumberLiteral, TokenKind::Identifier])?;
+
+                        if size.kind == TokenKind::NumberLiteral
+                            && size.value.parse::<usize>().is_err()
+                        {
+                            return Err(SyntaxError::UnexpectedToken {
+                                found: size.kind,
+                                position: size.position,
+                            });
+                        }
+                        self.use_token(&[TokenKind::RightBracket])?;
+
+                        let meta = self.parse_expression_meta()?;
+
+                        return Ok(Expression::Array(
+                            Array::new_repeat(expression, size, token.position),
+                            meta,
+                        ));
+                    }
+
                     expressions.push(expression);
 
                     let current_token = self.get_current_token();
@@ -735,6 +1577,27 @@ impl Parser {
             }
             TokenKind::LeftParenthesis => {
                 let expression = self.parse_expression(0)?;
+
+                if self.get_current_token().kind == TokenKind::Comma {
+                    // (a, b)
+
+                    let mut expressions: Vec<Expression> = vec![expression];
+
+                    while self.get_current_token().kind == TokenKind::Comma {
+                        self.use_token(&[TokenKind::Comma])?;
+                        expressions.push(self.parse_expression(0)?);
+                    }
+
+                    self.use_token(&[TokenKind::RightParenthesis])?;
+
+                    let meta = self.parse_expression_meta()?;
+
+                    return Ok(Expression::Tuple(
+                        Tuple::new(expressions, token.position),
+                        meta,
+                    ));
+                }
+
                 self.use_token(&[TokenKind::RightParenthesis])?;
 
                 let meta = self.parse_expression_meta()?;
@@ -744,6 +1607,64 @@ impl Parser {
                     meta,
                 ))
             }
+            TokenKind::AllocKeyword => {
+                // alloc<i32>(n)
+                self.use_token(&[TokenKind::LessThan])?;
+                let r#type = self.parse_type()?;
+                self.use_closing_angle_bracket()?;
+
+                self.use_token(&[TokenKind::LeftParenthesis])?;
+                let count = self.parse_expression(0)?;
+                self.use_token(&[TokenKind::RightParenthesis])?;
+
+                Ok(Expression::Alloc(Alloc::new(r#type, count, token.position)))
+            }
+            TokenKind::FunKeyword => {
+                // fun(a: i32) -> i32 { ... }
+                self.use_token(&[TokenKind::LeftParenthesis])?;
+                let params = self.parse_params_declaration()?;
+                self.use_token(&[TokenKind::RightParenthesis])?;
+
+                let r#type = self.parse_return_type_optional()?;
+                let block = self.parse_block()?;
+
+                Ok(Expression::Lambda(Lambda::new(
+                    ParamsDeclaration::new(params),
+                    r#type,
+                    block,
+                    token.position,
+                )))
+            }
+            TokenKind::IfKeyword => {
+                // if a { b } else { c }
+                let r#if = self.parse_if(token, true)?;
+
+                Ok(Expression::If(Box::new(r#if)))
+            }
+            TokenKind::LeftBrace => {
+                // { statement value }
+                self.tokens.push_front(token.clone());
+                let block = self.parse_block_expression()?;
+
+                Ok(Expression::Block(block, token.position))
+            }
+            TokenKind::LoopKeyword => {
+                // loop { ... break value; }
+                let block = self.parse_block()?;
+
+                Ok(Expression::Loop(Loop::new(block, token.position)))
+            }
+            TokenKind::Label => {
+                // 'label: loop { ... break 'label value; }
+                self.use_token(&[TokenKind::Colon])?;
+                let loop_token = self.use_token(&[TokenKind::LoopKeyword])?;
+                let block = self.parse_block()?;
+
+                let mut r#loop = Loop::new(block, loop_token.position);
+                r#loop.label = Some(Identifier::new(token));
+
+                Ok(Expression::Loop(r#loop))
+            }
             _ => Err(SyntaxError::ExpressionExpected {
                 position: token.position,
             }),
@@ -779,6 +1700,7 @@ fn is_binary_operator(kind: TokenKind) -> bool {
             | TokenKind::PipePipe
             | TokenKind::DotDot
             | TokenKind::DotDotEquals
+            | TokenKind::InKeyword
     )
 }
 
@@ -792,14 +1714,15 @@ fn get_binary_operator_precedence(kind: TokenKind) -> u32 {
     let groups: Vec<HashSet<&TokenKind>> = vec![
         hashset! {TokenKind::Slash, TokenKind::Star, TokenKind::Mod},
         hashset! {TokenKind::Plus, TokenKind::Minus},
+        hashset! {TokenKind::DotDot, TokenKind::DotDotEquals},
         hashset! {TokenKind::GreaterThan, TokenKind::GreaterThanEquals, TokenKind::LessThan, TokenKind::LessThanEquals},
+        hashset! {TokenKind::InKeyword},
         hashset! {TokenKind::EqualsEquals, TokenKind::ExclamationEquals},
         hashset! {TokenKind::Ampersand},
         hashset! {TokenKind::Circumflex},
         hashset! {TokenKind::Pipe},
         hashset! {TokenKind::AmpersandAmpersand},
         hashset! {TokenKind::PipePipe},
-        hashset! {TokenKind::DotDot, TokenKind::DotDotEquals},
     ];
 
     for (pos, hash_set) in groups.iter().enumerate() {
@@ -814,8 +1737,14 @@ fn get_binary_operator_precedence(kind: TokenKind) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::Parser;
+    use crate::lang::position::Positioned;
     use crate::lang::syntax::{
-        expressions::expression::Expression, shared::syntax_type::SyntaxType,
+        expressions::{
+            expression::{Expression, ExpressionMeta},
+            interpolation::InterpolationPart,
+        },
+        shared::syntax_type::SyntaxType,
+        syntax_error::SyntaxError,
         statements::statement::Statement,
         top_level_statements::top_level_statement::TopLevelStatement,
     };
@@ -850,6 +1779,7 @@ mod tests {
                     assert_eq!(fun.params_declaration.params.len(), 0);
                     assert!(fun.r#type.is_none());
                 }
+                _ => panic!("expected a function"),
             }
         }
 
@@ -867,10 +1797,161 @@ mod tests {
                     assert_eq!(fun.params_declaration.params.len(), 0);
                     assert!(fun.r#type.is_some());
                 }
+                _ => panic!("expected a function"),
+            }
+        }
+
+        let code = " fun max<T: numeric, U>(a: T, b: U) -> T { } ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_function_declaration();
+
+        assert!(result.is_ok());
+
+        if let Ok(statement) = result {
+            match statement {
+                TopLevelStatement::Function(fun) => {
+                    assert_eq!(fun.type_parameters.len(), 2);
+                    assert_eq!(fun.type_parameters[0].identifier.name, "T");
+                    assert_eq!(fun.type_parameters[0].bound.as_ref().unwrap().name, "numeric");
+                    assert!(fun.type_parameters[1].bound.is_none());
+                    assert_eq!(fun.params_declaration.params.len(), 2);
+                }
+                _ => panic!("expected a function"),
             }
         }
     }
 
+    #[test]
+    fn test_impl_declaration() {
+        let code = " impl vec<i32> { fun push(mut self, x: i32) { } fun len(self) -> i32 { } } ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_impl_declaration();
+
+        assert!(result.is_ok());
+
+        if let Ok(TopLevelStatement::Impl(r#impl)) = result {
+            assert_eq!(r#impl.functions.len(), 2);
+
+            let receiver = &r#impl.functions[0].params_declaration.params[0];
+            assert_eq!(receiver.identifier.name, "self");
+            assert!(receiver.mutable);
+            assert_eq!(receiver.r#type.to_string(), "vec<i32>");
+        } else {
+            panic!("expected an implementation");
+        }
+
+        // The receiver must be the first parameter.
+        let code = " impl i32 { fun double(x: i32) { } } ";
+        let mut parser = Parser::from_code(code);
+
+        assert!(parser.parse_impl_declaration().is_err());
+
+        let code = " impl Comparable for i32 { fun compare(self, other: Self) -> i32 { } } ";
+        let mut parser = Parser::from_code(code);
+
+        if let Ok(TopLevelStatement::Impl(r#impl)) = parser.parse_impl_declaration() {
+            assert_eq!(r#impl.r#trait.unwrap().name, "Comparable");
+            assert_eq!(r#impl.r#type.to_string(), "i32");
+        } else {
+            panic!("expected an implementation");
+        }
+    }
+
+    #[test]
+    fn test_trait_declaration() {
+        let code = " trait Comparable { fun compare(self, other: Self) -> i32; fun reset(mut self); } ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_trait_declaration();
+
+        assert!(result.is_ok());
+
+        if let Ok(TopLevelStatement::Trait(r#trait)) = result {
+            assert_eq!(r#trait.identifier.name, "Comparable");
+            assert_eq!(r#trait.signatures.len(), 2);
+
+            let receiver = &r#trait.signatures[1].params_declaration.params[0];
+            assert!(receiver.mutable);
+            assert_eq!(receiver.r#type.to_string(), "Self");
+            assert!(r#trait.signatures[1].r#type.is_none());
+        } else {
+            panic!("expected a trait");
+        }
+
+        // The methods of a trait have no body.
+        let code = " trait Comparable { fun compare(self) -> i32 { } } ";
+        let mut parser = Parser::from_code(code);
+
+        assert!(parser.parse_trait_declaration().is_err());
+    }
+
+    #[test]
+    fn test_extern_declaration() {
+        let code = " extern \"math.h\" fun cbrt(x: f64) -> f64; extern fun puts(s: cstring) -> i32; ";
+        let mut parser = Parser::from_code(code);
+
+        if let Ok(TopLevelStatement::Extern(r#extern)) = parser.parse_top_level_statement() {
+            assert_eq!(r#extern.header.unwrap().value, "math.h");
+            assert_eq!(r#extern.signature.identifier.name, "cbrt");
+            assert_eq!(r#extern.signature.params_declaration.params.len(), 1);
+        } else {
+            panic!("expected an extern function");
+        }
+
+        if let Ok(TopLevelStatement::Extern(r#extern)) = parser.parse_top_level_statement() {
+            assert!(r#extern.header.is_none());
+            assert_eq!(r#extern.signature.r#type.unwrap().to_string(), "i32");
+        } else {
+            panic!("expected an extern function");
+        }
+
+        // An extern function has no body.
+        let code = " extern fun puts(s: cstring) -> i32 { } ";
+        let mut parser = Parser::from_code(code);
+
+        assert!(parser.parse_extern_declaration().is_err());
+
+        let code = " export fun add(a: i32, b: i32) -> i32 { } ";
+        let mut parser = Parser::from_code(code);
+
+        match parser.parse_top_level_statement() {
+            Ok(TopLevelStatement::Function(function)) => assert!(function.export),
+            _ => panic!("expected a function"),
+        }
+    }
+
+    #[test]
+    fn test_defer_statement() {
+        let code = " defer free(grid); defer { println \"done\"; } ";
+        let mut parser = Parser::from_code(code);
+
+        match parser.parse_statement() {
+            Ok(Statement::Defer(defer)) => {
+                assert_eq!(defer.block.statements.len(), 1);
+                assert!(matches!(
+                    defer.block.statements[0],
+                    Statement::Expression(Expression::Identifier(..))
+                ));
+            }
+            _ => panic!("expected a defer statement"),
+        }
+
+        assert!(matches!(parser.parse_statement(), Ok(Statement::Defer(_))));
+
+        let code = " alloc<[i32; 100]>(n * 2) ";
+        let mut parser = Parser::from_code(code);
+
+        match parser.parse_expression(0) {
+            Ok(Expression::Alloc(alloc)) => {
+                assert_eq!(alloc.r#type.to_string(), "[i32; 100]");
+                assert!(matches!(alloc.count.as_ref(), Expression::Binary(_)));
+            }
+            _ => panic!("expected an alloc expression"),
+        }
+    }
+
     #[test]
     fn test_params_declaration() {
         let code = " a: i32, b: string, c: char ";
@@ -883,6 +1964,18 @@ mod tests {
         if let Ok(params) = result {
             assert_eq!(params.len(), 3);
         }
+
+        let code = " a: i32, mut b: [i32; 2] ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_params_declaration();
+
+        assert!(result.is_ok());
+
+        if let Ok(params) = result {
+            assert!(!params[0].mutable);
+            assert!(params[1].mutable);
+        }
     }
 
     #[test]
@@ -915,6 +2008,56 @@ mod tests {
                 None => {}
             }
         }
+
+        let code = " : [[i32; 2]] ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_type_optional();
+
+        assert!(result.is_ok());
+
+        if let Ok(Some(SyntaxType::Slice { r#type, .. })) = result {
+            assert!(matches!(r#type.as_ref(), SyntaxType::Array { .. }));
+        } else {
+            panic!("expected a slice type");
+        }
+
+        let code = " : [i32; N] ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_type_optional();
+
+        assert!(result.is_ok());
+
+        if let Ok(Some(SyntaxType::Array { size, .. })) = result {
+            assert_eq!(size.value, "N");
+        } else {
+            panic!("expected an array type");
+        }
+
+        let code = " : vec<vec<i32>> ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_type_optional();
+
+        assert!(result.is_ok());
+
+        if let Ok(Some(r#type)) = result {
+            assert_eq!(r#type.to_string(), "vec<vec<i32>>");
+        }
+
+        let code = " : ?[i32] ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_type_optional();
+
+        assert!(result.is_ok());
+
+        if let Ok(Some(SyntaxType::Optional { inner_type, .. })) = result {
+            assert!(matches!(inner_type.as_ref(), SyntaxType::Slice { .. }));
+        } else {
+            panic!("expected an optional type");
+        }
     }
 
     #[test]
@@ -968,11 +2111,44 @@ mod tests {
             match statement {
                 Statement::For(r#for) => {
                     assert_eq!(r#for.identifier.name, "i");
+                    assert!(r#for.index.is_none());
                     assert!(matches!(r#for.expression, Expression::Range(_)))
                 }
                 _ => {}
             };
         }
+
+        let code = " for i, x in array {  } ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_for_statement();
+
+        assert!(result.is_ok());
+
+        if let Ok(Statement::For(r#for)) = result {
+            assert_eq!(r#for.index.map(|index| index.name), Some(String::from("i")));
+            assert_eq!(r#for.identifier.name, "x");
+            assert!(matches!(r#for.expression, Expression::Identifier(..)))
+        }
+
+        let code = " for x in values(a) {  } ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_for_statement();
+
+        assert!(result.is_ok());
+
+        if let Ok(Statement::For(r#for)) = result {
+            assert!(matches!(
+                r#for.expression,
+                Expression::Identifier(_, Some(ExpressionMeta::Call(..)))
+            ))
+        }
+
+        let code = " for x in {  } ";
+        let mut parser = Parser::from_code(code);
+
+        assert!(parser.parse_for_statement().is_err());
     }
 
     #[test]
@@ -1001,6 +2177,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_labeled_statement() {
+        let code = " 'outer: for i in 0..3 { while a { break 'outer; continue; } } ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_statement();
+        assert!(result.is_ok());
+
+        if let Ok(Statement::For(r#for)) = result {
+            assert_eq!(r#for.label.unwrap().name, "outer");
+
+            match &r#for.block.statements[0] {
+                Statement::While(r#while) => {
+                    assert!(r#while.label.is_none());
+
+                    match &r#while.block.statements[..] {
+                        [Statement::Break(r#break), Statement::Continue(r#continue)] => {
+                            assert_eq!(r#break.label.as_ref().unwrap().name, "outer");
+                            assert!(r#continue.label.is_none());
+                        }
+                        _ => panic!("expected a break and a continue"),
+                    }
+                }
+                _ => panic!("expected a while loop"),
+            }
+        } else {
+            panic!("expected a for loop");
+        }
+
+        // Only loops can be labeled.
+        let code = " 'outer: if a { } ";
+        let mut parser = Parser::from_code(code);
+
+        assert!(parser.parse_statement().is_err());
+    }
+
+    #[test]
+    fn test_loop_expression() {
+        let code = " let found = 'search: loop { i += 1; break 'search i * 2; }; ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_statement();
+        assert!(result.is_ok());
+
+        if let Ok(Statement::Let(r#let)) = result {
+            if let Some(Expression::Loop(r#loop)) = r#let.expression {
+                assert_eq!(r#loop.label.unwrap().name, "search");
+
+                if let Statement::Break(r#break) = &r#loop.block.statements[1] {
+                    assert_eq!(r#break.label.as_ref().unwrap().name, "search");
+                    assert!(matches!(r#break.expression, Some(Expression::Binary(_))));
+                } else {
+                    panic!("expected a break");
+                }
+            } else {
+                panic!("expected a loop expression");
+            }
+        } else {
+            panic!("expected a let statement");
+        }
+
+        // Written as a statement, a loop needs no semicolon.
+        let code = " loop { break; } ";
+        let mut parser = Parser::from_code(code);
+
+        assert!(matches!(parser.parse_statement(), Ok(Statement::Loop(_))));
+    }
+
     #[test]
     fn test_if_statement() {
         let code = " if a == 2 { a += 2; } ";
@@ -1016,6 +2260,108 @@ mod tests {
                 assert!(r#if.r#else.is_none());
             }
         }
+
+        let code = " if let value = find(a) { } else { } ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_if_statement();
+        assert!(result.is_ok());
+
+        if let Ok(Statement::If(r#if)) = result {
+            assert_eq!(
+                r#if.binding.map(|binding| binding.name),
+                Some("value".to_string())
+            );
+            assert!(r#if.r#else.is_some());
+        }
+
+        // The `else if` is the only statement of the else block.
+        let code = " if a == 1 { } else if let value = find(a) { } else { a = 0; } ";
+        let mut parser = Parser::from_code(code);
+
+        if let Ok(Statement::If(r#if)) = parser.parse_if_statement() {
+            let block = r#if.r#else.unwrap().block;
+            assert_eq!(block.statements.len(), 1);
+
+            if let Statement::If(else_if) = &block.statements[0] {
+                assert!(else_if.binding.is_some());
+                assert!(else_if.r#else.is_some());
+            } else {
+                panic!("expected an else if");
+            }
+        } else {
+            panic!("expected an if statement");
+        }
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let code = " \"{name} has {count:04} edges {{}}\" ";
+        let mut parser = Parser::from_code(code);
+
+        if let Ok(Expression::Interpolation(interpolation)) = parser.parse_expression(0) {
+            assert_eq!(interpolation.parts.len(), 4);
+
+            let values: Vec<_> = interpolation.get_values().collect();
+            assert!(matches!(values[0].0, Expression::Identifier(..)));
+            assert_eq!(values[0].0.get_position().column, 4);
+            assert!(values[1].1.zero);
+            assert_eq!(values[1].1.width, Some(4));
+
+            if let InterpolationPart::Text(text) = &interpolation.parts[3] {
+                assert_eq!(text, " edges {}");
+            } else {
+                panic!("expected a text");
+            }
+        } else {
+            panic!("expected an interpolation");
+        }
+
+        let code = " \"{count\" ";
+        let mut parser = Parser::from_code(code);
+
+        assert!(matches!(
+            parser.parse_expression(0),
+            Err(SyntaxError::InvalidInterpolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_if_expression() {
+        let code = " let b = if a < 0 { -1 } else if a == 0 { 0 } else { let c = a; c * 2 }; ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_statement();
+        assert!(result.is_ok());
+
+        if let Ok(Statement::Let(r#let)) = result {
+            if let Some(Expression::If(r#if)) = r#let.expression {
+                assert!(r#if.block.get_value().is_some());
+
+                // The `else if` is the value of the else block.
+                let r#else = r#if.r#else.unwrap();
+                if let Some(Expression::If(else_if)) = r#else.block.get_value() {
+                    let block = &else_if.r#else.as_ref().unwrap().block;
+                    assert_eq!(block.statements.len(), 1);
+                    assert!(matches!(block.expression.as_deref(), Some(Expression::Binary(_))));
+                } else {
+                    panic!("expected an else if");
+                }
+            } else {
+                panic!("expected an if expression");
+            }
+        } else {
+            panic!("expected a let statement");
+        }
+
+        let code = " let c = { let t = a + 1; t * t }; ";
+        let mut parser = Parser::from_code(code);
+
+        if let Ok(Statement::Let(r#let)) = parser.parse_statement() {
+            assert!(matches!(r#let.expression, Some(Expression::Block(..))));
+        } else {
+            panic!("expected a let statement");
+        }
     }
 
     #[test]
@@ -1063,8 +2409,32 @@ mod tests {
             if let Statement::Let(r#let) = statement {
                 assert_eq!(r#let.identifier.name, "x");
                 assert!(r#let.r#type.is_some());
+                assert!(!r#let.mutable);
             }
         }
+
+        let code = " let mut x: i32; ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_variable_declaration_statement();
+        assert!(result.is_ok());
+
+        if let Ok(Statement::Let(r#let)) = result {
+            assert_eq!(r#let.identifier.name, "x");
+            assert!(r#let.mutable);
+            assert!(r#let.expression.is_none());
+        }
+
+        let code = " let (q, r) = divmod(a, b).0; ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_variable_declaration_statement();
+        assert!(result.is_ok());
+
+        if let Ok(Statement::Let(r#let)) = result {
+            assert_eq!(r#let.elements.len(), 2);
+            assert_eq!(r#let.elements[1].name, "r");
+        }
     }
 
     #[test]
@@ -1127,5 +2497,160 @@ mod tests {
         if let Ok(expression) = result {
             assert!(matches!(expression, Expression::Range(_)));
         }
+
+        let code = " 10..0 step -2 ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+        assert!(result.is_ok());
+
+        if let Ok(Expression::Range(range)) = result {
+            assert!(matches!(range.step.as_deref(), Some(Expression::Unary(_))));
+        }
+
+        let code = " x in 0..n + 1 && y ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+        assert!(result.is_ok());
+
+        if let Ok(Expression::Binary(binary)) = result {
+            assert_eq!(binary.operator.token.value, "&&");
+            assert!(matches!(binary.left.as_ref(), Expression::Binary(_)));
+        }
+
+        let code = " fun(a: i32, b: i32) -> bool { return a < b; } ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+        assert!(result.is_ok());
+
+        if let Ok(Expression::Lambda(lambda)) = result {
+            assert_eq!(lambda.params_declaration.params.len(), 2);
+            assert!(lambda.r#type.is_some());
+        }
+
+        let code = " parse(text)? + 1 ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+        assert!(result.is_ok());
+
+        if let Ok(Expression::Binary(binary)) = result {
+            match binary.left.as_ref() {
+                Expression::Identifier(_, Some(ExpressionMeta::Call(_, meta, _))) => {
+                    assert!(matches!(meta.as_ref(), Some(ExpressionMeta::Try(..))))
+                }
+                _ => panic!("expected a call followed by '?'"),
+            }
+        } else {
+            panic!("expected a binary expression");
+        }
+
+        let code = " stack.push(1).0 ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+        assert!(result.is_ok());
+
+        match result {
+            Ok(Expression::Identifier(
+                _,
+                Some(ExpressionMeta::Method(identifier, arguments, meta, _)),
+            )) => {
+                assert_eq!(identifier.name, "push");
+                assert_eq!(arguments.len(), 1);
+                assert!(matches!(meta.as_ref(), Some(ExpressionMeta::Field(..))));
+            }
+            _ => panic!("expected a method call"),
+        }
+    }
+
+    #[test]
+    fn test_array_expression() {
+        let code = " [1, 2, 3] ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+        assert!(result.is_ok());
+
+        if let Ok(Expression::Array(array, _)) = result {
+            assert_eq!(array.expressions.len(), 3);
+            assert!(array.size.is_none());
+        }
+
+        let code = " [false; 10] ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+        assert!(result.is_ok());
+
+        if let Ok(Expression::Array(array, _)) = result {
+            assert_eq!(array.expressions.len(), 1);
+            assert_eq!(array.size.map(|size| size.value), Some(String::from("10")));
+        }
+
+        // The size may be a size parameter of a generic function.
+        let code = " [0; N] ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+
+        if let Ok(Expression::Array(array, _)) = result {
+            assert_eq!(array.size.map(|size| size.value), Some(String::from("N")));
+        } else {
+            panic!("expected an array");
+        }
+
+        let code = " [0; 1.5] ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+        assert!(matches!(result, Err(SyntaxError::UnexpectedToken { .. })));
+    }
+
+    #[test]
+    fn test_tuple_expression() {
+        let code = " (1, true, \"a\") ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+
+        if let Ok(Expression::Tuple(tuple, None)) = result {
+            assert_eq!(tuple.expressions.len(), 3);
+        } else {
+            panic!("expected a tuple");
+        }
+
+        // A parenthesized expression without commas is not a tuple.
+        let code = " (1) ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+        assert!(matches!(result, Ok(Expression::Parenthesized(..))));
+
+        // The fields of a nested tuple are read as a single number by the lexer.
+        let code = " a.0.1 ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_expression(0);
+
+        match result {
+            Ok(Expression::Identifier(_, Some(ExpressionMeta::Field(first, meta, _)))) => {
+                assert_eq!(first.value, "0");
+
+                match meta.as_ref() {
+                    Some(ExpressionMeta::Field(second, ..)) => assert_eq!(second.value, "1"),
+                    _ => panic!("expected a second field"),
+                }
+            }
+            _ => panic!("expected a field access"),
+        }
+
+        let code = " (i32, bool) ";
+        let mut parser = Parser::from_code(code);
+
+        let result = parser.parse_type();
+        assert!(matches!(result, Ok(SyntaxType::Tuple { ref types, .. }) if types.len() == 2));
     }
 }
@@ -1,20 +1,51 @@
 use uuid::Uuid;
 
-use crate::lang::syntax::{statements::statement::Statement, tree_display::TreeDisplay};
+use crate::lang::syntax::{
+    expressions::expression::Expression, statements::statement::Statement,
+    tree_display::TreeDisplay,
+};
 
 #[derive(Clone, Debug)]
 pub struct Block {
     pub id: Uuid,
     pub statements: Vec<Statement>,
+
+    /// The value of the block, written after its statements without a semicolon, as in
+    /// `{ let a = 2; a * a }`. It is only given to blocks used as expressions.
+    pub expression: Option<Box<Expression>>,
 }
 
 impl Block {
     pub fn new(statements: Vec<Statement>) -> Self {
+        Self::with_expression(statements, None)
+    }
+
+    pub fn with_expression(statements: Vec<Statement>, expression: Option<Expression>) -> Self {
         Self {
             id: Uuid::new_v4(),
             statements,
+            expression: expression.map(Box::new),
+        }
+    }
+
+    /// The value of the block when it holds nothing else, as in `{ a }`.
+    pub fn get_value(&self) -> Option<&Expression> {
+        if self.statements.is_empty() {
+            self.expression.as_deref()
+        } else {
+            None
         }
     }
+
+    /// Whether the block ends with a `return`, `break` or `continue`, so that it is never
+    /// left through its end.
+    pub fn diverges(&self) -> bool {
+        self.expression.is_none()
+            && matches!(
+                self.statements.last(),
+                Some(Statement::Return(_) | Statement::Break(_) | Statement::Continue(_))
+            )
+    }
 }
 
 impl TreeDisplay for Block {
@@ -23,5 +54,9 @@ impl TreeDisplay for Block {
         for statement in &self.statements {
             statement.display(layer + 1);
         }
+
+        if let Some(expression) = &self.expression {
+            expression.display(layer + 1);
+        }
     }
 }
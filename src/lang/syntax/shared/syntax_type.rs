@@ -8,11 +8,19 @@ pub enum SyntaxType {
     Simple {
         identifier: Token,
     },
+    Generic {
+        identifier: Token,
+        arguments: Vec<SyntaxType>,
+    },
     Array {
         r#type: Box<SyntaxType>,
         size: Token,
         position: Position,
     },
+    Slice {
+        r#type: Box<SyntaxType>,
+        position: Position,
+    },
     Reference {
         inner_type: Box<SyntaxType>,
         position: Position,
@@ -22,6 +30,14 @@ pub enum SyntaxType {
         r#type: Box<SyntaxType>,
         position: Position,
     },
+    Tuple {
+        types: Vec<SyntaxType>,
+        position: Position,
+    },
+    Optional {
+        inner_type: Box<SyntaxType>,
+        position: Position,
+    },
 }
 
 impl SyntaxType {
@@ -29,6 +45,13 @@ impl SyntaxType {
         Self::Simple { identifier }
     }
 
+    pub fn new_generic(identifier: Token, arguments: Vec<SyntaxType>) -> Self {
+        Self::Generic {
+            identifier,
+            arguments,
+        }
+    }
+
     pub fn new_array(r#type: SyntaxType, size: Token, position: Position) -> Self {
         Self::Array {
             r#type: Box::new(r#type),
@@ -37,6 +60,13 @@ impl SyntaxType {
         }
     }
 
+    pub fn new_slice(r#type: SyntaxType, position: Position) -> Self {
+        Self::Slice {
+            r#type: Box::new(r#type),
+            position,
+        }
+    }
+
     pub fn new_function(params: Vec<SyntaxType>, r#type: SyntaxType, position: Position) -> Self {
         Self::Function {
             params,
@@ -45,21 +75,114 @@ impl SyntaxType {
         }
     }
 
+    pub fn new_tuple(types: Vec<SyntaxType>, position: Position) -> Self {
+        Self::Tuple { types, position }
+    }
+
+    pub fn new_optional(inner_type: SyntaxType, position: Position) -> Self {
+        Self::Optional {
+            inner_type: Box::new(inner_type),
+            position,
+        }
+    }
+
     pub fn new_reference(inner_type: SyntaxType, position: Position) -> Self {
         Self::Reference {
             inner_type: Box::new(inner_type),
             position,
         }
     }
+
+    /// Whether the given name is used as the size of an array within this type, as in `[i32; N]`.
+    pub fn uses_size(&self, name: &str) -> bool {
+        match self {
+            Self::Simple { .. } => false,
+            Self::Generic { arguments, .. } => {
+                arguments.iter().any(|r#type| r#type.uses_size(name))
+            }
+            Self::Array { r#type, size, .. } => size.value == name || r#type.uses_size(name),
+            Self::Slice { r#type, .. } => r#type.uses_size(name),
+            Self::Reference { inner_type, .. } | Self::Optional { inner_type, .. } => {
+                inner_type.uses_size(name)
+            }
+            Self::Function { params, r#type, .. } => {
+                params.iter().any(|param| param.uses_size(name)) || r#type.uses_size(name)
+            }
+            Self::Tuple { types, .. } => types.iter().any(|r#type| r#type.uses_size(name)),
+        }
+    }
+
+    /// Replaces the type with the given name within this type, as `Self` is replaced by the type
+    /// of an implementation.
+    pub fn replace(&self, name: &str, replacement: &SyntaxType) -> SyntaxType {
+        let replace_all = |types: &Vec<SyntaxType>| -> Vec<SyntaxType> {
+            types
+                .iter()
+                .map(|r#type| r#type.replace(name, replacement))
+                .collect()
+        };
+
+        match self {
+            Self::Simple { identifier } if identifier.value == name => replacement.clone(),
+            Self::Simple { .. } => self.clone(),
+            Self::Generic {
+                identifier,
+                arguments,
+            } => Self::Generic {
+                identifier: identifier.clone(),
+                arguments: replace_all(arguments),
+            },
+            Self::Array {
+                r#type,
+                size,
+                position,
+            } => Self::Array {
+                r#type: Box::new(r#type.replace(name, replacement)),
+                size: size.clone(),
+                position: *position,
+            },
+            Self::Slice { r#type, position } => Self::Slice {
+                r#type: Box::new(r#type.replace(name, replacement)),
+                position: *position,
+            },
+            Self::Reference {
+                inner_type,
+                position,
+            } => Self::Reference {
+                inner_type: Box::new(inner_type.replace(name, replacement)),
+                position: *position,
+            },
+            Self::Optional {
+                inner_type,
+                position,
+            } => Self::Optional {
+                inner_type: Box::new(inner_type.replace(name, replacement)),
+                position: *position,
+            },
+            Self::Function {
+                params,
+                r#type,
+                position,
+            } => Self::Function {
+                params: replace_all(params),
+                r#type: Box::new(r#type.replace(name, replacement)),
+                position: *position,
+            },
+            Self::Tuple { types, position } => Self::Tuple {
+                types: replace_all(types),
+                position: *position,
+            },
+        }
+    }
 }
 
 impl Positioned for SyntaxType {
     fn get_position(&self) -> Position {
         match &self {
-            Self::Simple { identifier } => identifier.position,
-            Self::Reference { position, .. } => position.clone(),
-            Self::Array { position, .. } => position.clone(),
-            Self::Function { position, .. } => position.clone(),
+            Self::Simple { identifier } | Self::Generic { identifier, .. } => identifier.position,
+            Self::Reference { position, .. } | Self::Optional { position, .. } => *position,
+            Self::Array { position, .. } | Self::Slice { position, .. } => *position,
+            Self::Function { position, .. } | Self::Tuple { position, .. } => *position,
         }
     }
 }
@@ -68,10 +191,24 @@ impl ToString for SyntaxType {
     fn to_string(&self) -> String {
         match &self {
             Self::Simple { identifier } => identifier.value.clone(),
+            Self::Generic {
+                identifier,
+                arguments,
+            } => format!(
+                "{}<{}>",
+                identifier.value,
+                arguments
+                    .iter()
+                    .map(|argument| argument.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
             Self::Reference { inner_type, .. } => format!("ref {}", inner_type.to_string()),
+            Self::Optional { inner_type, .. } => format!("?{}", inner_type.to_string()),
             Self::Array { r#type, size, .. } => {
                 format!("[{}; {}]", r#type.to_string(), size.value.clone())
             }
+            Self::Slice { r#type, .. } => format!("[{}]", r#type.to_string()),
             Self::Function { params, r#type, .. } => {
                 let mut str = String::from("(");
 
@@ -87,6 +224,14 @@ impl ToString for SyntaxType {
 
                 str
             }
+            Self::Tuple { types, .. } => format!(
+                "({})",
+                types
+                    .iter()
+                    .map(|r#type| r#type.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
         }
     }
 }
@@ -1,16 +1,33 @@
 use crate::lang::{
     position::{Position, Positioned},
-    syntax::tree_display::TreeDisplay,
+    syntax::{
+        expressions::expression::Expression, shared::identifier::Identifier,
+        tree_display::TreeDisplay,
+    },
 };
 
 #[derive(Clone, Debug)]
 pub struct Break {
+    /// The label of the loop it refers to, as in `break 'outer;`, or none for the innermost
+    /// loop.
+    pub label: Option<Identifier>,
+
+    /// The value given to the `loop` expression it leaves, as in `break i;`.
+    pub expression: Option<Expression>,
     position: Position,
 }
 
 impl Break {
-    pub fn new(position: Position) -> Self {
-        Self { position }
+    pub fn new(
+        label: Option<Identifier>,
+        expression: Option<Expression>,
+        position: Position,
+    ) -> Self {
+        Self {
+            label,
+            expression,
+            position,
+        }
     }
 }
 
@@ -22,6 +39,13 @@ impl Positioned for Break {
 
 impl TreeDisplay for Break {
     fn display(&self, layer: usize) {
-        println!("{}BreakStatement", "  ".repeat(layer));
+        match &self.label {
+            Some(label) => println!("{}BreakStatement ({})", "  ".repeat(layer), label.name),
+            None => println!("{}BreakStatement", "  ".repeat(layer)),
+        }
+
+        if let Some(expression) = &self.expression {
+            expression.display(layer + 1);
+        }
     }
 }
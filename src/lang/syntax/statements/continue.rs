@@ -1,16 +1,19 @@
 use crate::lang::{
     position::{Position, Positioned},
-    syntax::tree_display::TreeDisplay,
+    syntax::{shared::identifier::Identifier, tree_display::TreeDisplay},
 };
 
 #[derive(Clone, Debug)]
 pub struct Continue {
+    /// The label of the loop it refers to, as in `continue 'outer;`, or none for the innermost
+    /// loop.
+    pub label: Option<Identifier>,
     position: Position,
 }
 
 impl Continue {
-    pub fn new(position: Position) -> Self {
-        Self { position }
+    pub fn new(label: Option<Identifier>, position: Position) -> Self {
+        Self { label, position }
     }
 }
 
@@ -22,6 +25,9 @@ impl Positioned for Continue {
 
 impl TreeDisplay for Continue {
     fn display(&self, layer: usize) {
-        println!("{}ContinueStatement", "  ".repeat(layer));
+        match &self.label {
+            Some(label) => println!("{}ContinueStatement ({})", "  ".repeat(layer), label.name),
+            None => println!("{}ContinueStatement", "  ".repeat(layer)),
+        }
     }
 }
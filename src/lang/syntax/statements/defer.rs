@@ -0,0 +1,31 @@
+use crate::lang::{
+    position::{Position, Positioned},
+    syntax::{shared::block::Block, tree_display::TreeDisplay},
+};
+
+/// A block that runs when the enclosing block is left, however it is left, as in
+/// `defer free(grid);`. A single statement is kept as the only statement of a block.
+#[derive(Clone, Debug)]
+pub struct Defer {
+    pub block: Block,
+    position: Position,
+}
+
+impl Defer {
+    pub fn new(block: Block, position: Position) -> Self {
+        Self { block, position }
+    }
+}
+
+impl Positioned for Defer {
+    fn get_position(&self) -> Position {
+        self.position
+    }
+}
+
+impl TreeDisplay for Defer {
+    fn display(&self, layer: usize) {
+        println!("{}DeferStatement", "  ".repeat(layer));
+        self.block.display(layer + 1);
+    }
+}
@@ -1,16 +1,25 @@
 use crate::lang::syntax::{
-    expressions::expression::Expression, shared::block::Block, tree_display::TreeDisplay,
+    expressions::expression::Expression,
+    shared::{block::Block, identifier::Identifier},
+    tree_display::TreeDisplay,
 };
 
 #[derive(Clone, Debug)]
 pub struct DoWhile {
     pub block: Block,
     pub expression: Expression,
+
+    /// The label given to the loop, as in `'outer: do ...`.
+    pub label: Option<Identifier>,
 }
 
 impl DoWhile {
     pub fn new(block: Block, expression: Expression) -> Self {
-        Self { block, expression }
+        Self {
+            block,
+            expression,
+            label: None,
+        }
     }
 }
 
@@ -6,28 +6,47 @@ use crate::lang::syntax::{
 
 #[derive(Clone, Debug)]
 pub struct For {
+    pub index: Option<Identifier>,
     pub identifier: Identifier,
     pub expression: Expression,
     pub block: Block,
+
+    /// The label given to the loop, as in `'outer: for ...`.
+    pub label: Option<Identifier>,
 }
 
 impl For {
-    pub fn new(identifier: Identifier, expression: Expression, block: Block) -> Self {
+    pub fn new(
+        index: Option<Identifier>,
+        identifier: Identifier,
+        expression: Expression,
+        block: Block,
+    ) -> Self {
         Self {
+            index,
             identifier,
             expression,
             block,
+            label: None,
         }
     }
 }
 
 impl TreeDisplay for For {
     fn display(&self, layer: usize) {
-        println!(
-            "{}ForStatement ({})",
-            "  ".repeat(layer),
-            self.identifier.name
-        );
+        match &self.index {
+            Some(index) => println!(
+                "{}ForStatement ({}, {})",
+                "  ".repeat(layer),
+                index.name,
+                self.identifier.name
+            ),
+            None => println!(
+                "{}ForStatement ({})",
+                "  ".repeat(layer),
+                self.identifier.name
+            ),
+        }
 
         self.expression.display(layer + 1);
         self.block.display(layer + 1);
@@ -1,5 +1,10 @@
-use crate::lang::syntax::{
-    expressions::expression::Expression, shared::block::Block, tree_display::TreeDisplay,
+use crate::lang::{
+    position::{Position, Positioned},
+    syntax::{
+        expressions::expression::Expression,
+        shared::{block::Block, identifier::Identifier},
+        tree_display::TreeDisplay,
+    },
 };
 
 #[derive(Clone, Debug)]
@@ -22,24 +27,59 @@ impl TreeDisplay for Else {
 
 #[derive(Clone, Debug)]
 pub struct If {
+    /// The name given to the value of an optional in `if let value = expression`.
+    pub binding: Option<Identifier>,
     pub expression: Expression,
     pub block: Block,
     pub r#else: Option<Else>,
+    position: Position,
 }
 
 impl If {
-    pub fn new(expression: Expression, block: Block, r#else: Option<Else>) -> Self {
+    pub fn new(
+        binding: Option<Identifier>,
+        expression: Expression,
+        block: Block,
+        r#else: Option<Else>,
+        position: Position,
+    ) -> Self {
         Self {
+            binding,
             expression,
             block,
             r#else,
+            position,
+        }
+    }
+
+    /// The values of both branches when they hold nothing else, as in `if a { b } else { c }`,
+    /// which need no statements to be chosen between.
+    pub fn get_values(&self) -> Option<(&Expression, &Expression)> {
+        if self.binding.is_some() {
+            return None;
+        }
+
+        match (self.block.get_value(), &self.r#else) {
+            (Some(value), Some(r#else)) => Some((value, r#else.block.get_value()?)),
+            _ => None,
         }
     }
 }
 
+impl Positioned for If {
+    fn get_position(&self) -> Position {
+        self.position
+    }
+}
+
 impl TreeDisplay for If {
     fn display(&self, layer: usize) {
         println!("{}IfStatement", "  ".repeat(layer));
+
+        if let Some(binding) = &self.binding {
+            binding.display(layer + 1);
+        }
+
         self.expression.display(layer + 1);
         self.block.display(layer + 1);
 
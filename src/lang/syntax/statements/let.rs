@@ -1,3 +1,6 @@
+use crate::lang::lexer::token::Token;
+use crate::lang::lexer::token_kind::TokenKind;
+use crate::lang::position::Position;
 use crate::lang::syntax::expressions::expression::Expression;
 use crate::lang::syntax::shared::identifier::Identifier;
 
@@ -6,28 +9,71 @@ use crate::lang::syntax::tree_display::TreeDisplay;
 
 #[derive(Clone, Debug)]
 pub struct Let {
+    pub mutable: bool,
     pub identifier: Identifier,
     pub r#type: Option<SyntaxType>,
     pub expression: Option<Expression>,
+
+    /// The variables that receive the elements of a tuple, as in `let (q, r) = divmod(a, b);`.
+    /// The tuple itself is stored in a hidden variable named by `identifier`.
+    pub elements: Vec<Identifier>,
 }
 
 impl Let {
     pub fn new(
+        mutable: bool,
         identifier: Identifier,
         r#type: Option<SyntaxType>,
         expression: Option<Expression>,
     ) -> Self {
         Self {
+            mutable,
             identifier,
             r#type,
             expression,
+            elements: vec![],
+        }
+    }
+
+    pub fn new_destructuring(
+        mutable: bool,
+        elements: Vec<Identifier>,
+        position: Position,
+        r#type: Option<SyntaxType>,
+        expression: Expression,
+    ) -> Self {
+        let name = format!("__tuple_{}_{}", position.line, position.column);
+
+        Self {
+            mutable,
+            identifier: Identifier::new(Token::new(TokenKind::Identifier, position, &name)),
+            r#type,
+            expression: Some(expression),
+            elements,
         }
     }
 }
 
 impl TreeDisplay for Let {
     fn display(&self, layer: usize) {
-        let identifier_name = self.identifier.name.clone();
+        let name = if self.elements.is_empty() {
+            self.identifier.name.clone()
+        } else {
+            format!(
+                "({})",
+                self.elements
+                    .iter()
+                    .map(|element| element.name.clone())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+
+        let identifier_name = if self.mutable {
+            format!("mut {}", name)
+        } else {
+            name
+        };
 
         if let Some(r#type) = &self.r#type {
             println!(
@@ -0,0 +1,41 @@
+use crate::lang::{
+    position::{Position, Positioned},
+    syntax::{
+        shared::{block::Block, identifier::Identifier},
+        tree_display::TreeDisplay,
+    },
+};
+
+/// A loop that only ends through a `break`. Written as an expression, its value is the one
+/// given to the `break` that leaves it: `let found = loop { ... break i; };`.
+#[derive(Clone, Debug)]
+pub struct Loop {
+    pub block: Block,
+
+    /// The label given to the loop, as in `'outer: loop ...`.
+    pub label: Option<Identifier>,
+    position: Position,
+}
+
+impl Loop {
+    pub fn new(block: Block, position: Position) -> Self {
+        Self {
+            block,
+            label: None,
+            position,
+        }
+    }
+}
+
+impl Positioned for Loop {
+    fn get_position(&self) -> Position {
+        self.position
+    }
+}
+
+impl TreeDisplay for Loop {
+    fn display(&self, layer: usize) {
+        println!("{}LoopStatement", "  ".repeat(layer));
+        self.block.display(layer + 1);
+    }
+}
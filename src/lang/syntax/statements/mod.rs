@@ -1,10 +1,12 @@
 pub mod assignment;
 pub mod r#break;
 pub mod r#continue;
+pub mod defer;
 pub mod do_while;
 pub mod r#for;
 pub mod r#if;
 pub mod r#let;
+pub mod r#loop;
 pub mod print;
 pub mod r#return;
 pub mod statement;
@@ -3,13 +3,16 @@ use crate::lang::syntax::{expressions::expression::Expression, tree_display::Tre
 #[derive(Clone, Debug)]
 pub struct Print {
     pub new_line: bool,
+    /// Whether it is an `eprint` or `eprintln`, which write to the standard error.
+    pub stderr: bool,
     pub expressions: Vec<Expression>,
 }
 
 impl Print {
-    pub fn new(new_line: bool, expressions: Vec<Expression>) -> Self {
+    pub fn new(new_line: bool, stderr: bool, expressions: Vec<Expression>) -> Self {
         Self {
             new_line,
+            stderr,
             expressions,
         }
     }
@@ -1,4 +1,5 @@
 use super::assignment::Assignment;
+use super::defer::Defer;
 use super::do_while::DoWhile;
 use super::print::Print;
 use super::r#break::Break;
@@ -6,6 +7,7 @@ use super::r#continue::Continue;
 use super::r#for::For;
 use super::r#if::If;
 use super::r#let::Let;
+use super::r#loop::Loop;
 use super::r#return::Return;
 use super::r#while::While;
 
@@ -22,9 +24,11 @@ pub enum Statement {
     While(While),
     DoWhile(DoWhile),
     For(For),
+    Loop(Loop),
     Break(Break),
     Continue(Continue),
     Print(Print),
+    Defer(Defer),
     Assignment(Assignment),
     Expression(Expression),
 }
@@ -41,9 +45,11 @@ impl TreeDisplay for Statement {
             Self::While(r#while) => r#while.display(layer),
             Self::DoWhile(do_while) => do_while.display(layer),
             Self::For(r#for) => r#for.display(layer),
+            Self::Loop(r#loop) => r#loop.display(layer),
             Self::Break(r#break) => r#break.display(layer),
             Self::Continue(r#continue) => r#continue.display(layer),
             Self::Print(print) => print.display(layer),
+            Self::Defer(defer) => defer.display(layer),
         }
     }
 }
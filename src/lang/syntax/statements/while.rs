@@ -1,16 +1,25 @@
 use crate::lang::syntax::{
-    expressions::expression::Expression, shared::block::Block, tree_display::TreeDisplay,
+    expressions::expression::Expression,
+    shared::{block::Block, identifier::Identifier},
+    tree_display::TreeDisplay,
 };
 
 #[derive(Clone, Debug)]
 pub struct While {
     pub expression: Expression,
     pub block: Block,
+
+    /// The label given to the loop, as in `'outer: while ...`.
+    pub label: Option<Identifier>,
 }
 
 impl While {
     pub fn new(expression: Expression, block: Block) -> Self {
-        Self { expression, block }
+        Self {
+            expression,
+            block,
+            label: None,
+        }
     }
 }
 
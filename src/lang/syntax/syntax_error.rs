@@ -14,6 +14,10 @@ pub enum SyntaxError {
     ExpressionExpected {
         position: Position,
     },
+    // "{count" or "{value:?}"
+    InvalidInterpolation {
+        position: Position,
+    },
 }
 
 impl Display for SyntaxError {
@@ -40,6 +44,13 @@ impl Display for SyntaxError {
                     position.line, position.column
                 )
             }
+            SyntaxError::InvalidInterpolation { position } => {
+                write!(
+                    f,
+                    "Invalid interpolation at Line {} and Column {}",
+                    position.line, position.column
+                )
+            }
         }
     }
 }
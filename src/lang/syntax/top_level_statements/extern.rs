@@ -0,0 +1,29 @@
+use crate::lang::{lexer::token::Token, syntax::tree_display::TreeDisplay};
+
+use super::r#trait::Signature;
+
+/// A function implemented in C, declared with its signature: `extern fun id(params) -> type;`.
+/// The header given as `extern "header.h"` declares the function, which is otherwise declared
+/// from the signature.
+#[derive(Clone, Debug)]
+pub struct Extern {
+    pub header: Option<Token>,
+    pub signature: Signature,
+}
+
+impl Extern {
+    pub fn new(header: Option<Token>, signature: Signature) -> Self {
+        Self { header, signature }
+    }
+}
+
+impl TreeDisplay for Extern {
+    fn display(&self, layer: usize) {
+        match &self.header {
+            Some(header) => println!("{}Extern (\"{}\")", "  ".repeat(layer), header.value),
+            None => println!("{}Extern", "  ".repeat(layer)),
+        }
+
+        self.signature.display(layer + 1);
+    }
+}
@@ -5,27 +5,64 @@ use crate::lang::syntax::{
 
 #[derive(Clone, Debug)]
 pub struct ParamDeclaration {
+    pub mutable: bool,
     pub identifier: Identifier,
     pub r#type: SyntaxType,
 }
 
 impl ParamDeclaration {
-    pub fn new(identifier: Identifier, r#type: SyntaxType) -> Self {
-        Self { identifier, r#type }
+    pub fn new(mutable: bool, identifier: Identifier, r#type: SyntaxType) -> Self {
+        Self {
+            mutable,
+            identifier,
+            r#type,
+        }
     }
 }
 
 impl TreeDisplay for ParamDeclaration {
     fn display(&self, layer: usize) {
         println!(
-            "{}ParamDeclaration ({}: {})",
+            "{}ParamDeclaration ({}{}: {})",
             "  ".repeat(layer),
+            if self.mutable { "mut " } else { "" },
             self.identifier.name,
             self.r#type.to_string()
         );
     }
 }
 
+/// A type parameter of a generic function, optionally restricted by a bound: `T` or `T: numeric`.
+#[derive(Clone, Debug)]
+pub struct TypeParameter {
+    pub identifier: Identifier,
+    pub bound: Option<Identifier>,
+}
+
+impl TypeParameter {
+    pub fn new(identifier: Identifier, bound: Option<Identifier>) -> Self {
+        Self { identifier, bound }
+    }
+}
+
+impl TreeDisplay for TypeParameter {
+    fn display(&self, layer: usize) {
+        match &self.bound {
+            Some(bound) => println!(
+                "{}TypeParameter ({}: {})",
+                "  ".repeat(layer),
+                self.identifier.name,
+                bound.name
+            ),
+            None => println!(
+                "{}TypeParameter ({})",
+                "  ".repeat(layer),
+                self.identifier.name
+            ),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ParamsDeclaration {
     pub params: Vec<ParamDeclaration>,
@@ -53,7 +90,10 @@ impl TreeDisplay for ParamsDeclaration {
 
 #[derive(Clone, Debug)]
 pub struct Function {
+    /// Whether the function is declared with `export`, so that C code can call it by its name.
+    pub export: bool,
     pub identifier: Identifier,
+    pub type_parameters: Vec<TypeParameter>,
     pub params_declaration: ParamsDeclaration,
     pub r#type: Option<SyntaxType>,
     pub block: Block,
@@ -61,13 +101,17 @@ pub struct Function {
 
 impl Function {
     pub fn new(
+        export: bool,
         identifier: Identifier,
+        type_parameters: Vec<TypeParameter>,
         params_declaration: ParamsDeclaration,
         r#type: Option<SyntaxType>,
         block: Block,
     ) -> Self {
         Self {
+            export,
             identifier,
+            type_parameters,
             params_declaration,
             r#type,
             block,
@@ -78,21 +122,32 @@ impl Function {
 impl TreeDisplay for Function {
     fn display(&self, layer: usize) {
         let id = self.identifier.name.clone();
+        let export = if self.export { "export " } else { "" };
 
         match self.r#type.as_ref() {
             Some(r#type) => {
                 println!(
-                    "{}FunctionDeclaration ({}: {})",
+                    "{}FunctionDeclaration ({}{}: {})",
                     "  ".repeat(layer),
+                    export,
                     id,
                     r#type.to_string()
                 );
             }
             None => {
-                println!("{}FunctionDeclaration ({})", "  ".repeat(layer), id,);
+                println!(
+                    "{}FunctionDeclaration ({}{})",
+                    "  ".repeat(layer),
+                    export,
+                    id
+                );
             }
         };
 
+        for type_parameter in &self.type_parameters {
+            type_parameter.display(layer + 1);
+        }
+
         self.params_declaration.display(layer + 1);
         self.block.display(layer + 1);
     }
@@ -0,0 +1,43 @@
+use crate::lang::syntax::{
+    shared::{identifier::Identifier, syntax_type::SyntaxType},
+    tree_display::TreeDisplay,
+};
+
+use super::function::Function;
+
+/// The methods of a type, whose first parameter is the value they are called on: `self`. They
+/// may implement a trait, as in `impl Comparable for i32 { ... }`.
+#[derive(Clone, Debug)]
+pub struct Impl {
+    pub r#trait: Option<Identifier>,
+    pub r#type: SyntaxType,
+    pub functions: Vec<Function>,
+}
+
+impl Impl {
+    pub fn new(r#trait: Option<Identifier>, r#type: SyntaxType, functions: Vec<Function>) -> Self {
+        Self {
+            r#trait,
+            r#type,
+            functions,
+        }
+    }
+}
+
+impl TreeDisplay for Impl {
+    fn display(&self, layer: usize) {
+        match &self.r#trait {
+            Some(r#trait) => println!(
+                "{}Impl ({} for {})",
+                "  ".repeat(layer),
+                r#trait.name,
+                self.r#type.to_string()
+            ),
+            None => println!("{}Impl ({})", "  ".repeat(layer), self.r#type.to_string()),
+        }
+
+        for function in &self.functions {
+            function.display(layer + 1);
+        }
+    }
+}
@@ -1,2 +1,5 @@
+pub mod r#extern;
 pub mod function;
+pub mod r#impl;
 pub mod top_level_statement;
+pub mod r#trait;
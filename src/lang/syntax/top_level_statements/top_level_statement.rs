@@ -1,16 +1,25 @@
 use crate::lang::syntax::tree_display::TreeDisplay;
 
 use super::function::Function;
+use super::r#extern::Extern;
+use super::r#impl::Impl;
+use super::r#trait::Trait;
 
 #[derive(Clone, Debug)]
 pub enum TopLevelStatement {
     Function(Function),
+    Impl(Impl),
+    Trait(Trait),
+    Extern(Extern),
 }
 
 impl TreeDisplay for TopLevelStatement {
     fn display(&self, layer: usize) {
         match self {
             Self::Function(function) => function.display(layer),
+            Self::Impl(r#impl) => r#impl.display(layer),
+            Self::Trait(r#trait) => r#trait.display(layer),
+            Self::Extern(r#extern) => r#extern.display(layer),
         }
     }
 }
@@ -0,0 +1,70 @@
+use crate::lang::syntax::{
+    shared::{identifier::Identifier, syntax_type::SyntaxType},
+    tree_display::TreeDisplay,
+};
+
+use super::function::ParamsDeclaration;
+
+/// The signature of a method that the types implementing a trait must provide.
+#[derive(Clone, Debug)]
+pub struct Signature {
+    pub identifier: Identifier,
+    pub params_declaration: ParamsDeclaration,
+    pub r#type: Option<SyntaxType>,
+}
+
+impl Signature {
+    pub fn new(
+        identifier: Identifier,
+        params_declaration: ParamsDeclaration,
+        r#type: Option<SyntaxType>,
+    ) -> Self {
+        Self {
+            identifier,
+            params_declaration,
+            r#type,
+        }
+    }
+}
+
+impl TreeDisplay for Signature {
+    fn display(&self, layer: usize) {
+        match self.r#type.as_ref() {
+            Some(r#type) => println!(
+                "{}Signature ({}: {})",
+                "  ".repeat(layer),
+                self.identifier.name,
+                r#type.to_string()
+            ),
+            None => println!("{}Signature ({})", "  ".repeat(layer), self.identifier.name),
+        }
+
+        self.params_declaration.display(layer + 1);
+    }
+}
+
+/// The methods shared by the types that implement the trait, where `Self` stands for each type.
+#[derive(Clone, Debug)]
+pub struct Trait {
+    pub identifier: Identifier,
+    pub signatures: Vec<Signature>,
+}
+
+impl Trait {
+    pub fn new(identifier: Identifier, signatures: Vec<Signature>) -> Self {
+        Self {
+            identifier,
+            signatures,
+        }
+    }
+}
+
+impl TreeDisplay for Trait {
+    fn display(&self, layer: usize) {
+        println!("{}Trait ({})", "  ".repeat(layer), self.identifier.name);
+
+        for signature in &self.signatures {
+            signature.display(layer + 1);
+        }
+    }
+}
@@ -11,6 +11,7 @@ fn main() {
     parser.add_option("-f", "--file");
     parser.add_option("-v", "--verbose");
     parser.add_option("-r", "--repl");
+    parser.add_option("-D", "--deny-warnings");
 
     let args = std::env::args().collect::<Vec<String>>();
     let options = parser.parse(&args);
@@ -24,7 +25,10 @@ fn main() {
 
         match res {
             Ok(_) => {}
-            Err(error) => eprintln!("{}", error),
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
         }
     }
 }
@@ -32,7 +36,8 @@ fn main() {
 fn compile(options: &ParsedOptions) -> Result<(), String> {
     let file_path = options.get("--compile").unwrap();
 
-    let compiler = Compiler::from_file(file_path).unwrap();
+    let compiler =
+        Compiler::from_file(file_path)?.with_deny_warnings(options.has("--deny-warnings"));
     compiler.compile()?;
 
     Ok(())